@@ -0,0 +1,84 @@
+//! How long parsing a batch of ingredient lines takes, e.g. `cargo bench -p ingredient`.
+//! `name, many1` is how names were captured before they were taken as one slice,
+//! a `String` per character, joined.
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use ingredient::IngredientParser;
+use nom::{bytes::complete::take_while1, character::complete::satisfy, multi::many1, IResult};
+
+const LINES: &[&str] = &[
+    "1 cup (125.5 grams) all-purpose flour, sifted",
+    "2 tablespoons unsalted butter, melted and cooled",
+    "1 tablespoon plus 1 teaspoon extra virgin olive oil",
+    "3 large eggs, at room temperature",
+    "1/2 teaspoon fine sea salt, plus more to taste",
+    "2 x 400 g cans whole peeled tomatoes",
+    "zest of 1 lemon, finely grated",
+    "1-2 cups low sodium chicken or vegetable stock",
+    "freshly ground black pepper",
+    "1 ½ cups granulated sugar (300 grams)",
+];
+
+// same as the parser's
+fn is_name_char(c: char) -> bool {
+    match c {
+        '-' | '—' | '\'' | '’' | '.' | '\\' => true,
+        c => c.is_alphanumeric() || c.is_whitespace(),
+    }
+}
+
+fn many1_name(input: &str) -> IResult<&str, String> {
+    many1(|i| satisfy(is_name_char)(i).map(|(i, c)| (i, c.to_string())))(input)
+        .map(|(i, chunks)| (i, chunks.join("").trim_matches(' ').to_string()))
+}
+
+fn slice_name(input: &str) -> IResult<&str, String> {
+    take_while1(is_name_char)(input)
+        .map(|(i, s): (&str, &str)| (i, s.trim_matches(' ').to_string()))
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let ip = IngredientParser::new(false);
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Elements(LINES.len() as u64));
+    group.bench_function("parse_ingredient", |b| {
+        b.iter(|| {
+            for l in LINES {
+                black_box(ip.clone().parse_ingredient(black_box(l)).unwrap());
+            }
+        })
+    });
+    group.bench_function("parse_ingredient_fast", |b| {
+        b.iter(|| {
+            for l in LINES {
+                black_box(ip.clone().parse_ingredient_fast(black_box(l)).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_name(c: &mut Criterion) {
+    let names: Vec<&str> = LINES
+        .iter()
+        .map(|l| l.trim_start_matches(|c: char| !c.is_alphabetic()))
+        .collect();
+    let mut group = c.benchmark_group("name");
+    group.bench_function("many1", |b| {
+        b.iter(|| {
+            for n in &names {
+                black_box(many1_name(black_box(n)).unwrap());
+            }
+        })
+    });
+    group.bench_function("slice", |b| {
+        b.iter(|| {
+            for n in &names {
+                black_box(slice_name(black_box(n)).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_name);
+criterion_main!(benches);
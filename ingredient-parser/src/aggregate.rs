@@ -0,0 +1,166 @@
+//! Combining ingredient lists, e.g. for a shopping list across several recipes.
+use crate::unit::Measure;
+use crate::Ingredient;
+
+/// Merges ingredients with the same [Ingredient::normalized_name], summing amounts
+/// of the same kind. Amounts that can't be summed are kept as separate entries
+/// next to each other, since an ingredient's amounts are equivalents rather than parts.
+/// ```
+/// use ingredient::{aggregate, from_str, unit::Measure};
+/// let list = aggregate(vec![
+///     from_str("1 cup flour"),
+///     from_str("2 tbsp sugar"),
+///     from_str("4 tbsp flour"),
+///     from_str("200 g Fresh flour"),
+/// ]);
+/// assert_eq!(list.len(), 3);
+/// assert_eq!(list[0].amounts, vec![Measure::parse_new("cup", 1.25)]);
+/// assert_eq!(list[1].amounts, vec![Measure::parse_new("g", 200.0)]);
+/// assert_eq!(list[2].name, "sugar");
+/// ```
+pub fn aggregate(ingredients: Vec<Ingredient>) -> Vec<Ingredient> {
+    aggregate_with_mappings(ingredients, vec![])
+}
+
+/// Same as [aggregate], but also converts between kinds using `mappings`
+/// (see [Measure::convert_measure_via_mappings]), so that `350 g` and `1 cup`
+/// of flour can be summed given how much a cup of flour weighs.
+pub fn aggregate_with_mappings(
+    ingredients: Vec<Ingredient>,
+    mappings: Vec<(Measure, Measure)>,
+) -> Vec<Ingredient> {
+    let mut merged: Vec<(String, Ingredient)> = vec![];
+    'ingredients: for ingredient in ingredients {
+        let key = ingredient.normalized_name();
+        let mut last = None;
+        for (idx, (k, existing)) in merged.iter_mut().enumerate() {
+            if *k != key {
+                continue;
+            }
+            last = Some(idx);
+            if let Some(amounts) = add_amounts(&existing.amounts, &ingredient.amounts, &mappings) {
+                existing.amounts = amounts;
+                if existing.modifier != ingredient.modifier {
+                    existing.modifier = None;
+                }
+                continue 'ingredients;
+            }
+        }
+        // an amount of another kind, after the entries for the same ingredient
+        match last {
+            Some(idx) => merged.insert(idx + 1, (key, ingredient)),
+            None => merged.push((key, ingredient)),
+        }
+    }
+    merged.into_iter().map(|(_, i)| i).collect()
+}
+
+// `totals` plus `amounts`, each a list of equivalents like `1 cup / 120 g`, or `None`
+// when none of them can be summed. Only the totals that `amounts` has a counterpart
+// for are kept, the others would no longer be equivalent.
+fn add_amounts(
+    totals: &[Measure],
+    amounts: &[Measure],
+    mappings: &[(Measure, Measure)],
+) -> Option<Vec<Measure>> {
+    // there's nothing to add, or nothing to add it to yet
+    if totals.is_empty() || amounts.is_empty() {
+        return Some([totals, amounts].concat());
+    }
+    let sums: Vec<Measure> = totals
+        .iter()
+        .filter_map(|total| {
+            amounts
+                .iter()
+                .find_map(|m| sum_measures(total, m, mappings))
+        })
+        .collect();
+    match sums.is_empty() {
+        true => None,
+        false => Some(sums),
+    }
+}
+
+fn sum_measures(total: &Measure, m: &Measure, mappings: &[(Measure, Measure)]) -> Option<Measure> {
+    let kind = total.kind().ok()?;
+    let m = match m.kind().ok()? == kind {
+        true => m.clone(),
+        false => m.convert_measure_via_mappings(kind, mappings.to_vec())?,
+    };
+    total
+        .normalize()
+        .checked_add(&m)
+        .ok()
+        .map(Measure::denormalize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_str;
+
+    #[test]
+    fn test_aggregate_with_mappings() {
+        let list = aggregate_with_mappings(
+            vec![from_str("350 g flour"), from_str("1 cup flour, sifted")],
+            vec![(Measure::parse_str("1 cup"), Measure::parse_str("120 g"))],
+        );
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].amounts, vec![Measure::parse_new("g", 470.0)]);
+        assert_eq!(list[0].modifier, None);
+    }
+    #[test]
+    fn test_aggregate_counts() {
+        let list = aggregate(vec![
+            from_str("2 cloves garlic"),
+            from_str("1 clove garlic"),
+            from_str("1 head garlic"),
+        ]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].amounts, vec![Measure::parse_new("clove", 3.0)]);
+        assert_eq!(list[1].amounts, vec![Measure::parse_new("head", 1.0)]);
+    }
+    #[test]
+    fn test_aggregate_equivalents() {
+        // the 120 g is no longer what 2 cups weigh
+        let list = aggregate(vec![
+            from_str("1 cup / 120 g flour"),
+            from_str("1 cup flour"),
+        ]);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].amounts, vec![Measure::parse_new("cup", 2.0)]);
+
+        let list = aggregate(vec![
+            from_str("1 cup / 120 g flour"),
+            from_str("2 tbsp / 15 g flour"),
+        ]);
+        assert_eq!(
+            list[0].amounts,
+            vec![
+                Measure::parse_new("cup", 1.125),
+                Measure::parse_new("g", 135.0)
+            ]
+        );
+
+        // cups and grams are added to separately, not equivalents of each other
+        let list = aggregate(vec![
+            from_str("1 cup flour"),
+            from_str("200 g flour"),
+            from_str("1 cup flour"),
+            from_str("50 g flour"),
+        ]);
+        assert_eq!(
+            list.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            vec!["2 cups flour", "250 g flour"]
+        );
+
+        let list = aggregate(vec![
+            from_str("1 cup flour"),
+            from_str("100 g / 0.75 cup flour"),
+            from_str("salt"),
+            from_str("1 tsp salt"),
+        ]);
+        assert_eq!(list[0].amounts, vec![Measure::parse_new("cup", 1.75)]);
+        assert_eq!(list[1].amounts, vec![Measure::parse_new("tsp", 1.0)]);
+    }
+}
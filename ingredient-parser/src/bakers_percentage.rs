@@ -0,0 +1,113 @@
+//! Baker's percentages, where each ingredient's weight is relative to the total flour weight.
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::space0,
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+use crate::fraction::decimal;
+use crate::unit::MeasureKind;
+use crate::Ingredient;
+
+/// Pulls a `(100%)` or `(62% hydration)` annotation out of `s`, returning the
+/// percentage and what follows it, like `hydration`, along with the rest of the text.
+/// ```
+/// use ingredient::bakers_percentage::take_percentage;
+/// assert_eq!(
+///     take_percentage("(62% hydration)"),
+///     Some((62.0, Some("hydration".to_string()), "".to_string()))
+/// );
+/// assert_eq!(take_percentage("sifted (2%)"), Some((2.0, None, "sifted".to_string())));
+/// assert_eq!(take_percentage("sifted"), None);
+/// ```
+pub fn take_percentage(s: &str) -> Option<(f64, Option<String>, String)> {
+    s.match_indices('(').find_map(|(idx, _)| {
+        let (rest, (value, label)) = percentage(&s[idx..]).ok()?;
+        let before = s[..idx].trim().trim_end_matches(',');
+        let after = rest.trim().trim_start_matches(',').trim_start();
+        let remaining = [before, after]
+            .iter()
+            .filter(|p| !p.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(", ");
+        let label = Some(label.trim().to_string()).filter(|l| !l.is_empty());
+        Some((value, label, remaining))
+    })
+}
+
+fn percentage(input: &str) -> IResult<&str, (f64, &str)> {
+    let (rest, ((value, _), label)) = delimited(
+        tag("("),
+        tuple((
+            tuple((delimited(space0, decimal, space0), tag("%"))),
+            take_until(")"),
+        )),
+        tag(")"),
+    )(input)?;
+    Ok((rest, (value, label)))
+}
+
+/// Derives baker's percentages from the weights of `ingredients`, where every
+/// ingredient whose [Ingredient::normalized_name] contains `flour_key` counts towards
+/// the 100%. Ingredients without a weight, or a recipe without any flour weight, get `None`.
+/// ```
+/// use ingredient::{bakers_percentage::compute_percentages, from_str};
+/// let dough = vec![
+///     from_str("400 g bread flour"),
+///     from_str("100 g whole wheat flour"),
+///     from_str("350 g water"),
+///     from_str("10 g salt"),
+///     from_str("1 pinch sugar"),
+/// ];
+/// assert_eq!(
+///     compute_percentages(&dough, "flour"),
+///     vec![Some(80.0), Some(20.0), Some(70.0), Some(2.0), None]
+/// );
+/// ```
+pub fn compute_percentages(ingredients: &[Ingredient], flour_key: &str) -> Vec<Option<f64>> {
+    let weights: Vec<Option<f64>> = ingredients.iter().map(grams).collect();
+    let flour: f64 = ingredients
+        .iter()
+        .zip(weights.iter())
+        .filter(|(i, _)| i.normalized_name().contains(flour_key))
+        .filter_map(|(_, w)| *w)
+        .sum();
+    weights
+        .into_iter()
+        .map(|w| match flour > 0.0 {
+            true => w.map(|w| (w / flour * 1000.0).round() / 10.0),
+            false => None,
+        })
+        .collect()
+}
+
+fn grams(i: &Ingredient) -> Option<f64> {
+    i.amounts
+        .iter()
+        .find(|m| matches!(m.kind(), Ok(MeasureKind::Weight)))
+        .map(|m| m.normalize().values().0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_percentage() {
+        assert_eq!(
+            take_percentage("( 75 % )"),
+            Some((75.0, None, "".to_string()))
+        );
+        assert_eq!(take_percentage("(about 2 tsp)"), None);
+        assert_eq!(
+            take_percentage("sifted (2%), fine"),
+            Some((2.0, None, "sifted, fine".to_string()))
+        );
+        assert_eq!(
+            take_percentage("(see note) (1.5%)"),
+            Some((1.5, None, "(see note)".to_string()))
+        );
+    }
+}
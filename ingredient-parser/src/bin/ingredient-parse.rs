@@ -0,0 +1,114 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use clap::{Parser, ValueEnum};
+use ingredient::{locale::Locale, Ingredient, IngredientParser};
+
+/// Parses ingredient lines, one per line, from a file or stdin.
+#[derive(Parser)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    /// file to read, stdin if omitted
+    file: Option<String>,
+    #[arg(short, long, value_enum, default_value_t = Format::Pretty)]
+    format: Format,
+    /// fail on lines which don't have any amounts
+    #[arg(short, long)]
+    strict: bool,
+    /// language of the input, e.g. `de`
+    #[arg(short, long, value_parser = Locale::from_str)]
+    locale: Option<Locale>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
+    Pretty,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let reader: Box<dyn BufRead> = match &cli.file {
+        Some(path) => match File::open(path) {
+            Ok(f) => Box::new(BufReader::new(f)),
+            Err(e) => {
+                eprintln!("failed to open {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Box::new(io::stdin().lock()),
+    };
+    let ip = IngredientParser::new(false).with_locale(cli.locale.unwrap_or_default());
+
+    if let Format::Csv = cli.format {
+        println!("input,name,amounts,modifier");
+    }
+    let mut failed = false;
+    for (n, line) in reader.lines().enumerate() {
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("failed to read line {}: {e}", n + 1);
+                return ExitCode::FAILURE;
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let i = ip.clone().from_str(line);
+        if cli.strict && i.amounts.is_empty() {
+            eprintln!("line {}: no amounts found in '{line}'", n + 1);
+            failed = true;
+            continue;
+        }
+        match cli.format {
+            Format::Json => println!("{}", serde_json::to_string(&i).unwrap()),
+            Format::Csv => println!("{}", csv_row(line, &i)),
+            Format::Pretty => println!("{}", pretty(&i)),
+        }
+    }
+    match failed {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+fn csv_row(input: &str, i: &Ingredient) -> String {
+    let amounts: Vec<String> = i.amounts.iter().map(|a| a.to_string()).collect();
+    [
+        input,
+        &i.name,
+        &amounts.join(" / "),
+        i.modifier.as_deref().unwrap_or_default(),
+    ]
+    .iter()
+    .map(|f| match f.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", f.replace('"', "\"\"")),
+        false => f.to_string(),
+    })
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+// same colors as food_ui: amounts in yellow, name in blue, modifier in gray
+fn pretty(i: &Ingredient) -> String {
+    let amounts: Vec<String> = i.amounts.iter().map(|a| a.to_string()).collect();
+    let amount_list = match amounts.len() {
+        0 => "n/a ".to_string(),
+        _ => format!("{} ", amounts.join(" / ")),
+    };
+    let modifier = match &i.modifier {
+        Some(m) => format!(", {m}"),
+        None => "".to_string(),
+    };
+    format!(
+        "\x1b[33m{amount_list}\x1b[0m\x1b[94m{}\x1b[0m\x1b[90m{modifier}\x1b[0m",
+        i.name
+    )
+}
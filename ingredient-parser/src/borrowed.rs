@@ -0,0 +1,200 @@
+//! A borrowed variant of [Ingredient], for bulk parsing where the
+//! per-field `String` allocations add up.
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::{not_line_ending, space0},
+    combinator::{consumed, opt},
+    error::context,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::{
+    combine_amounts, footnote_marker, is_name_char,
+    unit::{Measure, Unit},
+    Ingredient, IngredientParser, Multiplier, ParseErr, Res,
+};
+
+/// Like [Ingredient], but with the name, modifier and footnote borrowed from the input.
+/// The amounts are read with the same grammar as [IngredientParser::parse_ingredient],
+/// so they're the same [Measure]s.
+///
+/// Adjectives are not moved out of the name, since that requires building a new string,
+/// nor are notes, alternatives or usage flags pulled out of the modifier.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct IngredientRef<'a> {
+    pub name: &'a str,
+    pub amounts: Vec<AmountRef<'a>>,
+    pub modifier: Option<&'a str>,
+    pub footnote: Option<&'a str>,
+    pub multiplier: Option<Multiplier>,
+}
+
+/// An amount of an [IngredientRef], with its unit borrowed from the input as it was written,
+/// e.g. `Tbsp` for `2 Tbsp. butter`, or `""` when it wasn't (`1 egg`).
+#[derive(Clone, PartialEq, Debug)]
+pub struct AmountRef<'a> {
+    pub unit: &'a str,
+    pub measure: Measure,
+}
+
+impl IngredientRef<'_> {
+    pub fn to_ingredient(&self) -> Ingredient {
+        Ingredient {
+            name: self.name.to_string(),
+            amounts: self.amounts.iter().map(|a| a.measure.clone()).collect(),
+            modifier: self.modifier.map(str::to_string),
+            footnote: self.footnote.map(str::to_string),
+            multiplier: self.multiplier.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IngredientParser {
+    /// Zero-copy version of [IngredientParser::parse_ingredient].
+    /// ```
+    /// use ingredient::{unit::Measure, IngredientParser};
+    /// let (_, i) = IngredientParser::new(false)
+    ///     .parse_ingredient_ref("1 cup / 120 grams flour, sifted")
+    ///     .unwrap();
+    /// assert_eq!(i.name, "flour");
+    /// assert_eq!(i.modifier, Some("sifted"));
+    /// assert_eq!(i.amounts[1].unit, "grams");
+    /// assert_eq!(i.amounts[1].measure, Measure::parse_new("grams", 120.0));
+    /// ```
+    pub fn parse_ingredient_ref<'a>(&self, input: &'a str) -> Res<&'a str, IngredientRef<'a>> {
+        self.parse_ingredient_ref_generic(input)
+    }
+
+    /// Same as [IngredientParser::parse_ingredient_ref], but without building up a
+    /// [nom::error::VerboseError], like [IngredientParser::parse_ingredient_fast].
+    pub fn parse_ingredient_ref_fast<'a>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, IngredientRef<'a>> {
+        self.parse_ingredient_ref_generic(input)
+    }
+
+    fn parse_ingredient_ref_generic<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, IngredientRef<'a>, E> {
+        context(
+            "ing_ref",
+            tuple((
+                consumed(opt(|a| self.multiplied_amounts(a))),
+                space0,
+                opt(take_while1(is_name_char)),
+                opt(footnote_marker),
+                consumed(opt(|a| self.amt_parens(a))),
+                opt(tag(", ")),
+                not_line_ending,
+            )),
+        )(input)
+        .map(|(next_input, res)| {
+            let ((written, amounts), _, name, footnote, (written2, amounts2), _, modifier) = res;
+            let (multiplier, amounts) = combine_amounts(amounts, amounts2);
+            let mut units = self.written_units::<E>(written);
+            units.extend(self.written_units::<E>(written2));
+            let amounts = amounts
+                .into_iter()
+                .map(|measure| {
+                    let unit = match units.iter().position(|(_, u)| *u == measure.unit()) {
+                        Some(i) if !measure.inferred_unit() => units.remove(i).0,
+                        _ => "",
+                    };
+                    AmountRef { unit, measure }
+                })
+                .collect();
+            (
+                next_input,
+                IngredientRef {
+                    name: name.unwrap_or_default().trim_matches(' '),
+                    amounts,
+                    modifier: match modifier {
+                        "" => None,
+                        m => Some(m),
+                    },
+                    footnote,
+                    multiplier,
+                },
+            )
+        })
+    }
+
+    // the units written in some amounts, in order, along with the unit each is read as
+    fn written_units<'a, E: ParseErr<'a>>(&self, amounts: &'a str) -> Vec<(&'a str, Unit)> {
+        let mut found = vec![];
+        let mut rest = amounts;
+        let mut prev: Option<char> = None;
+        while let Some(c) = rest.chars().next() {
+            // units start a word, but can follow a number, `167ml`
+            if !prev.is_some_and(char::is_alphabetic) {
+                if let Ok((next, unit)) = self.unit::<E>(rest) {
+                    let written = &rest[..rest.len() - next.len()];
+                    let unit = Measure::from_parts(&unit.to_lowercase(), 1.0, None).unit();
+                    found.push((written, unit));
+                    prev = written.chars().last();
+                    rest = next;
+                    continue;
+                }
+            }
+            prev = Some(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ingredient_ref() {
+        let ip = IngredientParser::new(false);
+        for input in [
+            "1¼  cups / 155.5 grams flour",
+            "2-3 cups flour, sifted",
+            "⅔ cup (167ml) cold water",
+            "1 egg",
+            "egg",
+            "1 tsp 5-spice powder",
+            "1 tablespoon plus 1 teaspoon olive oil",
+            "1 cup plus 2 cloves garlic",
+            "2 x 400 g cans tomatoes",
+            "2 to 4 days",
+            "up to 10 minutes",
+            "1 cup = 240 ml milk",
+            "1 cup flour*, sifted",
+        ] {
+            let (rest, i) = ip.parse_ingredient_ref(input).unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(i.to_ingredient(), ip.from_str(input), "{input}");
+        }
+        assert_eq!(
+            ip.parse_ingredient_ref("2 Tbsp. butter (melted)"),
+            Ok((
+                "",
+                IngredientRef {
+                    name: "butter",
+                    amounts: vec![AmountRef {
+                        unit: "Tbsp",
+                        measure: Measure::parse_new("tbsp", 2.0)
+                    }],
+                    modifier: Some("(melted)"),
+                    ..Default::default()
+                }
+            ))
+        );
+        let units = |input| {
+            let (_, i) = ip.parse_ingredient_ref_fast(input).unwrap();
+            i.amounts.iter().map(|a| a.unit).collect::<Vec<_>>()
+        };
+        assert_eq!(units("⅔ cup (167ml) cold water"), vec!["cup", "ml"]);
+        assert_eq!(units("1¼  cups / 155.5 grams flour"), vec!["cups", "grams"]);
+        assert_eq!(units("2 x 400 g cans tomatoes"), vec!["g", "cans"]);
+        assert_eq!(units("1 egg"), vec![""]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{locale::Locale, IngredientParser};
+
+/// How [IngredientParser::parse] treats input it can't fully make sense of.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ParseMode {
+    /// anything after what could be parsed is ignored
+    #[default]
+    Default,
+    /// text left over after the ingredient, or numbers in the name, are an error
+    Strict,
+    /// never fails, an unparseable line becomes the name
+    Lenient,
+}
+
+/// Builds an [IngredientParser], starting from the defaults of [IngredientParser::new].
+/// ```
+/// use ingredient::{IngredientParser, ParseMode, locale::Locale};
+/// let ip = IngredientParser::builder()
+///     .mode(ParseMode::Strict)
+///     .locale(Locale::De)
+///     .units(["knob"])
+///     .build();
+/// assert_eq!(ip.parse("1 knob butter").unwrap().to_string(), "1 knob butter");
+/// assert!(ip.parse("1 cup flour 2").is_err());
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct IngredientParserBuilder {
+    parser: IngredientParser,
+}
+
+impl Default for IngredientParserBuilder {
+    fn default() -> Self {
+        IngredientParserBuilder {
+            parser: IngredientParser::new(false),
+        }
+    }
+}
+
+impl IngredientParserBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Whether the input is free text (a recipe step) rather than an ingredient line.
+    pub fn rich_text(mut self, is_rich_text: bool) -> Self {
+        self.parser.is_rich_text = is_rich_text;
+        self
+    }
+    /// Include nom's trace of what was tried in [IngredientParser::parse] errors.
+    /// Slower, since the trace is built up while parsing.
+    pub fn verbose_errors(mut self, verbose_errors: bool) -> Self {
+        self.parser.verbose_errors = verbose_errors;
+        self
+    }
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.parser.mode = mode;
+        self
+    }
+    /// See [IngredientParser::with_locale].
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.parser = self.parser.with_locale(locale);
+        self
+    }
+    /// See [IngredientParser::with_units].
+    pub fn units<'a>(mut self, units: impl IntoIterator<Item = &'a str>) -> Self {
+        self.parser = self.parser.with_units(units);
+        self
+    }
+    /// See [IngredientParser::with_adjectives].
+    pub fn adjectives<'a>(mut self, adjectives: impl IntoIterator<Item = &'a str>) -> Self {
+        self.parser = self.parser.with_adjectives(adjectives);
+        self
+    }
+    /// The unit for amounts that don't have one, `None` to leave it empty.
+    /// See [IngredientParser::bare_count_unit].
+    pub fn bare_count_unit(mut self, unit: Option<&str>) -> Self {
+        self.parser = match unit {
+            Some(u) => self.parser.with_bare_count_unit(u),
+            None => self.parser.without_bare_count_unit(),
+        };
+        self
+    }
+    pub fn build(self) -> IngredientParser {
+        self.parser
+    }
+}
@@ -0,0 +1,172 @@
+//! Grocery store sections, for grouping a shopping list by aisle.
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::{normalize::singular_name, Ingredient};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub enum Category {
+    Produce,
+    Dairy,
+    Meat,
+    Seafood,
+    Pantry,
+    Spice,
+    Bakery,
+    Frozen,
+    Beverage,
+    Other,
+}
+
+#[rustfmt::skip]
+const DEFAULT_KEYWORDS: &[(Category, &[&str])] = &[
+    (Category::Produce, &[
+        "onion", "garlic", "shallot", "scallion", "leek", "carrot", "celery", "potato", "tomato",
+        "lettuce", "spinach", "kale", "cabbage", "broccoli", "cauliflower", "pepper", "jalapeño",
+        "cucumber", "zucchini", "squash", "mushroom", "avocado", "lemon", "lime", "orange",
+        "apple", "banana", "berries", "strawberries", "blueberries", "ginger", "parsley",
+        "cilantro", "basil", "mint", "thyme", "rosemary", "dill", "chives",
+    ]),
+    (Category::Dairy, &[
+        "milk", "butter", "cream", "heavy cream", "sour cream", "yogurt", "cheese", "parmesan",
+        "mozzarella", "cheddar", "ricotta", "buttermilk", "crème fraîche", "egg", "eggs",
+    ]),
+    (Category::Meat, &[
+        "chicken", "beef", "pork", "bacon", "sausage", "lamb", "turkey", "ham", "prosciutto",
+        "ground beef", "steak",
+    ]),
+    (Category::Seafood, &[
+        "salmon", "tuna", "shrimp", "cod", "anchovies", "anchovy", "scallops", "mussels",
+        "clams", "crab", "fish",
+    ]),
+    (Category::Pantry, &[
+        "flour", "sugar", "brown sugar", "rice", "pasta", "oats", "oil", "olive oil", "vinegar",
+        "honey", "maple syrup", "baking soda", "baking powder", "yeast", "vanilla extract",
+        "cocoa powder", "chocolate", "chocolate chips", "beans", "chickpeas", "lentils",
+        "stock", "broth", "soy sauce", "coconut milk", "peanut butter", "nuts", "almonds",
+        "walnuts", "cornstarch", "breadcrumbs", "water",
+    ]),
+    (Category::Spice, &[
+        "salt", "kosher salt", "black pepper", "cumin", "paprika", "cinnamon", "nutmeg",
+        "oregano", "chili powder", "cayenne", "turmeric", "cloves", "bay leaf", "bay leaves",
+        "red pepper flakes", "coriander", "cardamom", "allspice",
+    ]),
+    (Category::Bakery, &["bread", "baguette", "tortillas", "pita", "buns", "rolls"]),
+    (Category::Frozen, &["frozen", "ice cream", "frozen peas"]),
+    (Category::Beverage, &["wine", "beer", "coffee", "tea", "juice", "sparkling water"]),
+];
+
+/// Keywords to [Category], overridable per application.
+/// The longest keyword found in the name wins, so `coconut milk` is pantry rather than dairy.
+/// Words match whether they're singular or plural.
+/// ```
+/// use ingredient::{category::{Category, CategoryMap}, from_str};
+/// assert_eq!(from_str("2 large eggs").category(), Category::Dairy);
+/// assert_eq!(from_str("1 can coconut milk").category(), Category::Pantry);
+///
+/// let mine = CategoryMap::default().with("eggs", Category::Meat);
+/// assert_eq!(from_str("2 large eggs").category_with(&mine), Category::Meat);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct CategoryMap {
+    keywords: HashMap<String, Category>,
+}
+
+impl Default for CategoryMap {
+    fn default() -> Self {
+        CategoryMap {
+            keywords: DEFAULT_KEYWORDS
+                .iter()
+                .flat_map(|(c, words)| words.iter().map(move |w| (singular_words(w), *c)))
+                .collect(),
+        }
+    }
+}
+
+impl CategoryMap {
+    /// Without any of the default keywords.
+    pub fn empty() -> Self {
+        CategoryMap {
+            keywords: HashMap::new(),
+        }
+    }
+    /// Adds or replaces a keyword.
+    pub fn with(mut self, keyword: &str, category: Category) -> Self {
+        self.keywords
+            .insert(singular_words(&keyword.to_lowercase()), category);
+        self
+    }
+    /// Categorizes a [Ingredient::normalized_name].
+    pub fn categorize(&self, normalized_name: &str) -> Category {
+        let words = singular_words(normalized_name);
+        let words: Vec<&str> = words.split_whitespace().collect();
+        self.keywords
+            .iter()
+            .filter(|(k, _)| {
+                let k: Vec<&str> = k.split_whitespace().collect();
+                !k.is_empty() && words.windows(k.len()).any(|w| w == k.as_slice())
+            })
+            .max_by_key(|(k, _)| (k.len(), k.as_str()))
+            .map_or(Category::Other, |(_, c)| *c)
+    }
+}
+
+impl Ingredient {
+    /// The grocery [Category], from the default [CategoryMap].
+    pub fn category(&self) -> Category {
+        static DEFAULT: OnceLock<CategoryMap> = OnceLock::new();
+        self.category_with(DEFAULT.get_or_init(CategoryMap::default))
+    }
+    pub fn category_with(&self, map: &CategoryMap) -> Category {
+        map.categorize(&self.normalized_name())
+    }
+}
+
+// each word singular, so `blueberry` is found in `blueberries`
+fn singular_words(s: &str) -> String {
+    s.split_whitespace()
+        .map(singular_name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categorize() {
+        let m = CategoryMap::default();
+        assert_eq!(m.categorize("boneless chicken thighs"), Category::Meat);
+        assert_eq!(m.categorize("black pepper"), Category::Spice);
+        assert_eq!(m.categorize("red bell pepper"), Category::Produce);
+        assert_eq!(m.categorize("unobtainium"), Category::Other);
+        // whole words only
+        assert_eq!(m.categorize("hamachi"), Category::Other);
+        assert_eq!(CategoryMap::empty().categorize("salt"), Category::Other);
+    }
+    #[test]
+    fn test_categorize_plurals() {
+        use crate::from_str;
+        for line in [
+            "3 carrots",
+            "2 tomatoes",
+            "2 lemons",
+            "2 apples",
+            "2 potatoes",
+            "onions",
+            "bananas",
+            "1 cup blueberry",
+            "1 cup blueberries",
+        ] {
+            assert_eq!(from_str(line).category(), Category::Produce, "{}", line);
+        }
+        assert_eq!(from_str("2 whole cloves").category(), Category::Spice);
+        assert_eq!(
+            from_str("1 tsp red pepper flake").category(),
+            Category::Spice
+        );
+        let mine = CategoryMap::empty().with("Tortilla Chips", Category::Pantry);
+        assert_eq!(mine.categorize("tortilla chip"), Category::Pantry);
+    }
+}
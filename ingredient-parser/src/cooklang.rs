@@ -0,0 +1,151 @@
+//! Interop with [Cooklang](https://cooklang.org)'s `@name{qty%unit}` ingredient syntax.
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::{take_till1, take_until, take_while1},
+    character::complete::char,
+    combinator::{map, opt},
+    error::{context, VerboseError},
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+
+use crate::{unit::Measure, util::num_without_zeroes, Ingredient, IngredientParser, ParseErr};
+
+impl IngredientParser {
+    /// Parses a single Cooklang ingredient token.
+    /// ```
+    /// use ingredient::{IngredientParser, unit::Measure};
+    /// let ip = IngredientParser::new(false);
+    /// let i = ip.parse_cooklang("@ground black pepper{1/2%tsp}").unwrap();
+    /// assert_eq!(i.name, "ground black pepper");
+    /// assert_eq!(i.amounts, vec![Measure::parse_new("tsp", 0.5)]);
+    /// assert_eq!(ip.parse_cooklang("@salt").unwrap().name, "salt");
+    /// ```
+    pub fn parse_cooklang(&self, input: &str) -> Result<Ingredient> {
+        match self.cooklang_token::<VerboseError<&str>>(input) {
+            Ok((_, i)) => Ok(i),
+            Err(e) => Err(anyhow::anyhow!(
+                "parse_cooklang on '{}' failed: {:?}",
+                input,
+                e
+            )),
+        }
+    }
+
+    /// Every Cooklang ingredient mentioned in a step, e.g.
+    /// `Crack @eggs{2} into a bowl, add @salt` has `eggs` and `salt`.
+    pub fn cooklang_ingredients(&self, step: &str) -> Vec<Ingredient> {
+        step.match_indices('@')
+            .filter_map(|(idx, _)| {
+                self.clone()
+                    .cooklang_token::<VerboseError<&str>>(&step[idx..])
+                    .ok()
+                    .map(|(_, i)| i)
+            })
+            .collect()
+    }
+
+    fn cooklang_token<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, Ingredient, E> {
+        // multi-word names need the braces, single words don't
+        let with_braces = tuple((
+            take_till1(|c: char| matches!(c, '{' | '@' | '#' | '~' | '\n')),
+            map(delimited(char('{'), take_until("}"), char('}')), Some),
+        ));
+        let single_word = tuple((
+            take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-'),
+            |a| Ok((a, None)),
+        ));
+        context(
+            "cooklang_token",
+            tuple((
+                preceded(char('@'), alt((with_braces, single_word))),
+                opt(delimited(char('('), take_until(")"), char(')'))),
+            )),
+        )(input)
+        .map(|(next_input, ((name, quantity), modifier))| {
+            let amounts = match quantity.map(|q: &str| q.trim()) {
+                None | Some("") => vec![],
+                Some(q) => {
+                    let (value, unit) = q.split_once('%').unwrap_or((q, ""));
+                    self.parse_amount(&format!("{} {}", value.trim(), unit.trim()))
+                        .unwrap_or_default()
+                }
+            };
+            (
+                next_input,
+                Ingredient {
+                    name: name.trim().to_string(),
+                    amounts,
+                    modifier: modifier.map(|m| m.trim().to_string()),
+                    ..Default::default()
+                },
+            )
+        })
+    }
+}
+
+impl Ingredient {
+    /// Renders as a Cooklang token, using the first amount.
+    /// ```
+    /// use ingredient::from_str;
+    /// assert_eq!(from_str("2 cups flour, sifted").to_cooklang(), "@flour{2%cups}(sifted)");
+    /// assert_eq!(from_str("3 eggs").to_cooklang(), "@eggs{3}");
+    /// assert_eq!(from_str("salt").to_cooklang(), "@salt");
+    /// assert_eq!(from_str("olive oil").to_cooklang(), "@olive oil{}");
+    /// ```
+    pub fn to_cooklang(&self) -> String {
+        let quantity = self.amounts.first().map(cooklang_quantity);
+        let mut s = match quantity {
+            None if !self.name.contains(char::is_whitespace) => format!("@{}", self.name),
+            q => format!("@{}{{{}}}", self.name, q.unwrap_or_default()),
+        };
+        if let Some(m) = &self.modifier {
+            s.push_str(&format!("({m})"));
+        }
+        s
+    }
+}
+
+fn cooklang_quantity(m: &Measure) -> String {
+    let (value, _, unit) = m.values();
+    let value = num_without_zeroes(value);
+    match m.inferred_unit() || unit.is_empty() {
+        true => value,
+        false => format!("{value}%{unit}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooklang_ingredients() {
+        let ip = IngredientParser::new(false);
+        let found = ip.cooklang_ingredients(
+            "Crack @eggs{2} into a bowl, whisk in @milk{250%ml} and @onion{1}(finely chopped).",
+        );
+        assert_eq!(
+            found.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(),
+            vec!["eggs", "milk", "onion"]
+        );
+        assert_eq!(found[1].amounts, vec![Measure::parse_new("ml", 250.0)]);
+        assert_eq!(found[2].modifier, Some("finely chopped".to_string()));
+    }
+    #[test]
+    fn test_round_trip() {
+        let ip = IngredientParser::new(false);
+        for token in [
+            "@flour{2%cups}(sifted)",
+            "@eggs{3}",
+            "@salt",
+            "@olive oil{}",
+        ] {
+            assert_eq!(ip.parse_cooklang(token).unwrap().to_cooklang(), token);
+        }
+    }
+}
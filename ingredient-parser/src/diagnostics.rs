@@ -0,0 +1,126 @@
+//! Structured parse errors, for pointing at the problem in an editor rather than
+//! showing [nom::error::convert_error]'s text.
+use nom::error::{VerboseError, VerboseErrorKind};
+
+use crate::{unit::Measure, Ingredient, IngredientParser};
+
+/// Where and why parsing stopped.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    /// byte offset into the input where parsing stopped
+    pub offset: usize,
+    /// what would have been accepted at `offset`
+    pub expected: Vec<Expected>,
+    /// the parsers that were running at the time, innermost first, e.g. `["num", "many_amount"]`
+    pub contexts: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub enum Expected {
+    Char(char),
+    /// a nom combinator, e.g. `Digit` or `Tag`
+    Token(String),
+    /// the end of the input, but there was text left over
+    End,
+}
+
+impl Diagnostic {
+    /// Builds a diagnostic from an error returned while parsing `input`.
+    pub fn from_error(input: &str, e: nom::Err<VerboseError<&str>>) -> Diagnostic {
+        let errors = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.errors,
+            nom::Err::Incomplete(_) => vec![],
+        };
+        let offset_of = |rest: &str| input.len() - rest.len();
+        // report the furthest point any alternative got to
+        let offset = errors
+            .iter()
+            .map(|(rest, _)| offset_of(rest))
+            .max()
+            .unwrap_or(input.len());
+        let mut expected = vec![];
+        let mut contexts = vec![];
+        for (rest, kind) in errors {
+            match kind {
+                VerboseErrorKind::Context(c) => contexts.push(c.to_string()),
+                VerboseErrorKind::Char(c) if offset_of(rest) == offset => {
+                    expected.push(Expected::Char(c))
+                }
+                VerboseErrorKind::Nom(k) if offset_of(rest) == offset => {
+                    expected.push(Expected::Token(format!("{k:?}")))
+                }
+                _ => {}
+            }
+        }
+        expected.dedup();
+        contexts.dedup();
+        Diagnostic {
+            offset,
+            expected,
+            contexts,
+        }
+    }
+    fn unconsumed(input: &str, rest: &str) -> Diagnostic {
+        Diagnostic {
+            offset: input.len() - rest.len(),
+            expected: vec![Expected::End],
+            contexts: vec![],
+        }
+    }
+}
+
+impl IngredientParser {
+    /// Like [IngredientParser::parse_ingredient], but requires the whole input to be
+    /// consumed, and describes failures with a [Diagnostic].
+    pub fn diagnose_ingredient(&self, input: &str) -> Result<Ingredient, Diagnostic> {
+        match self.parse_ingredient(input) {
+            Ok(("", i)) => Ok(i),
+            Ok((rest, _)) => Err(Diagnostic::unconsumed(input, rest)),
+            Err(e) => Err(Diagnostic::from_error(input, e)),
+        }
+    }
+    /// Like [IngredientParser::parse_amount], but requires the whole input to be
+    /// consumed, and describes failures with a [Diagnostic].
+    /// ```
+    /// use ingredient::{diagnostics::Expected, IngredientParser};
+    /// let ip = IngredientParser::new(false);
+    /// let d = ip.diagnose_amount("2 cups flour").unwrap_err();
+    /// assert_eq!(d.offset, 6);
+    /// assert_eq!(d.expected, vec![Expected::End]);
+    /// ```
+    pub fn diagnose_amount(&self, input: &str) -> Result<Vec<Measure>, Diagnostic> {
+        match self.many_amount::<VerboseError<&str>>(input) {
+            Ok(("", m)) => Ok(m),
+            Ok((rest, _)) => Err(Diagnostic::unconsumed(input, rest)),
+            Err(e) => Err(Diagnostic::from_error(input, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_amount() {
+        let ip = IngredientParser::new(false);
+        assert_eq!(
+            ip.diagnose_amount("2 cups"),
+            Ok(vec![Measure::parse_new("cups", 2.0)])
+        );
+        let d = ip.diagnose_amount("cups").unwrap_err();
+        assert_eq!(d.offset, 0);
+        assert!(d.contexts.contains(&"many_amount".to_string()));
+        assert!(!d.expected.is_empty());
+    }
+    #[test]
+    fn test_diagnose_ingredient() {
+        let ip = IngredientParser::new(false);
+        assert_eq!(
+            ip.diagnose_ingredient("1 cup flour").map(|i| i.name),
+            Ok("flour".to_string())
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{
+    ingredient::Ingredient,
+    locale::Locale,
+    normalize::plural_name,
+    temperature::find_temperature,
+    unit::{Bound, Measure, Source, Unit},
+    util::num_with_precision,
+    INVERTED_PARTS,
+};
+
+/// Controls how [Measure] and [Ingredient] are rendered as text.
+/// The [Default] matches the plain `Display` output.
+/// A [Locale] other than english writes the line in that language:
+/// ```
+/// use ingredient::{display::DisplayOptions, locale::Locale, IngredientParser};
+/// let ip = IngredientParser::new(false).with_locale(Locale::Fr);
+/// let i = ip.from_str("250 g farine");
+/// assert_eq!(i.format(&DisplayOptions::new().locale(Locale::Fr)), "250 g de farine");
+/// ```
+/// ```
+/// use ingredient::{display::DisplayOptions, from_str};
+/// let i = from_str("2 tbsp / 30 grams butter");
+/// assert_eq!(i.to_string(), "2 tbsp / 30 g butter");
+/// assert_eq!(
+///     i.format(&DisplayOptions::new().long_units(true).all_amounts(false)),
+///     "2 tablespoons butter"
+/// );
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(Serialize, Deserialize),
+    serde(default)
+)]
+pub struct DisplayOptions {
+    /// use `tablespoon` instead of `tbsp`
+    pub long_units: bool,
+    /// maximum number of decimal places, trailing zeroes are always trimmed
+    pub precision: usize,
+    /// rounds values to this many significant figures first, see [Measure::rounded]
+    pub significant_figures: Option<usize>,
+    /// pluralize units when the value is above 1
+    pub pluralize: bool,
+    /// show every amount, or only the first one
+    pub all_amounts: bool,
+    /// show amounts that were converted from the written ones, see [Source::Derived]
+    pub derived_amounts: bool,
+    /// goes between the bounds of a range, e.g. `–` for `1–2 cups`
+    pub range_separator: String,
+    /// language of unit names, decimal separator and word order
+    pub locale: Locale,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        DisplayOptions {
+            long_units: false,
+            precision: 2,
+            significant_figures: None,
+            pluralize: true,
+            all_amounts: true,
+            derived_amounts: true,
+            range_separator: " - ".to_string(),
+            locale: Locale::En,
+        }
+    }
+}
+
+impl DisplayOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn long_units(mut self, long_units: bool) -> Self {
+        self.long_units = long_units;
+        self
+    }
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+    pub fn significant_figures(mut self, significant_figures: usize) -> Self {
+        self.significant_figures = Some(significant_figures);
+        self
+    }
+    pub fn pluralize(mut self, pluralize: bool) -> Self {
+        self.pluralize = pluralize;
+        self
+    }
+    pub fn all_amounts(mut self, all_amounts: bool) -> Self {
+        self.all_amounts = all_amounts;
+        self
+    }
+    pub fn derived_amounts(mut self, derived_amounts: bool) -> Self {
+        self.derived_amounts = derived_amounts;
+        self
+    }
+    pub fn range_separator(mut self, range_separator: &str) -> Self {
+        self.range_separator = range_separator.to_string();
+        self
+    }
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Renders a unit label for a value (or range upper bound) of `amount`.
+    pub fn unit_label(&self, unit: &Unit, amount: f64) -> String {
+        let plural = self.pluralize && amount > 1.0;
+        if let Some(s) = self.locale.unit_name(unit, self.long_units, plural) {
+            return s;
+        }
+        if self.long_units {
+            let s = unit.clone().to_long_str();
+            return match unit {
+                _ if !plural => s,
+                Unit::Inch => "inches".to_string(),
+                Unit::Farhenheit | Unit::Celcius | Unit::Other(_) => s,
+                _ => format!("{s}s"),
+            };
+        }
+        let mut s = unit.clone().to_str();
+        // abbreviations stay as-is, only units that are written out get an `s`
+        if plural && matches!(unit, Unit::Cup | Unit::Minute) {
+            s.push('s');
+        }
+        s
+    }
+
+    pub fn format_measure(&self, m: &Measure) -> String {
+        let mut measure = m.clone().denormalize();
+        if let Some(n) = self.significant_figures {
+            measure = measure.rounded(n);
+        }
+        let (value, upper, _) = measure.values();
+        let num = |v: f64| {
+            num_with_precision(v, self.precision)
+                .replace('.', &self.locale.decimal_separator().to_string())
+        };
+        let s = match upper {
+            None if measure.bound() == Some(Bound::AtLeast) => format!("at least {}", num(value)),
+            Some(u) if u != 0.0 && value == 0.0 => format!("up to {}", num(u)),
+            Some(u) if u != 0.0 => format!("{}{}{}", num(value), self.range_separator, num(u)),
+            _ => num(value),
+        };
+        // plural by what's shown, so `1.001 cups` at no decimal places is `1 cup`
+        let shown = |v: f64| num_with_precision(v, self.precision).parse().unwrap_or(v);
+        let label = self.unit_label(
+            &measure.unit(),
+            shown(value).max(shown(upper.unwrap_or_default())),
+        );
+        match label.as_str() {
+            "" => s,
+            _ => format!("{s} {label}"),
+        }
+    }
+
+    pub fn format_ingredient(&self, i: &Ingredient) -> String {
+        if let Some(line) = self.format_inverted(i) {
+            return line;
+        }
+        let shown = i
+            .amounts
+            .iter()
+            .filter(|a| self.derived_amounts || a.source() != Source::Derived);
+        let amounts: Vec<String> = match self.all_amounts {
+            true => shown.map(|a| self.format_measure(a)).collect(),
+            false => shown.take(1).map(|a| self.format_measure(a)).collect(),
+        };
+        let amount_list = match amounts.len() {
+            0 => "n/a ".to_string(),
+            _ => format!("{} ", amounts.join(" / ")),
+        };
+        // `2 œufs`, not `2 de œufs`, when there's no unit to connect
+        let connective = match i.amounts.first() {
+            Some(a) if !a.inferred_unit() && !self.unit_label(&a.unit(), 1.0).is_empty() => {
+                self.locale.unit_connective(&i.name)
+            }
+            _ => "",
+        };
+        format!("{}{}{}", amount_list, connective, self.describe(i))
+    }
+
+    // `zest of 2 lemons` the way it was written, rather than `2 lemon lemon zest`
+    fn format_inverted(&self, i: &Ingredient) -> Option<String> {
+        let [amount] = i.amounts.as_slice() else {
+            return None;
+        };
+        let Unit::Other(head) = amount.unit() else {
+            return None;
+        };
+        let (source, part) = INVERTED_PARTS
+            .iter()
+            .find_map(|p| Some((i.name.strip_suffix(p)?.strip_suffix(' ')?, p)))?;
+        if source.rsplit(' ').next() != Some(head.as_str()) {
+            return None;
+        }
+        let (value, upper, _) = amount.values();
+        let count = Measure::new_with_upper(Unit::Other(String::new()), value, upper);
+        let source = match self.pluralize && value.max(upper.unwrap_or_default()) > 1.0 {
+            true => plural_name(source),
+            false => source.to_string(),
+        };
+        let rest = self.describe(&Ingredient {
+            name: String::new(),
+            ..i.clone()
+        });
+        Some(format!(
+            "{part} of {} {source}{rest}",
+            self.format_measure(&count)
+        ))
+    }
+
+    /// The name, alternatives, note and modifier, without any amounts.
+    pub(crate) fn describe(&self, i: &Ingredient) -> String {
+        let mut modifier = String::new();
+        // one written in the modifier, `heated to 110°F`, is already shown by it
+        if let Some(t) = i
+            .temperature
+            .filter(|_| (i.modifier.as_deref()).and_then(find_temperature).is_none())
+        {
+            modifier.push_str(&format!(" ({t})"));
+        }
+        if let Some(n) = &i.note {
+            modifier.push_str(&format!(" ({n})"));
+        }
+        if let Some(p) = i.percentage {
+            let label = match &i.percentage_label {
+                Some(l) => format!(" {l}"),
+                None => "".to_string(),
+            };
+            modifier.push_str(&format!(
+                " ({}%{label})",
+                num_with_precision(p, self.precision)
+            ));
+        }
+        if let Some(m) = &i.modifier {
+            modifier.push_str(&format!(", {m}"));
+        }
+        if i.divided {
+            modifier.push_str(", divided");
+        }
+        if let Some(e) = &i.extra_for {
+            modifier.push_str(&format!(", plus more for {e}"));
+        }
+        if let Some(f) = &i.flexibility {
+            modifier.push_str(&format!(", {f}"));
+        }
+        if let Some(p) = &i.purpose {
+            modifier.push_str(&format!(", {p}"));
+        }
+        let name = std::iter::once(i.name.clone())
+            .chain(i.alternatives.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" or ");
+        let footnote = i.footnote.as_deref().unwrap_or_default();
+        format!("{}{}{}", name, footnote, modifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_str, unit::MeasurementSystem};
+
+    #[test]
+    fn test_display_options() {
+        let i = from_str("1.333 cups / 155.555 grams flour, sifted");
+        assert_eq!(
+            i.format(&DisplayOptions::default()),
+            "1.33 cups / 155.56 g flour, sifted"
+        );
+        assert_eq!(
+            i.format(&DisplayOptions::new().precision(0).all_amounts(false)),
+            "1 cup flour, sifted"
+        );
+        assert_eq!(
+            i.format(&DisplayOptions::new().long_units(true).precision(1)),
+            "1.3 cups / 155.6 grams flour, sifted"
+        );
+        assert_eq!(
+            i.format(&DisplayOptions::new().long_units(true).pluralize(false)),
+            "1.33 cup / 155.56 gram flour, sifted"
+        );
+    }
+    #[test]
+    fn test_significant_figures() {
+        let o = DisplayOptions::new().significant_figures(3);
+        let i = from_str("1.333 cups / 155.555 grams flour");
+        assert_eq!(i.format(&o), "1.33 cups / 156 g flour");
+        assert_eq!(
+            from_str("1234.5-1260 g flour").format(&o.clone().precision(0)),
+            "1230 - 1260 g flour"
+        );
+        assert_eq!(from_str("0.5 tsp salt").format(&o), "0.5 tsp salt");
+    }
+    #[test]
+    fn test_derived_amounts() {
+        let i = from_str("8 oz (227 g) cream cheese").prefer(MeasurementSystem::Metric);
+        assert_eq!(i.to_string(), "227 g / 8 oz cream cheese");
+        let i = from_str("2 cups milk").prefer(MeasurementSystem::Metric);
+        assert_eq!(i.to_string(), "473.18 ml / 2 cups milk");
+        assert_eq!(
+            i.format(&DisplayOptions::new().derived_amounts(false)),
+            "2 cups milk"
+        );
+    }
+    #[test]
+    fn test_ranges() {
+        let i = from_str("1-2 cups flour");
+        assert_eq!(i.to_string(), "1 - 2 cups flour");
+        assert_eq!(from_str(&i.to_string()), i);
+        assert_eq!(
+            i.format(&DisplayOptions::new().range_separator("–")),
+            "1–2 cups flour"
+        );
+        let i = from_str("up to 10 minutes");
+        assert_eq!(i.amounts[0].to_string(), "up to 10 minutes");
+        assert_eq!(from_str(&i.to_string()), i);
+    }
+    #[test]
+    fn test_percentage() {
+        for line in [
+            "500 g flour (70%)",
+            "350 g water (62% hydration)",
+            "10 g salt (2%), fine",
+        ] {
+            let i = from_str(line);
+            assert!(i.percentage.is_some(), "{}", line);
+            assert_eq!(i.to_string(), line);
+            assert_eq!(from_str(&i.to_string()), i);
+        }
+    }
+    #[test]
+    fn test_locale() {
+        let i = from_str("1.5 tbsp sugar");
+        let o = DisplayOptions::new();
+        assert_eq!(i.format(&o.clone().locale(Locale::De)), "1,5 EL sugar");
+        assert_eq!(i.format(&o.clone().locale(Locale::Es)), "1,5 cda de sugar");
+        assert_eq!(
+            from_str("2 cups oil").format(&o.clone().locale(Locale::Fr).long_units(true)),
+            "2 tasses d'oil"
+        );
+        let fr = o.locale(Locale::Fr);
+        assert_eq!(from_str("2 eggs").format(&fr), "2 eggs");
+        assert_eq!(from_str("1 whole egg").format(&fr), "1 egg");
+        assert_eq!(
+            from_str("2 cloves garlic").format(&fr),
+            "2 gousses de garlic"
+        );
+        assert_eq!(from_str("8 oz butter").format(&fr), "8 oz de butter");
+        assert_eq!(
+            from_str("1 lb butter").format(&fr.long_units(true)),
+            "1 livre de butter"
+        );
+    }
+    #[test]
+    fn test_unit_label() {
+        let o = DisplayOptions::new().long_units(true);
+        assert_eq!(o.unit_label(&Unit::Tablespoon, 1.0), "tablespoon");
+        assert_eq!(o.unit_label(&Unit::Inch, 2.0), "inches");
+        assert_eq!(o.unit_label(&Unit::FluidOunce, 2.0), "fluid ounces");
+        assert_eq!(
+            o.unit_label(&Unit::Other("whole".to_string()), 2.0),
+            "whole"
+        );
+        assert_eq!(
+            DisplayOptions::new().unit_label(&Unit::Tablespoon, 2.0),
+            "tbsp"
+        );
+    }
+}
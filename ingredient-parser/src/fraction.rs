@@ -1,56 +1,100 @@
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{satisfy, space0, space1},
-    combinator::opt,
+    bytes::complete::{tag, tag_no_case},
+    character::complete::{char, digit0, digit1, satisfy, space0, space1},
+    combinator::{map_opt, opt, recognize},
     error::context,
-    number::complete::double,
     sequence::tuple,
 };
 
-use crate::Res;
+use nom::IResult;
+
+use crate::{ParseErr, Res};
 
 fn v_frac_to_num(input: char) -> Result<f64, String> {
     // two ranges for unicode fractions
     // https://www.compart.com/en/unicode/search?q=vulgar+fraction#characters
     let (n, d): (i32, i32) = match input {
-        '¾' => (3, 4),
-        '⅛' => (1, 8),
         '¼' => (1, 4),
-        '⅓' => (1, 3),
         '½' => (1, 2),
+        '¾' => (3, 4),
+        '⅐' => (1, 7),
+        '⅑' => (1, 9),
+        '⅒' => (1, 10),
+        '⅓' => (1, 3),
         '⅔' => (2, 3),
+        '⅕' => (1, 5),
+        '⅖' => (2, 5),
+        '⅗' => (3, 5),
+        '⅘' => (4, 5),
+        '⅙' => (1, 6),
+        '⅚' => (5, 6),
+        '⅛' => (1, 8),
+        '⅜' => (3, 8),
+        '⅝' => (5, 8),
+        '⅞' => (7, 8),
         _ => return Err(format!("unkown fraction: {input}")),
     };
     Ok(n as f64 / d as f64)
 }
 
-fn is_frac_char(c: char) -> bool {
+pub(crate) fn is_frac_char(c: char) -> bool {
     v_frac_to_num(c).is_ok()
 }
 
 /// parses unicode vulgar fractions
-fn v_fraction(input: &str) -> Res<&str, f64> {
+fn v_fraction<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, f64, E> {
     context("v_fraction", satisfy(is_frac_char))(input)
         .map(|(next_input, res)| (next_input, v_frac_to_num(res).unwrap()))
 }
-fn n_fraction(input: &str) -> Res<&str, f64> {
-    context("n_fraction", tuple((double, tag("/"), double)))(input)
-        .map(|(next_input, res)| (next_input, res.0 / res.2))
+/// A plain decimal number: digits, optionally followed by a point and more digits,
+/// or a point and digits (`.5`). Unlike nom's `double`, there's no sign, exponent,
+/// `inf` or `nan`, so `3e` or `nan` aren't taken for numbers.
+pub(crate) fn decimal<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, f64, E> {
+    context(
+        "decimal",
+        map_opt(
+            recognize(alt((
+                recognize(tuple((digit1, opt(tuple((char('.'), digit0)))))),
+                recognize(tuple((char('.'), digit1))),
+            ))),
+            |s: &str| s.parse::<f64>().ok(),
+        ),
+    )(input)
+}
+fn n_fraction<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, f64, E> {
+    // `1/0` isn't a number
+    context(
+        "n_fraction",
+        map_opt(tuple((decimal, tag("/"), decimal)), |(n, _, d)| {
+            (d != 0.0).then(|| n / d)
+        }),
+    )(input)
 }
 
-/// parses `1 ⅛` or `1 1/8` into `1.125`
+// the ` and ` of `1 and ½`
+fn and<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    recognize(tuple((space1, tag_no_case("and"), space1)))(input)
+}
+
+/// parses `1 ⅛`, `1 1/8` or `1 and 1/8` into `1.125`
 pub fn fraction_number(input: &str) -> Res<&str, f64> {
+    fraction_number_generic(input)
+}
+
+pub(crate) fn fraction_number_generic<'a, E: ParseErr<'a>>(
+    input: &'a str,
+) -> IResult<&'a str, f64, E> {
     context(
         "fraction_number",
         alt((
             tuple((
-                opt(tuple((double, space0))), // optional number (and if number, optional space) before
-                v_fraction,                   // vulgar frac
+                opt(tuple((decimal, alt((and, space0))))), // optional number (and if number, optional space) before
+                v_fraction,                                // vulgar frac
             )),
             tuple((
-                opt(tuple((double, space1))), // optional number (and if number, required space space) before
-                n_fraction,                   // regular frac
+                opt(tuple((decimal, alt((and, space1))))), // optional number (and if number, required space space) before
+                n_fraction,                                // regular frac
             )),
         )),
     )(input)
@@ -77,8 +121,13 @@ mod tests {
         assert_eq!(fraction_number("1 ⅛"), Ok(("", 1.125)));
         assert_eq!(fraction_number("1 1/8"), Ok(("", 1.125)));
         assert_eq!(fraction_number("1⅓"), Ok(("", 1.3333333333333333)));
+        assert_eq!(fraction_number("1 and ½ cups"), Ok((" cups", 1.5)));
+        assert_eq!(fraction_number("2 AND 3/4"), Ok(("", 2.75)));
         assert_eq!(fraction_number("¼"), Ok(("", 0.25)));
         assert_eq!(fraction_number("1/4"), Ok(("", 0.25)));
+        assert!(fraction_number("1/0").is_err());
+        assert!(fraction_number("0/0").is_err());
+        assert!(fraction_number("2 1/0").is_err());
         assert_eq!(
             fraction_number("1"),
             Err(NomErr::Error(VerboseError {
@@ -92,6 +141,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decimal() {
+        let d = |s| super::decimal::<VerboseError<&str>>(s);
+        assert_eq!(d("1.5 cups"), Ok((" cups", 1.5)));
+        assert_eq!(d(".5"), Ok(("", 0.5)));
+        assert_eq!(d("3e"), Ok(("e", 3.0)));
+        assert_eq!(d("2e3"), Ok(("e3", 2.0)));
+        assert!(d("inf").is_err());
+        assert!(d("nan").is_err());
+        assert!(d("-1").is_err());
+    }
+
     #[test]
     fn test_v_fraction() {
         assert_eq!(v_frac_to_num('⅛'), Ok(0.125));
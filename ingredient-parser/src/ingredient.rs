@@ -1,14 +1,187 @@
 use std::{convert::TryFrom, fmt};
 
-use crate::{from_str, unit::Measure};
+use crate::{
+    display::DisplayOptions,
+    from_str,
+    normalize::{normalize_name, NormalizedIngredient},
+    temperature::Temperature,
+    unit::{Measure, MeasurementSystem},
+};
 
-#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+// `remote = "Self"` turns the derives into inherent functions, which the
+// manual impls below wrap so that an ingredient can also be read from a string.
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(Serialize, Deserialize),
+    serde(remote = "Self")
+)]
 #[derive(Clone, PartialEq, PartialOrd, Debug, Default)]
 /// Holds a name, list of [Measure], and optional modifier string
+///
+/// With `serde-derive`, it can be deserialized from either the struct form or
+/// from a string, which is run through [from_str]:
+/// ```
+/// use ingredient::{from_str, Ingredient};
+/// let i: Vec<Ingredient> = serde_json::from_str(
+///     r#"["1 cup flour", {"name": "flour", "amounts": [], "modifier": null}]"#,
+/// ).unwrap();
+/// assert_eq!(i[0], from_str("1 cup flour"));
+/// assert_eq!(i[1], from_str("flour"));
+/// ```
 pub struct Ingredient {
     pub name: String,
     pub amounts: Vec<Measure>,
     pub modifier: Option<String>,
+    /// substitutes for the ingredient, e.g. `margarine` in `butter or margarine`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub alternatives: Vec<String>,
+    /// the amount is split across several steps of the recipe
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub divided: bool,
+    /// what any extra is for, e.g. `serving` in `plus more for serving`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub extra_for: Option<String>,
+    /// what the whole ingredient is for, e.g. `for serving` in `lime wedges, for serving`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub purpose: Option<String>,
+    /// the amount is a suggestion, e.g. `or to taste`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub flexibility: Option<Flexibility>,
+    /// e.g. `110°F` in `butter, melted and cooled to 110°F`, also left in the modifier
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub temperature: Option<Temperature>,
+    /// baker's percentage, e.g. `62` in `350 g water (62%)`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub percentage: Option<f64>,
+    /// what follows the [Ingredient::percentage], e.g. `hydration` in `(62% hydration)`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub percentage_label: Option<String>,
+    /// parenthetical text that isn't an amount, e.g. `thawed if frozen` in
+    /// `1 cup peas (thawed if frozen)`
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub note: Option<String>,
+    /// a footnote marker after the name, e.g. `*` in `1 cup cashews*`, pointing to the
+    /// recipe's notes
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub footnote: Option<String>,
+    /// how many of what size, e.g. `2 x 400 g` in `2 x 400 g cans tomatoes`,
+    /// with the total in the amounts
+    #[cfg_attr(
+        feature = "serde-derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub multiplier: Option<Multiplier>,
+}
+
+#[cfg(feature = "serde-derive")]
+impl serde::Serialize for Ingredient {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Ingredient::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde-derive")]
+impl<'de> serde::Deserialize<'de> for Ingredient {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StringOrStruct;
+        impl<'de> serde::de::Visitor<'de> for StringOrStruct {
+            type Value = Ingredient;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an ingredient string or struct")
+            }
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Ingredient, E> {
+                Ok(from_str(value))
+            }
+            fn visit_map<M: serde::de::MapAccess<'de>>(
+                self,
+                map: M,
+            ) -> Result<Ingredient, M::Error> {
+                Ingredient::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+            }
+        }
+        deserializer.deserialize_any(StringOrStruct)
+    }
+}
+
+/// `count` items of `each`, see [Ingredient::multiplier].
+/// ```
+/// use ingredient::{from_str, unit::Measure, Multiplier};
+/// let i = from_str("2 x 400 g cans chopped tomatoes");
+/// assert_eq!(
+///     i.multiplier,
+///     Some(Multiplier { count: 2.0, each: Measure::parse_new("g", 400.0) })
+/// );
+/// assert_eq!(i.to_string(), "800 g / 2 can tomatoes, chopped");
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct Multiplier {
+    pub count: f64,
+    pub each: Measure,
+}
+
+/// How much an amount can be adjusted, for amounts that are only advisory,
+/// such as `1 tsp chili flakes, or to taste`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub enum Flexibility {
+    /// `to taste` or `or to taste`
+    ToTaste,
+    /// `or more`, `or more as needed`
+    OrMore,
+    /// `as needed`
+    AsNeeded,
+}
+
+impl fmt::Display for Flexibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Flexibility::ToTaste => "to taste",
+            Flexibility::OrMore => "or more",
+            Flexibility::AsNeeded => "as needed",
+        })
+    }
+}
+
+/// Result of [crate::IngredientParser::parse_lenient]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct ParsedIngredient {
+    pub ingredient: Ingredient,
+    /// from `0.0` to `1.0`, lower when there were warnings
+    pub confidence: f32,
+    /// anything that looked off while parsing
+    pub warnings: Vec<String>,
 }
 
 impl TryFrom<&str> for Ingredient {
@@ -18,19 +191,68 @@ impl TryFrom<&str> for Ingredient {
     }
 }
 
+impl Ingredient {
+    /// Canonical key for the ingredient, e.g. `onion` for `large yellow onion`.
+    /// See [crate::normalize::normalize_name].
+    pub fn normalized_name(&self) -> String {
+        normalize_name(&self.name).0
+    }
+    /// A hashable canonical form, so that `2 Tbsp Morton salt` and `6 tsp salt` match.
+    /// ```
+    /// use ingredient::from_str;
+    /// use std::collections::HashSet;
+    /// let pantry = HashSet::from([from_str("6 tsp salt").normalized()]);
+    /// assert!(pantry.contains(&from_str("2 Tbsp Morton salt").normalized()));
+    /// assert!(!pantry.contains(&from_str("2 tsp salt").normalized()));
+    /// ```
+    pub fn normalized(&self) -> NormalizedIngredient {
+        self.into()
+    }
+    /// The brands and descriptors [Ingredient::normalized_name] strips.
+    pub fn name_descriptors(&self) -> Vec<String> {
+        normalize_name(&self.name).1
+    }
+    /// Puts an amount in `system` first, converting one if there isn't any,
+    /// for showing a recipe in the reader's preferred units.
+    /// ```
+    /// use ingredient::{from_str, unit::MeasurementSystem};
+    /// let i = from_str("2 tbsp / 30 g butter");
+    /// assert_eq!(i.prefer(MeasurementSystem::Metric).to_string(), "30 g / 2 tbsp butter");
+    /// let i = from_str("1 lb ground beef");
+    /// assert_eq!(
+    ///     i.prefer(MeasurementSystem::Metric).to_string(),
+    ///     "453.59 g / 1 lb ground beef"
+    /// );
+    /// ```
+    pub fn prefer(&self, system: MeasurementSystem) -> Ingredient {
+        let mut amounts = self.amounts.clone();
+        match amounts
+            .iter()
+            .position(|a| a.unit().system() == Some(system))
+        {
+            Some(idx) => {
+                let preferred = amounts.remove(idx);
+                amounts.insert(0, preferred);
+            }
+            None => {
+                if let Some(converted) = amounts.iter().find_map(|a| a.to_system(system)) {
+                    amounts.insert(0, converted);
+                }
+            }
+        }
+        Ingredient {
+            amounts,
+            ..self.clone()
+        }
+    }
+    /// Formats with custom [DisplayOptions], `Display` uses the defaults.
+    pub fn format(&self, options: &DisplayOptions) -> String {
+        options.format_ingredient(self)
+    }
+}
+
 impl fmt::Display for Ingredient {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let amounts: Vec<String> = self.amounts.iter().map(|id| id.to_string()).collect();
-        let modifier = match &self.modifier {
-            Some(m) => {
-                format!(", {m}")
-            }
-            None => "".to_string(),
-        };
-        let amount_list = match amounts.len() {
-            0 => "n/a ".to_string(),
-            _ => format!("{} ", amounts.join(" / ")),
-        };
-        write!(f, "{}{}{}", amount_list, self.name, modifier)
+        write!(f, "{}", self.format(&DisplayOptions::default()))
     }
 }
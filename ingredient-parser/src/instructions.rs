@@ -0,0 +1,83 @@
+//! Amounts mentioned inside recipe steps, e.g. `add 120 g of the flour`.
+use std::ops::Range;
+
+use crate::{unit::Measure, IngredientParser};
+
+/// Byte offsets into the instruction text.
+pub type Span = Range<usize>;
+
+/// Finds every amount in a free-form instruction, using [IngredientParser::new] in rich text mode.
+/// See [IngredientParser::extract_amounts].
+/// ```
+/// use ingredient::{instructions::extract_amounts, unit::Measure};
+/// let step = "add 120 g of the flour and 30 ml water";
+/// let found = extract_amounts(step);
+/// assert_eq!(found[0], (4..9, Measure::parse_new("g", 120.0)));
+/// assert_eq!(&step[found[1].0.clone()], "30 ml");
+/// ```
+pub fn extract_amounts(input: &str) -> Vec<(Span, Measure)> {
+    IngredientParser::new(true).extract_amounts(input)
+}
+
+impl IngredientParser {
+    /// Finds every amount in a free-form instruction, along with where it was written,
+    /// so that it can be replaced, e.g. when scaling a recipe.
+    /// Equivalent amounts written together (`120 g / 1 cup`) share a span.
+    pub fn extract_amounts(&self, input: &str) -> Vec<(Span, Measure)> {
+        let mut found = vec![];
+        let mut rest = input;
+        let mut prev: Option<char> = None;
+        while let Some(c) = rest.chars().next() {
+            let start = input.len() - rest.len();
+            // amounts start a word, the `1` in `step1` isn't one
+            if !prev.is_some_and(char::is_alphanumeric) {
+                if let Ok((next, measures)) = self.many_amount::<nom::error::Error<&str>>(rest) {
+                    let text = trim_amount(&rest[..rest.len() - next.len()]);
+                    if !text.is_empty() && !measures.is_empty() {
+                        let span = start..start + text.len();
+                        found.extend(measures.into_iter().map(|m| (span.clone(), m)));
+                        prev = text.chars().last();
+                        rest = &rest[text.len()..];
+                        continue;
+                    }
+                }
+            }
+            prev = Some(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        found
+    }
+}
+
+// what the amount parsers consume around an amount that isn't part of it
+fn trim_amount(s: &str) -> &str {
+    let s = s.trim_end();
+    let s = s.strip_suffix(" of").unwrap_or(s);
+    s.strip_suffix('.').unwrap_or(s).trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_amounts() {
+        let step = "Bake at 350°F for 25-30 minutes. Step2: cool for 1 hour.";
+        let found: Vec<(&str, Measure)> = extract_amounts(step)
+            .into_iter()
+            .map(|(span, m)| (&step[span], m))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("350°F", Measure::parse_new("°F", 350.0)),
+                (
+                    "25-30 minutes",
+                    Measure::parse_new_with_upper("minutes", 25.0, 30.0)
+                ),
+                ("1 hour", Measure::parse_new("hour", 1.0)),
+            ]
+        );
+        assert_eq!(extract_amounts("whisk until smooth"), vec![]);
+    }
+}
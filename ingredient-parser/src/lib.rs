@@ -1,22 +1,23 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
-pub use crate::ingredient::Ingredient;
+pub use crate::aggregate::aggregate;
+pub use crate::builder::{IngredientParserBuilder, ParseMode};
+pub use crate::ingredient::{Flexibility, Ingredient, Multiplier, ParsedIngredient};
 use anyhow::Result;
-use fraction::fraction_number;
+use fraction::{decimal, fraction_number_generic as fraction_number};
+use locale::Locale;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{alpha1, char, not_line_ending, satisfy, space0, space1},
-    combinator::{opt, verify},
-    error::{context, VerboseError},
+    bytes::complete::{tag, tag_no_case, take_while1},
+    character::complete::{char, not_line_ending, satisfy, space0, space1},
+    combinator::{map, map_opt, not, opt, peek, recognize, verify},
+    error::{context, ContextError, ParseError, VerboseError},
     multi::{many1, separated_list1},
-    number::complete::double,
-    sequence::{delimited, tuple},
+    sequence::{delimited, preceded, terminated, tuple},
     IResult,
 };
-use tracing::info;
-use unit::Measure;
+use unit::{Bound, Measure, Source};
 
 extern crate nom;
 
@@ -24,23 +25,73 @@ extern crate nom;
 #[macro_use]
 extern crate serde;
 
+pub mod aggregate;
+pub mod bakers_percentage;
+pub mod borrowed;
+pub mod builder;
+pub mod category;
+pub mod cooklang;
+pub mod diagnostics;
+pub mod display;
 mod fraction;
 pub mod ingredient;
+pub mod instructions;
+pub mod locale;
+pub mod markdown;
+pub mod matching;
+pub mod normalize;
+#[cfg(feature = "nutrition")]
+pub mod nutrition;
+pub mod parsers;
+pub mod recipe;
+pub mod recipe_yield;
 pub mod rich_text;
+pub mod temperature;
 pub mod unit;
 pub mod util;
 pub type Res<T, U> = IResult<T, U, VerboseError<T>>;
 
+/// Error types the parsers are generic over, [VerboseError] for diagnostics
+/// or the cheaper [nom::error::Error] when only the result matters.
+pub trait ParseErr<'a>: ParseError<&'a str> + ContextError<&'a str> {}
+impl<'a, E: ParseError<&'a str> + ContextError<&'a str>> ParseErr<'a> for E {}
+
 /// use [IngredientParser] to customize
 pub fn from_str(input: &str) -> Ingredient {
     (IngredientParser::new(false)).from_str(input)
 }
 
+/// use [IngredientParser::parse_duration] to customize
+pub fn parse_duration(input: &str) -> Result<std::time::Duration> {
+    IngredientParser::new(false).parse_duration(input)
+}
+
+/// use [IngredientParser::parse_yield] to customize
+pub fn parse_yield(input: &str) -> Result<recipe_yield::Yield> {
+    IngredientParser::new(false).parse_yield(input)
+}
+
+/// use [IngredientParser::parse_lenient] to customize
+pub fn parse_lenient(input: &str) -> ParsedIngredient {
+    IngredientParser::new(false).parse_lenient(input)
+}
+
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct IngredientParser {
     pub units: HashSet<String>,
     pub adjectives: HashSet<String>,
+    /// words that can stand in for a number, e.g. `one` or `a`
+    pub number_words: HashMap<String, f64>,
+    /// alternate (singular) names for standard units, e.g. `el` for `tbsp`
+    pub unit_aliases: HashMap<String, String>,
+    pub locale: Locale,
+    /// unit given to amounts without one, e.g. the `3` in `3 limes`.
+    /// If unset, those amounts have an empty unit.
+    pub bare_count_unit: Option<String>,
     pub is_rich_text: bool,
+    /// see [IngredientParserBuilder::verbose_errors]
+    pub verbose_errors: bool,
+    pub mode: ParseMode,
 }
 impl IngredientParser {
     pub fn new(is_rich_text: bool) -> Self {
@@ -49,9 +100,10 @@ impl IngredientParser {
             // default set
             "whole", "packet", "sticks", "stick", "cloves", "clove", "bunch", "head", "large",
             "pinch", "small", "medium", "package", "recipe", "slice", "standard", "can", "leaf",
-            "leaves", "strand", "tin",
+            "leaves", "strand", "tin", "sprig", "piece", "stalk", "fillet",
         ]
         .iter()
+        .chain(unit::INFORMAL_UNITS.iter().map(|(u, _, _)| u))
         .map(|&s| s.into())
         .collect();
         let adjectives: Vec<String> = vec![
@@ -72,20 +124,212 @@ impl IngredientParser {
         IngredientParser {
             units: HashSet::from_iter(units.iter().cloned()),
             adjectives: HashSet::from_iter(adjectives.iter().cloned()),
+            number_words: HashMap::from([
+                ("one".to_string(), 1.0),
+                ("a".to_string(), 1.0),
+                ("an".to_string(), 1.0),
+            ]),
+            // aliases rather than units, so that these are only units when following
+            // a number, not on their own in rich text (`beat after each addition`)
+            unit_aliases: HashMap::from_iter(
+                [
+                    ("each", "each"),
+                    ("ea", "each"),
+                    ("count", "count"),
+                    ("ct", "count"),
+                ]
+                .iter()
+                .map(|&(a, u)| (a.to_string(), u.to_string())),
+            ),
+            locale: Locale::En,
+            bare_count_unit: Some("whole".to_string()),
             is_rich_text,
+            verbose_errors: false,
+            mode: ParseMode::Default,
         }
     }
+    /// For configuring a parser, rather than chaining `with_` calls on [IngredientParser::new].
+    pub fn builder() -> IngredientParserBuilder {
+        IngredientParserBuilder::new()
+    }
+    /// Adds the vocabulary of another language, english is always understood.
+    pub fn with_locale(self, locale: Locale) -> Self {
+        let mut ip = self
+            .with_units(locale.units().iter().copied())
+            .with_number_words(locale.number_words().iter().copied())
+            .with_unit_aliases(locale.unit_aliases().iter().copied());
+        ip.locale = locale;
+        ip
+    }
+    /// Adds to the set of non-standard units, for site or region specific vocabularies.
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// let ip = IngredientParser::new(false).with_units(["rasher", "knob"]);
+    /// assert_eq!(ip.from_str("2 rashers bacon").to_string(), "2 rasher bacon");
+    /// ```
+    pub fn with_units<'a>(mut self, units: impl IntoIterator<Item = &'a str>) -> Self {
+        self.units
+            .extend(units.into_iter().map(|u| u.to_lowercase()));
+        self
+    }
+    /// Sets the unit for amounts that don't have one, `whole` by default.
+    /// ```
+    /// use ingredient::{IngredientParser, unit::Measure};
+    /// let ip = IngredientParser::new(false).with_bare_count_unit("each");
+    /// assert_eq!(ip.from_str("3 limes").amounts, vec![Measure::parse_new("each", 3.0)]);
+    /// ```
+    pub fn with_bare_count_unit(mut self, unit: &str) -> Self {
+        self.bare_count_unit = Some(unit.to_lowercase());
+        self
+    }
+    /// Leaves the unit empty for amounts that don't have one, rather than
+    /// using [IngredientParser::bare_count_unit].
+    /// ```
+    /// use ingredient::{IngredientParser, unit::Measure};
+    /// let ip = IngredientParser::new(false).without_bare_count_unit();
+    /// assert_eq!(ip.from_str("3 limes").amounts, vec![Measure::parse_new("", 3.0)]);
+    /// ```
+    pub fn without_bare_count_unit(mut self) -> Self {
+        self.bare_count_unit = None;
+        self
+    }
+    /// Adds to the set of adjectives which are moved from the name into the modifier.
+    pub fn with_adjectives<'a>(mut self, adjectives: impl IntoIterator<Item = &'a str>) -> Self {
+        self.adjectives
+            .extend(adjectives.into_iter().map(|a| a.to_lowercase()));
+        self
+    }
+    /// Adds alternate names for standard units, e.g. `("gramm", "g")`.
+    pub fn with_unit_aliases<'a>(
+        mut self,
+        aliases: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        self.unit_aliases.extend(
+            aliases
+                .into_iter()
+                .map(|(a, u)| (a.to_lowercase(), u.to_string())),
+        );
+        self
+    }
+    /// Adds words which can be used in place of a number, e.g. `("dozen", 12.0)`.
+    pub fn with_number_words<'a>(
+        mut self,
+        words: impl IntoIterator<Item = (&'a str, f64)>,
+    ) -> Self {
+        self.number_words
+            .extend(words.into_iter().map(|(w, n)| (w.to_lowercase(), n)));
+        self
+    }
     /// wrapper for [self.parse_ingredient]
     /// ```
     /// use ingredient::{from_str};
     /// assert_eq!(from_str("one whole egg").to_string(),"1 whole egg");
     /// ```
-    pub fn from_str(self, input: &str) -> Ingredient {
+    pub fn from_str(&self, input: &str) -> Ingredient {
         //todo: add back error handling? can't get this to ever fail since parser is pretty flexible
-        self.parse_ingredient(input).unwrap().1
+        self.parse_ingredient(&util::normalize_unicode(input))
+            .unwrap()
+            .1
+    }
+
+    /// Parses an ingredient line according to the parser's [ParseMode].
+    /// ```
+    /// use ingredient::{IngredientParser, ParseMode};
+    /// let ip = IngredientParser::builder().mode(ParseMode::Lenient).build();
+    /// assert_eq!(ip.parse("1 cup flour").unwrap().to_string(), "1 cup flour");
+    ///
+    /// let ip = IngredientParser::builder().mode(ParseMode::Strict).build();
+    /// assert!(ip.parse("flour 00 type").is_err());
+    /// assert_eq!(ip.parse("salt").unwrap().name, "salt");
+    /// ```
+    pub fn parse(&self, input: &str) -> Result<Ingredient> {
+        let input = util::normalize_unicode(input);
+        if self.mode == ParseMode::Lenient {
+            return Ok(self.parse_lenient(&input).ingredient);
+        }
+        let (rest, ingredient) = match self.verbose_errors {
+            true => self.parse_ingredient(&input).map_err(|e| match e {
+                nom::Err::Error(e) | nom::Err::Failure(e) => anyhow::anyhow!(
+                    "failed to parse '{}':\n{}",
+                    input,
+                    nom::error::convert_error(input.as_str(), e)
+                ),
+                nom::Err::Incomplete(_) => anyhow::anyhow!("failed to parse '{}'", input),
+            })?,
+            false => self
+                .parse_ingredient_fast(&input)
+                .map_err(|e| anyhow::anyhow!("failed to parse '{}': {}", input, e))?,
+        };
+        if self.mode == ParseMode::Strict {
+            if !rest.trim().is_empty() {
+                return Err(anyhow::anyhow!(
+                    "unexpected '{}' after ingredient in '{}'",
+                    rest.trim(),
+                    input
+                ));
+            }
+            if let Some(w) = name_warning(&ingredient) {
+                return Err(anyhow::anyhow!("{} in '{}'", w, input));
+            }
+        }
+        Ok(ingredient)
+    }
+
+    /// Like [IngredientParser::from_str], but reports how much to trust the result
+    /// instead of panicking. If nothing parses, the whole line becomes the name.
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// let p = IngredientParser::new(false).parse_lenient("1 cup flour");
+    /// assert_eq!(p.confidence, 1.0);
+    /// let p = IngredientParser::new(false).parse_lenient("flour 00 type");
+    /// assert_eq!(p.ingredient.name, "flour 00 type");
+    /// assert!(p.confidence < 1.0);
+    /// assert_eq!(p.warnings.len(), 2);
+    /// ```
+    pub fn parse_lenient(&self, input: &str) -> ParsedIngredient {
+        let (mut ingredient, mut warnings) =
+            match self.parse_ingredient_fast(&util::normalize_unicode(input)) {
+                Ok((rest, ingredient)) => {
+                    let mut warnings = vec![];
+                    if !rest.trim().is_empty() {
+                        warnings.push(format!("ignored trailing text '{}'", rest.trim()));
+                    }
+                    (ingredient, warnings)
+                }
+                Err(e) => (
+                    Ingredient {
+                        name: input.trim().to_string(),
+                        ..Default::default()
+                    },
+                    vec![format!("could not parse: {e}")],
+                ),
+            };
+        // `1 cup plus 2 cloves garlic` can't be added up, so the second amount is left for
+        // the reader in the modifier, rather than in the name
+        if let Some(rest) = ingredient.name.strip_prefix("plus ") {
+            if let Ok((name, _)) = self.amount1::<nom::error::Error<&str>>(rest) {
+                let extra = format!("plus {}", rest[..rest.len() - name.len()].trim());
+                warnings.push(format!("could not add '{extra}' to the amount"));
+                ingredient.modifier = Some(match &ingredient.modifier {
+                    Some(m) => format!("{extra}, {m}"),
+                    None => extra,
+                });
+                ingredient.name = name.trim().to_string();
+            }
+        }
+        if ingredient.amounts.is_empty() {
+            warnings.push("no amount found".to_string());
+        }
+        warnings.extend(name_warning(&ingredient));
+        ParsedIngredient {
+            ingredient,
+            confidence: (1.0 - 0.25 * warnings.len() as f32).max(0.0),
+            warnings,
+        }
     }
 
     /// Parses one or two amounts, e.g. `12 grams` or `120 grams / 1 cup`. Used by [self.parse_ingredient].
+    /// Bare numbers, like a quantity column's, are counts of `whole`, see [Measure::inferred_unit].
     /// ```
     /// use ingredient::{IngredientParser,unit::Measure};
     /// let ip = IngredientParser::new(false);
@@ -105,7 +349,10 @@ impl IngredientParser {
     #[tracing::instrument(name = "parse_amount")]
     pub fn parse_amount(&self, input: &str) -> Result<Vec<Measure>> {
         // todo: also can't get this one to fail either
-        match self.clone().many_amount(input) {
+        match self
+            .clone()
+            .many_amount::<VerboseError<&str>>(&util::normalize_unicode(input))
+        {
             Ok((_, res)) => Ok(res),
             Err(e) => Err(anyhow::anyhow!(
                 "parse_amount on '{}' failed: {:?}",
@@ -117,11 +364,40 @@ impl IngredientParser {
     pub fn must_parse_amount(&self, input: &str) -> Vec<Measure> {
         self.parse_amount(input).expect("parse failed")
     }
+    /// Parses a length of time, e.g. `1 hour 30 minutes` or `1½ hours`.
+    /// Ranges such as `20-25 minutes` use the lower bound.
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// use std::time::Duration;
+    /// let ip = IngredientParser::new(false);
+    /// assert_eq!(ip.parse_duration("1 hour and 30 minutes").unwrap(), Duration::from_secs(5400));
+    /// assert_eq!(ip.parse_duration("90 min").unwrap(), Duration::from_secs(5400));
+    /// assert_eq!(ip.parse_duration("1½ hours").unwrap(), Duration::from_secs(5400));
+    /// assert!(ip.parse_duration("2 cups").is_err());
+    /// assert!(ip.parse_duration("99999999999999999999 hours").is_err());
+    /// ```
+    pub fn parse_duration(&self, input: &str) -> Result<std::time::Duration> {
+        let times: Vec<std::time::Duration> = self
+            .parse_amount(&input.replace(" and ", " "))?
+            .iter()
+            .filter_map(Measure::duration)
+            .collect();
+        if times.is_empty() {
+            return Err(anyhow::anyhow!("no time found in '{}'", input));
+        }
+        times
+            .into_iter()
+            .try_fold(std::time::Duration::ZERO, std::time::Duration::checked_add)
+            .ok_or_else(|| anyhow::anyhow!("'{}' is too long", input))
+    }
 
     /// Parse an ingredient line item, such as `120 grams / 1 cup whole wheat flour, sifted lightly`.
     ///
     /// returns an [Ingredient], Can be used as a wrapper to return verbose errors.
     ///
+    /// Unlike [IngredientParser::from_str], the input isn't passed through
+    /// [util::normalize_unicode] first, since the remaining input is returned.
+    ///
     /// supported formats include:
     /// * 1 g name
     /// * 1 g / 1g name, modifier
@@ -130,6 +406,8 @@ impl IngredientParser {
     /// * 1/4 g name
     /// * 1 ¼ g name
     /// * 1 1/4 g name
+    /// * 1 and 1/4 g name
+    /// * 1 ¼-2 g name
     /// * 1 g (1 g) name
     /// * 1 g name (about 1 g; 1 g)
     /// * name
@@ -148,22 +426,138 @@ impl IngredientParser {
     ///                 Measure::parse_new("grams", 155.5),
     ///             ],
     ///             modifier: None,
+    ///             ..Default::default()
     ///         }
     ///     ))
     /// );
     /// ```
     #[tracing::instrument(name = "parse_ingredient")]
+    pub fn parse_ingredient<'a>(&self, input: &'a str) -> Res<&'a str, Ingredient> {
+        self.parse_ingredient_generic(input)
+    }
+
+    /// Same as [IngredientParser::parse_ingredient], but without building up a
+    /// [VerboseError] along the way, which is noticeably faster for large batches.
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// let ip = IngredientParser::new(false);
+    /// assert_eq!(
+    ///     ip.clone().parse_ingredient_fast("1 cup flour").unwrap().1,
+    ///     ip.from_str("1 cup flour")
+    /// );
+    /// ```
+    pub fn parse_ingredient_fast<'a>(&self, input: &'a str) -> IResult<&'a str, Ingredient> {
+        self.parse_ingredient_generic(input)
+    }
+
+    fn parse_ingredient_generic<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, Ingredient, E> {
+        alt((|a| self.inverted_ingredient(a), |a| self.ingredient_line(a)))(input).map(
+            |(next_input, mut ingredient)| {
+                if let Some((percentage, label, rest)) = ingredient
+                    .modifier
+                    .as_deref()
+                    .and_then(bakers_percentage::take_percentage)
+                {
+                    ingredient.percentage = Some(percentage);
+                    ingredient.percentage_label = label;
+                    ingredient.modifier = Some(rest).filter(|m| !m.is_empty());
+                }
+                ingredient.temperature = ingredient
+                    .modifier
+                    .as_deref()
+                    .and_then(temperature::find_temperature);
+                // `water (110°F)` is how warm the water is, not how much of it there is
+                let in_amounts = ingredient
+                    .amounts
+                    .iter()
+                    .find_map(temperature::Temperature::from_measure);
+                ingredient
+                    .amounts
+                    .retain(|a| temperature::Temperature::from_measure(a).is_none());
+                ingredient.temperature = ingredient.temperature.or(in_amounts);
+                if let Some((note, rest)) = ingredient
+                    .modifier
+                    .as_deref()
+                    .and_then(|m| self.take_note(m))
+                {
+                    ingredient.note = Some(note);
+                    ingredient.modifier = Some(rest).filter(|m| !m.is_empty());
+                }
+                (next_input, ingredient)
+            },
+        )
+    }
+
+    // parses `zest of 1 lemon, finely grated` into `1 lemon` of `lemon zest`
+    fn inverted_ingredient<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, Ingredient, E> {
+        context(
+            "inverted_ingredient",
+            tuple((
+                inverted_part,
+                space1,
+                tag_no_case("of"),
+                space1,
+                |a| self.get_value(a),
+                space1,
+                take_while1(is_name_char), // the thing the part comes from, e.g. `large lemons`
+                opt(tag(", ")),
+                not_line_ending,
+            )),
+        )(input)
+        .map(|(next_input, res)| {
+            let (part, _, _, _, (value, upper), _, source, _, modifier) = res;
+            // `large` in `1 large lemon` describes the lemon rather than its zest
+            let mut source = source.trim().to_lowercase();
+            let mut described = vec![];
+            while let Some(d) = normalize::DESCRIPTORS
+                .iter()
+                .find(|d| normalize::strip_word(&source, d).is_some())
+            {
+                source = normalize::strip_word(&source, d)
+                    .unwrap_or_default()
+                    .to_string();
+                described.push(*d);
+            }
+            let source = normalize::singular_name(&source);
+            let head = source.rsplit(' ').next().unwrap_or_default();
+            let modifier = described
+                .into_iter()
+                .chain(Some(modifier.trim()).filter(|m| !m.is_empty()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (
+                next_input,
+                Ingredient {
+                    name: format!("{source} {}", part.to_lowercase()),
+                    amounts: vec![Measure::from_parts(head, value, upper)],
+                    modifier: Some(modifier).filter(|m| !m.is_empty()),
+                    ..Default::default()
+                },
+            )
+        })
+    }
+
     #[allow(clippy::type_complexity)]
-    pub fn parse_ingredient(self, input: &str) -> Res<&str, Ingredient> {
+    fn ingredient_line<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, Ingredient, E> {
         context(
             "ing",
             tuple((
-                opt(|a| self.clone().many_amount(a)),
+                opt(|a| self.multiplied_amounts(a)),
                 space0, // space between amount(s) and name
-                opt(tuple((|a| self.clone().adjective(a), space1))), // optional modifier
-                opt(many1(text)), // name, can be multiple words
-                opt(|a| self.clone().amt_parens(a)), // can have some more amounts in parens after the name
-                opt(tag(", ")),                      // comma seperates the modifier
+                opt(tuple((|a| self.adjective(a), space1))), // optional modifier
+                opt(take_while1(is_name_char)), // name, can be multiple words
+                opt(footnote_marker), // `*` or `†`, pointing to the recipe's notes
+                opt(|a| self.amt_parens(a)), // can have some more amounts in parens after the name
+                opt(tag(", ")), // comma seperates the modifier
                 not_line_ending, // modifier, can be multiple words and even include numbers, since once we've hit the comma everything is fair game.
             )),
         )(input)
@@ -172,28 +566,53 @@ impl IngredientParser {
                 amounts,
                 _maybespace,
                 adjective,
-                name_chunks,
+                raw_name,
+                footnote,
                 amounts2,
                 _maybecomma,
                 modifier_chunks,
             ): (
-                Option<Vec<Measure>>,
+                Option<(Option<f64>, Vec<Measure>)>,
                 &str,
                 Option<(String, &str)>,
-                Option<Vec<String>>,
+                Option<&str>,
+                Option<&str>,
                 Option<Vec<Measure>>,
                 Option<&str>,
                 &str,
             ) = res;
-            let mut modifiers: String = modifier_chunks.to_owned();
+            // before alternatives, so `or to taste` isn't taken for one
+            // a marker can also end the line, e.g. `1 cup flour, sifted*`
+            let (modifier_chunks, footnote) = match footnote {
+                Some(f) => (modifier_chunks, Some(f)),
+                None => {
+                    let trimmed = modifier_chunks.trim_end_matches(FOOTNOTE_MARKERS);
+                    let marker = &modifier_chunks[trimmed.len()..];
+                    (trimmed, Some(marker).filter(|m| !m.is_empty()))
+                }
+            };
+            let (mut modifiers, mut flexibility) = take_flexibility(modifier_chunks);
+            let mut alternatives = vec![];
+            // `butter, or margarine`, but not `or more if needed`, which stays a modifier
+            match modifiers.strip_prefix("or ") {
+                Some(alt) if !starts_with_word(alt, &["more", "less"]) => {
+                    alternatives.extend(split_alternatives(alt));
+                    modifiers = "".to_string();
+                }
+                _ => {}
+            }
+            let (rest, mut divided, mut extra_for, mut purpose) = usage_flags(&modifiers);
+            modifiers = rest;
             if let Some((adjective, _)) = adjective {
                 modifiers.push_str(&adjective);
             }
-            let mut name: String = name_chunks
-                .unwrap_or(vec![])
-                .join("")
-                .trim_matches(' ')
-                .to_string();
+            let (mut name, name_divided, name_extra_for, name_purpose) =
+                usage_flags(raw_name.unwrap_or_default().trim_matches(' '));
+            divided |= name_divided;
+            extra_for = extra_for.or(name_extra_for);
+            purpose = purpose.or(name_purpose);
+            let (rest, name_flexibility) = take_flexibility(&name);
+            name = rest;
 
             // if the ingredient name still has adjective in it, remove that
             self.adjectives.iter().for_each(|f| {
@@ -202,15 +621,20 @@ impl IngredientParser {
                     name = name.replace(f, "").trim_matches(' ').to_string();
                 }
             });
+            let (rest, adjective_flexibility) = take_flexibility(&modifiers);
+            modifiers = rest;
+            flexibility = flexibility.or(name_flexibility).or(adjective_flexibility);
 
-            let mut amounts = match amounts {
-                Some(a) => a,
-                None => vec![],
-            };
-            amounts = match amounts2 {
-                Some(a) => amounts.into_iter().chain(a.into_iter()).collect(),
-                None => amounts,
-            };
+            // `butter or margarine`, but not `red or white wine` where the words
+            // before the `or` describe the noun after it
+            let mut names = split_alternatives(&name);
+            let shared_noun = names.len() == 2 && !names[0].contains(' ') && names[1].contains(' ');
+            if names.len() > 1 && !shared_noun {
+                name = names.remove(0);
+                alternatives.splice(0..0, names);
+            }
+
+            let (multiplier, amounts) = combine_amounts(amounts, amounts2);
 
             (
                 next_input,
@@ -221,27 +645,35 @@ impl IngredientParser {
                         0 => None,
                         _ => Some(modifiers.to_string()),
                     },
+                    alternatives,
+                    divided,
+                    extra_for,
+                    purpose,
+                    flexibility,
+                    footnote: footnote.map(str::to_string),
+                    multiplier,
+                    ..Default::default()
                 },
             )
         })
     }
-    fn get_value(self, input: &str) -> Res<&str, (f64, Option<f64>)> {
+    fn get_value<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, (f64, Option<f64>), E> {
         context(
             "get_value",
-            alt((
-                |a| self.clone().upper_range_only(a),
-                |a| self.clone().num_or_range(a),
-            )),
+            alt((|a| self.upper_range_only(a), |a| self.num_or_range(a))),
         )(input)
     }
 
-    fn num_or_range(self, input: &str) -> Res<&str, (f64, Option<f64>)> {
+    fn num_or_range<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, (f64, Option<f64>), E> {
         context(
             "num_or_range",
-            tuple((
-                |a| self.clone().num(a),
-                opt(|a| self.clone().range_up_num(a)),
-            )),
+            tuple((|a| self.num(a), opt(|a| self.range_up_num(a)))),
         )(input)
         .map(|(next_input, res)| {
             let (val, upper_val) = res;
@@ -250,26 +682,51 @@ impl IngredientParser {
         })
     }
 
-    fn upper_range_only(self, input: &str) -> Res<&str, (f64, Option<f64>)> {
+    fn upper_range_only<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, (f64, Option<f64>), E> {
         context(
             "upper_range_only",
             tuple((
                 opt(space0),
                 alt((tag("up to"), tag("at most"))),
                 space0,
-                |a| self.clone().num(a),
+                |a| self.num(a),
             )),
         )(input)
         .map(|(next_input, res)| (next_input, (0.0, Some(res.3))))
     }
 
-    fn unit(self, input: &str) -> Res<&str, String> {
+    // the unit may directly follow the value (`2tbsp`), in which case it still has
+    // to be the whole word: `1cupsugar` has no unit
+    fn unit<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, String, E> {
         context(
             "unit",
-            verify(unitamt, |s: &str| unit::is_valid(self.units.clone(), s)),
+            alt((
+                fluid_ounce,
+                map(verify(unitamt, |s: &str| self.is_unit(s)), |s| {
+                    self.canonical_unit(s)
+                }),
+            )),
         )(input)
     }
-    fn unit_extra(self, input: &str) -> Res<&str, String> {
+    fn is_unit(&self, s: &str) -> bool {
+        unit::is_valid(self.units.clone(), s) || self.unit_alias(s).is_some()
+    }
+    // as written first, for aliases that end in `s` like `càs`
+    fn unit_alias(&self, s: &str) -> Option<&String> {
+        self.unit_aliases
+            .get(&s.to_lowercase())
+            .or_else(|| self.unit_aliases.get(&unit::singular(s)))
+    }
+    fn canonical_unit(&self, s: String) -> String {
+        match self.unit_alias(&s) {
+            Some(u) => u.clone(),
+            None => s,
+        }
+    }
+    fn unit_extra<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, String, E> {
         context(
             "unit",
             verify(unitamt, |s: &str| {
@@ -277,7 +734,7 @@ impl IngredientParser {
             }),
         )(input)
     }
-    fn adjective(self, input: &str) -> Res<&str, String> {
+    fn adjective<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, String, E> {
         context(
             "adjective",
             verify(unitamt, |s: &str| {
@@ -287,37 +744,82 @@ impl IngredientParser {
     }
 
     // parses a single amount
-    fn amount1(self, input: &str) -> Res<&str, Measure> {
+    fn amount1<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, Measure, E> {
         context(
             "amount1",
             tuple(
                 (
+                    not(|a| self.numbered_name(a)),
                     opt(tag("about ")), // todo: add flag for estimates
-                    opt(|a| self.clone().mult_prefix_1(a)),
-                    |a| self.clone().get_value(a), // value
+                    opt(bound_prefix),
+                    opt(|a| self.mult_prefix_1(a)),
+                    |a| self.get_value(a), // value
                     space0,
-                    opt(|a| self.clone().unit(a)), // unit
+                    alt((
+                        map(of_article, |_| None), // `½ of a lemon` is half of one whole lemon
+                        opt(|a| self.unit(a)),     // unit
+                    )),
                     opt(alt((tag("."), tag(" of")))),
                 ), // 1 gram
             ),
         )(input)
         .map(|(next_input, res)| {
-            let (_prefix, mult, value, _space, unit, _period) = res;
-            let mut v = value.0;
+            let (_, _prefix, bound, mult, value, _space, unit, _period) = res;
+            let (mut v, mut upper) = value;
             if let Some(m) = mult {
                 v *= m
             }
+            // `up to 3` is from nothing to 3
+            if bound == Some(Bound::AtMost) && upper.is_none() {
+                upper = Some(v);
+                v = 0.0;
+            }
+            let measure = self.measure_from_parts(unit, v, upper);
             return (
                 next_input,
-                Measure::from_parts(
-                    unit.unwrap_or("whole".to_string()).to_lowercase().as_ref(),
-                    v,
-                    value.1,
-                ),
+                match bound {
+                    Some(b) => measure.with_bound(b),
+                    None => measure,
+                },
             );
         })
     }
-    fn just_extra_unit(self, input: &str) -> Res<&str, Measure> {
+    // a name that starts with a number, e.g. `5-spice powder`, `7-Up` or `half-and-half`,
+    // rather than an amount, which `1-inch piece` still is
+    fn numbered_name<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, &'a str, E> {
+        recognize(tuple((
+            |a| self.num(a),
+            char('-'),
+            verify(letters1, |w: &str| !self.is_unit(w)),
+        )))(input)
+    }
+    // pulls the first parenthetical that isn't an amount or a temperature, e.g. `(see note)`,
+    // out of a modifier, returning it along with the rest of the modifier
+    fn take_note(&self, s: &str) -> Option<(String, String)> {
+        s.match_indices('(').find_map(|(open, _)| {
+            let close = open + s[open..].find(')')?;
+            let inner = s[open + 1..close].trim();
+            let is_amount = matches!(
+                self.many_amount::<nom::error::Error<&str>>(inner),
+                Ok((rest, _)) if rest.trim().is_empty()
+            );
+            if inner.is_empty() || is_amount || temperature::find_temperature(inner).is_some() {
+                return None;
+            }
+            let rest = format!("{}{}", s[..open].trim_end(), &s[close + 1..]);
+            let rest = rest.trim().trim_start_matches(',').trim_end_matches(',');
+            Some((inner.to_string(), rest.trim().to_string()))
+        })
+    }
+    // falls back to the bare count unit when no unit was written
+    fn measure_from_parts(&self, unit: Option<String>, value: f64, upper: Option<f64>) -> Measure {
+        match (unit, &self.bare_count_unit) {
+            (Some(unit), _) => Measure::from_parts(&unit.to_lowercase(), value, upper),
+            (None, Some(bare)) => Measure::from_parts(bare, value, upper).with_inferred_unit(),
+            (None, None) => Measure::from_parts("", value, upper),
+        }
+    }
+    fn just_extra_unit<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, Measure, E> {
         context(
             "just_extra_unit",
             tuple((
@@ -328,7 +830,7 @@ impl IngredientParser {
                         space0(a)
                     }
                 },
-                |a| self.clone().unit_extra(a), // unit
+                |a| self.unit_extra(a), // unit
                 opt(alt((tag("."), tag(" of")))),
                 space1,
             )),
@@ -342,154 +844,504 @@ impl IngredientParser {
         })
     }
     // parses an amount like `78g to 104g cornmeal`
-    fn amount_with_units_twice(self, input: &str) -> Res<&str, Option<Measure>> {
+    fn amount_with_units_twice<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, Measure, E> {
         context(
             "amount_with_units_twice",
-            tuple((
-                opt(tag("about ")),            // todo: add flag for estimates
-                |a| self.clone().get_value(a), // value
-                space0,
-                opt(|a| self.clone().unit(a)), // unit
-                |a| self.clone().range_up_num(a),
-                opt(|a| self.clone().unit(a)),
-                opt(alt((tag("."), tag(" of")))),
-            )),
+            verify(
+                tuple((
+                    opt(tag("about ")),    // todo: add flag for estimates
+                    |a| self.get_value(a), // value
+                    space0,
+                    opt(|a| self.unit(a)), // unit
+                    |a| self.range_up_num(a),
+                    opt(preceded(space0, |a| self.unit(a))),
+                    opt(alt((tag("."), tag(" of")))),
+                )),
+                // `1 cup or 240 ml` isn't a range, but two equivalent amounts
+                |(_, _, _, unit, _, upper_unit, _)| match (unit, upper_unit) {
+                    (_, None) => true,
+                    (Some(u), Some(upper)) => unit::singular(u) == unit::singular(upper),
+                    (None, Some(_)) => false,
+                },
+            ),
         )(input)
         .map(|(next_input, res)| {
-            let (_prefix, value, _space, unit, upper_val, upper_unit, _period) = res;
-            if upper_unit.is_some() && unit != upper_unit {
-                info!("unit mismatch: {:?} vs {:?}", unit, upper_unit);
-                // panic!("unit mismatch: {:?} vs {:?}", unit, upper_unit)
-                return (next_input, None);
-            }
-            // let upper = match value.1 {
-            //     Some(u) => Some(u),
-            //     None => upper_val,
-            //      match upper_val {
-            //         Some(u) => Some(u),
-            //         None => None,
-            //     },
-            // };
-            let upper = Some(upper_val);
-            return (
+            let (_prefix, value, _space, unit, upper_val, _upper_unit, _period) = res;
+            (
                 next_input,
-                Some(Measure::from_parts(
-                    unit.unwrap_or("whole".to_string()).to_lowercase().as_ref(),
-                    value.0,
-                    upper,
-                )),
-            );
+                self.measure_from_parts(unit, value.0, Some(upper_val)),
+            )
         })
     }
     // parses 1-n amounts, e.g. `12 grams` or `120 grams / 1 cup`
     #[tracing::instrument(name = "many_amount")]
-    fn many_amount(self, input: &str) -> Res<&str, Vec<Measure>> {
+    fn many_amount<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, Vec<Measure>, E> {
         context(
             "many_amount",
             separated_list1(
-                alt((tag("; "), tag(" / "), tag(" "), tag(", "), tag("/"))),
+                // longer separators first, so ` = ` isn't taken as a space
+                alt((
+                    tag("; "),
+                    tag(" / "),
+                    tag(" // "),
+                    tag(" = "),
+                    tag(" | "),
+                    tag(" or "),
+                    tag(" "),
+                    tag(", "),
+                    tag("//"),
+                    tag("/"),
+                    tag("="),
+                    tag("|"),
+                )),
                 alt((
-                    |a| self.clone().plus_amount(a).map(|(a, b)| (a, vec![b])),
-                    |a| {
-                        self.clone().amount_with_units_twice(a).map(|(a, b)| {
-                            (
-                                a,
-                                match b {
-                                    Some(a) => vec![a],
-                                    None => vec![],
-                                },
-                            )
-                        })
-                    }, // regular amount
-                    |a| self.clone().amt_parens(a), // amoiunt with parens
-                    |a| self.clone().amount1(a).map(|(a, b)| (a, vec![b])), // regular amount
-                    |a| self.clone().just_extra_unit(a).map(|(a, b)| (a, vec![b])), // regular amount
+                    |a| self.plus_amount(a).map(|(a, b)| (a, vec![b])),
+                    |a| self.amount_with_units_twice(a).map(|(a, b)| (a, vec![b])), // regular amount
+                    |a| self.amt_parens(a), // amoiunt with parens
+                    |a| self.amount1(a).map(|(a, b)| (a, vec![b])), // regular amount
+                    |a| self.just_extra_unit(a).map(|(a, b)| (a, vec![b])), // regular amount
                 )),
             ),
         )(input)
-        .map(|(next_input, res)| {
-            // let (a, b) = res;
-            (next_input, res.into_iter().flatten().collect())
-        })
+        .map(|(next_input, res)| (next_input, dedupe_amounts(res.into_iter().flatten())))
     }
 
-    fn amt_parens(self, input: &str) -> Res<&str, Vec<Measure>> {
+    fn amt_parens<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, Vec<Measure>, E> {
         context(
             "amt_parens",
-            delimited(char('('), |a| self.clone().many_amount(a), char(')')),
+            delimited(
+                tuple((char('('), opt(tag("or ")))),
+                |a| self.many_amount(a),
+                char(')'),
+            ),
         )(input)
+        .map(|(next_input, amounts)| {
+            let amounts = amounts
+                .into_iter()
+                .map(|a| a.with_source(Source::Parenthetical))
+                .collect();
+            (next_input, amounts)
+        })
     }
     /// handles vulgar fraction, or just a number
-    fn num(self, input: &str) -> Res<&str, f64> {
+    fn num<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, f64, E> {
         if self.is_rich_text {
-            context("num", alt((fraction_number, double)))(input)
+            context("num", alt((fraction_number, decimal)))(input)
         } else {
-            context("num", alt((fraction_number, text_number, double)))(input)
+            context(
+                "num",
+                alt((
+                    fraction_number,
+                    |a| self.fraction_phrase(a),
+                    |a| self.text_number(a),
+                    decimal,
+                )),
+            )(input)
         }
     }
-    fn mult_prefix_1(self, input: &str) -> Res<&str, f64> {
+    fn text_number<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, f64, E> {
+        context(
+            "text_number",
+            map_opt(letters1, |s: &str| {
+                self.number_words.get(&s.to_lowercase()).copied()
+            }),
+        )(input)
+    }
+    // `half a`, `a quarter of a`, `a half`
+    fn fraction_phrase<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, f64, E> {
+        let word = |words: &'static [&'static str]| {
+            verify(letters1, move |s: &str| {
+                words.iter().any(|w| w.eq_ignore_ascii_case(s))
+            })
+        };
+        let article = || word(&["a", "an"]);
+        context(
+            "fraction_phrase",
+            tuple((
+                opt(tuple((article(), space1))),
+                word(&["half", "quarter", "third"]),
+                opt(tuple((
+                    space1,
+                    opt(tuple((tag_no_case("of"), space1))),
+                    article(),
+                ))),
+            )),
+        )(input)
+        .map(|(next_input, (_, fraction, _))| {
+            let value = match fraction.to_lowercase().as_str() {
+                "half" => 0.5,
+                "quarter" => 0.25,
+                _ => 1.0 / 3.0,
+            };
+            (next_input, value)
+        })
+    }
+    // the amounts at the start of a line, and how many times over they're meant
+    fn multiplied_amounts<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, (Option<f64>, Vec<Measure>), E> {
+        alt((
+            map(|a| self.sized_count(a), |(n, amounts)| (Some(n), amounts)),
+            map(
+                tuple((|a| self.mult_prefix_1(a), |a| self.many_amount(a))),
+                |(n, amounts)| (Some(n), amounts),
+            ),
+            map(|a| self.many_amount(a), |amounts| (None, amounts)),
+        ))(input)
+    }
+    // `2 (15-ounce) cans` or `2 cans (14 oz each)`, which are `2 x 15 oz cans`
+    fn sized_count<'a, E: ParseErr<'a>>(
+        &self,
+        input: &'a str,
+    ) -> IResult<&'a str, (f64, Vec<Measure>), E> {
+        let count_unit = |a| verify(|a| self.unit(a), |u: &str| unit::is_count_unit(u))(a);
+        let size = |a| {
+            tuple((|a| self.num(a), opt(char('-')), space0, |a| self.unit(a)))(a).map(
+                |(next_input, (value, _, _, unit))| {
+                    (next_input, self.measure_from_parts(Some(unit), value, None))
+                },
+            )
+        };
+        context(
+            "sized_count",
+            alt((
+                map(
+                    tuple((
+                        |a| self.num(a),
+                        space0,
+                        delimited(char('('), size, char(')')),
+                        space0,
+                        count_unit,
+                    )),
+                    |(count, _, size, _, unit)| (count, size, unit),
+                ),
+                map(
+                    tuple((
+                        |a| self.num(a),
+                        space1,
+                        count_unit,
+                        space0,
+                        delimited(
+                            char('('),
+                            terminated(size, tuple((space1, tag_no_case("each")))),
+                            char(')'),
+                        ),
+                    )),
+                    |(count, _, unit, _, size)| (count, size, unit),
+                ),
+            )),
+        )(input)
+        .map(|(next_input, (count, size, unit))| {
+            let unit = Measure::from_parts(&unit.to_lowercase(), 1.0, None);
+            (next_input, (count, vec![size, unit]))
+        })
+    }
+    // `2 x `, `2x` or `2×`
+    fn mult_prefix_1<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, f64, E> {
         context(
             "mult_prefix_1",
-            tuple((|a| self.clone().num(a), space1, tag("x"), space1)),
+            tuple((
+                |a| self.num(a),
+                space0,
+                alt((tag("x"), tag("X"), tag("×"))),
+                space0,
+                peek(|a| self.num(a)), // not `2 xl eggs`
+            )),
         )(input)
         .map(|(next_input, res)| {
-            let (num, _, _, _) = res;
+            let (num, _, _, _, _) = res;
             (next_input, num)
         })
     }
-    fn range_up_num(self, input: &str) -> Res<&str, f64> {
+    fn range_up_num<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, f64, E> {
         context(
             "range_up_num",
             alt((
                 tuple((
                     space0,
-                    alt((tag("-"), tag("–"))), // second dash is an unusual variant
+                    alt((tag("-"), tag("–"), tag("—"))), // en and em dashes are unusual variants
                     space0,
-                    |a| self.clone().num(a),
+                    |a| self.num(a),
                 )),
                 tuple((
                     space1,
                     alt((tag("to"), tag("through"), tag("or"))),
                     space1,
-                    |a| self.clone().num(a),
+                    |a| self.num(a),
                 )),
             )),
         )(input)
         .map(|(next_input, (_space1, _, _space2, num))| (next_input, num))
     }
-    fn plus_amount(self, input: &str) -> Res<&str, Measure> {
+    fn plus_amount<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, Measure, E> {
         context(
             "plus_num",
-            tuple((
-                |a| self.clone().amount1(a),
-                space1,
-                tag("plus"),
-                space1,
-                |a| self.clone().amount1(a),
-            )),
+            // amounts that can't be added, like `1 cup plus 2 cloves`, aren't a sum
+            map_opt(
+                tuple((
+                    |a| self.amount1(a),
+                    space1,
+                    tag("plus"),
+                    space1,
+                    |a| self.amount1(a),
+                )),
+                |(a, _, _, _, b)| a.add(b).ok(),
+            ),
         )(input)
-        .map(|(next_input, (a, _space1, _, _, b))| {
-            let c = a.add(b).unwrap();
-            (next_input, c)
-        })
     }
 }
 
-fn text(input: &str) -> Res<&str, String> {
-    (satisfy(|c| match c {
+fn name_warning(ingredient: &Ingredient) -> Option<String> {
+    if ingredient.name.is_empty() {
+        Some("no name found".to_string())
+    } else if ingredient
+        .name
+        .split_whitespace()
+        // but not `5-spice`
+        .any(|w| w.contains(|c: char| c.is_ascii_digit()) && !w.contains('-'))
+    {
+        Some("name contains numbers, an amount may have been missed".to_string())
+    } else {
+        None
+    }
+}
+
+/// what an ingredient that's only `for ...` tends to be for. In a name, only these count,
+/// so that `stock for soup` keeps its name.
+const PURPOSES: &[&str] = &[
+    "serving",
+    "garnish",
+    "garnishing",
+    "dusting",
+    "greasing",
+    "frying",
+    "brushing",
+    "drizzling",
+    "decoration",
+    "decorating",
+    "topping",
+    "the pan",
+    "the pot",
+    "the grill",
+];
+
+/// pulls `divided`, `plus more for serving` and `for garnish` out of a name or modifier,
+/// returning what's left along with the flags
+fn usage_flags(s: &str) -> (String, bool, Option<String>, Option<String>) {
+    let mut divided = false;
+    let mut extra_for = None;
+    let mut purpose = None;
+    let mut rest = vec![];
+    for part in s.split(", ") {
+        let mut part = part.trim();
+        let markers = ["plus more for ", "plus extra for ", "and more for "];
+        if let Some((idx, len)) = markers
+            .iter()
+            .find_map(|m| part.find(m).map(|idx| (idx, m.len())))
+        {
+            extra_for = Some(part[idx + len..].trim().to_string());
+            part = part[..idx].trim();
+        }
+        let unwrapped = part
+            .strip_prefix('(')
+            .and_then(|p| p.strip_suffix(')'))
+            .unwrap_or(part);
+        if is_purpose(unwrapped) {
+            purpose = Some(unwrapped.to_string());
+            continue;
+        }
+        // `flour for dusting`
+        if let Some(idx) = part.find(" for ").filter(|&i| is_purpose(&part[i + 1..])) {
+            purpose = Some(part[idx + 1..].to_string());
+            part = part[..idx].trim();
+        }
+        if part.eq_ignore_ascii_case("divided") {
+            divided = true;
+        } else if let Some(p) = part.strip_suffix(" divided") {
+            divided = true;
+            rest.push(p);
+        } else if !part.is_empty() {
+            rest.push(part);
+        }
+    }
+    (rest.join(", "), divided, extra_for, purpose)
+}
+// `for the pan`, `to serve`
+fn is_purpose(s: &str) -> bool {
+    let s = s.to_lowercase();
+    match s.strip_prefix("for ") {
+        Some(p) => PURPOSES.contains(&p),
+        None => matches!(s.as_str(), "to serve" | "to garnish" | "to decorate"),
+    }
+}
+/// pulls `or to taste`, `or more` and `as needed` out of a name or modifier
+fn take_flexibility(s: &str) -> (String, Option<Flexibility>) {
+    #[rustfmt::skip]
+    let phrases = [
+        ("or more as needed", Flexibility::OrMore), ("or more to taste", Flexibility::OrMore),
+        ("or more", Flexibility::OrMore), ("or to taste", Flexibility::ToTaste),
+        ("to taste", Flexibility::ToTaste), ("or as needed", Flexibility::AsNeeded),
+        ("as needed", Flexibility::AsNeeded),
+    ];
+    let mut flexibility = None;
+    let mut rest = vec![];
+    for part in s.split(", ") {
+        let lower = part.trim().to_lowercase();
+        match phrases
+            .iter()
+            .find(|(p, _)| lower == *p || lower.ends_with(&format!(" {p}")))
+        {
+            Some((p, f)) => {
+                flexibility = flexibility.or(Some(*f));
+                let kept = part.trim()[..lower.len() - p.len()].trim_end();
+                if !kept.is_empty() {
+                    rest.push(kept);
+                }
+            }
+            None if !part.trim().is_empty() => rest.push(part.trim()),
+            None => {}
+        }
+    }
+    (rest.join(", "), flexibility)
+}
+// the amounts before the name and the ones in parens after it, where `2 x 400 g cans`
+// is 800 g in 2 cans
+fn combine_amounts(
+    amounts: Option<(Option<f64>, Vec<Measure>)>,
+    amounts2: Option<Vec<Measure>>,
+) -> (Option<Multiplier>, Vec<Measure>) {
+    let (count, mut amounts) = amounts.unwrap_or_default();
+    let multiplier = count
+        .zip(amounts.first().cloned())
+        .map(|(count, each)| Multiplier { count, each });
+    if let Some(n) = count {
+        amounts = amounts.into_iter().map(|a| a * n).collect();
+    }
+    let amounts = match amounts2 {
+        Some(a) => dedupe_amounts(amounts.into_iter().chain(a)),
+        None => amounts,
+    };
+    (multiplier, amounts)
+}
+/// drops amounts that restate an earlier one, e.g. the `1 cup` or `16 tbsp` of
+/// `1 cup (1 cup; 240 ml)` or `1 cup (16 tbsp)`, but not a conversion like the `240 ml`
+fn dedupe_amounts(amounts: impl IntoIterator<Item = Measure>) -> Vec<Measure> {
+    let mut kept: Vec<Measure> = vec![];
+    for a in amounts {
+        let n = a.normalize();
+        let same = |k: &Measure| {
+            let k = k.normalize();
+            let close = |x: f64, y: f64| (x - y).abs() < 1e-9 * x.abs().max(1.0);
+            k.unit() == n.unit()
+                && close(k.values().0, n.values().0)
+                && match (k.values().1, n.values().1) {
+                    (Some(x), Some(y)) => close(x, y),
+                    (x, y) => x == y,
+                }
+        };
+        if !kept.iter().any(same) {
+            kept.push(a);
+        }
+    }
+    kept
+}
+// whether `s` starts with one of `words`, in any case
+fn starts_with_word(s: &str, words: &[&str]) -> bool {
+    let first = s.split_whitespace().next().unwrap_or_default();
+    words.iter().any(|w| first.eq_ignore_ascii_case(w))
+}
+fn split_alternatives(s: &str) -> Vec<String> {
+    s.split(" or ")
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+/// what `zest of 1 lemon` takes from the lemon, longest first
+pub(crate) const INVERTED_PARTS: &[&str] = &[
+    "zest and juice",
+    "juice and zest",
+    "zest",
+    "juice",
+    "peel",
+    "rind",
+];
+fn inverted_part<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    INVERTED_PARTS
+        .iter()
+        .find_map(|p| tag_no_case::<_, _, E>(*p)(input).ok())
+        .ok_or_else(|| nom::Err::Error(E::from_error_kind(input, nom::error::ErrorKind::Tag)))
+}
+fn is_name_char(c: char) -> bool {
+    match c {
         '-' | '—' | '\'' | '’' | '.' | '\\' => true,
         c => c.is_alphanumeric() || c.is_whitespace(),
-    }))(input)
-    .map(|(next_input, res)| (next_input, res.to_string()))
+    }
 }
-fn unitamt(input: &str) -> Res<&str, String> {
-    nom::multi::many0(alt((alpha1, tag("°"), tag("\""))))(input)
-        .map(|(next_input, res)| (next_input, res.join("")))
+// `at least `, `up to ` or `no more than `
+fn bound_prefix<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, Bound, E> {
+    terminated(
+        alt((
+            map(
+                alt((tag_no_case("at least"), tag_no_case("no less than"))),
+                |_| Bound::AtLeast,
+            ),
+            map(
+                alt((
+                    tag_no_case("up to"),
+                    tag_no_case("at most"),
+                    tag_no_case("no more than"),
+                )),
+                |_| Bound::AtMost,
+            ),
+        )),
+        space1,
+    )(input)
 }
-
-fn text_number(input: &str) -> Res<&str, f64> {
-    context("text_number", alt((tag("one"), tag("a "))))(input)
-        .map(|(next_input, _)| (next_input, 1.0))
+const FOOTNOTE_MARKERS: &[char] = &['*', '†', '‡'];
+// `*`, `**` or `†` right after a name
+fn footnote_marker<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    preceded(
+        space0,
+        recognize(many1(satisfy(|c| FOOTNOTE_MARKERS.contains(&c)))),
+    )(input)
+}
+// the one unit that's two words, `fl oz`, `fl. oz.` or `floz`
+fn fluid_ounce<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, String, E> {
+    tuple((
+        alt((tag_no_case("fluid"), tag_no_case("fl"))),
+        opt(char('.')),
+        space0,
+        alt((
+            tag_no_case("ounces"),
+            tag_no_case("ounce"),
+            tag_no_case("oz"),
+        )),
+    ))(input)
+    .map(|(next_input, _)| (next_input, "fl oz".to_string()))
+}
+// `of a` or `of an`, leaving the space before the name
+fn of_article<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    recognize(tuple((
+        tag_no_case("of"),
+        space1,
+        alt((tag_no_case("an"), tag_no_case("a"))),
+        peek(space1),
+    )))(input)
+}
+// like `alpha1`, but for any letters rather than only ascii ones, e.g. `stück` or `càs`
+pub(crate) fn letters1<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    take_while1(char::is_alphabetic)(input)
+}
+fn unitamt<'a, E: ParseErr<'a>>(input: &'a str) -> IResult<&'a str, String, E> {
+    nom::multi::many0(alt((letters1, tag("°"), tag("\""))))(input)
+        .map(|(next_input, res)| (next_input, res.join("")))
 }
 
 #[cfg(test)]
@@ -509,6 +1361,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unitless_amounts() {
+        let ip = IngredientParser::new(false);
+        for (input, value, upper) in [
+            ("2", 2.0, None),
+            ("½", 0.5, None),
+            ("1 1/2", 1.5, None),
+            ("2-3", 2.0, Some(3.0)),
+            ("2 to 3", 2.0, Some(3.0)),
+        ] {
+            let amounts = ip.must_parse_amount(input);
+            assert_eq!(amounts.len(), 1, "{input}");
+            let (v, u, unit) = amounts[0].values();
+            assert_eq!((v, u, unit.as_str()), (value, upper, "whole"), "{input}");
+            assert!(amounts[0].inferred_unit(), "{}", input);
+        }
+    }
+
     #[test]
     fn test_amount_range() {
         assert_eq!(
@@ -522,6 +1392,7 @@ mod tests {
                 name: "flour".to_string(),
                 amounts: vec![Measure::parse_new_with_upper("cups", 1.0, 2.0)],
                 modifier: None,
+                ..Default::default()
             })
         );
         assert_eq!(
@@ -539,10 +1410,65 @@ mod tests {
             vec![Measure::parse_new_with_upper("days", 2.0, 4.0)]
         );
 
+        // a mixed number's fraction doesn't end the range
+        for input in [
+            "1 ½-2 cups",
+            "1 ½ – 2 cups",
+            "1 1/2 to 2 cups",
+            "1 1/2-2 cups",
+            "1 and ½ to 2 cups",
+        ] {
+            assert_eq!(
+                (IngredientParser::new(false)).must_parse_amount(input),
+                vec![Measure::parse_new_with_upper("cups", 1.5, 2.0)],
+                "{input}"
+            );
+        }
+        assert_eq!(
+            from_str("1 and 1/2 - 2 cups flour").to_string(),
+            "1.5 - 2 cups flour"
+        );
+
         // #30
         assert_eq!(
             (IngredientParser::new(false)).must_parse_amount("up to 4 days"),
-            vec![Measure::parse_new_with_upper("days", 0.0, 4.0)]
+            vec![Measure::parse_new_with_upper("days", 0.0, 4.0).with_bound(Bound::AtMost)]
+        );
+    }
+    #[test]
+    fn test_bounds() {
+        for (input, bound, display) in [
+            (
+                "at least 2 cups water",
+                Bound::AtLeast,
+                "at least 2 cups water",
+            ),
+            (
+                "no less than 2 cups water",
+                Bound::AtLeast,
+                "at least 2 cups water",
+            ),
+            (
+                "up to 3 tablespoons sugar",
+                Bound::AtMost,
+                "up to 3 tbsp sugar",
+            ),
+            ("no more than 1 tsp salt", Bound::AtMost, "up to 1 tsp salt"),
+        ] {
+            let i = from_str(input);
+            assert_eq!(i.amounts[0].bound(), Some(bound), "{input}");
+            assert_eq!(i.to_string(), display, "{input}");
+            assert_eq!(from_str(display), i, "{input}");
+        }
+        // a minimum isn't the same as a plain amount, nor a maximum a range from nothing
+        assert_ne!(from_str("at least 2 cups water"), from_str("2 cups water"));
+        assert_ne!(
+            Measure::parse_str("up to 1 tsp"),
+            Measure::parse_new_with_upper("tsp", 0.0, 1.0)
+        );
+        assert_eq!(
+            (Measure::parse_str("up to 3 tbsp") * 2.0).to_string(),
+            "up to 6 tbsp"
         );
     }
     #[test]
@@ -553,6 +1479,7 @@ mod tests {
                 name: "flour".to_string(),
                 amounts: vec![Measure::parse_new("cups", 12.0)],
                 modifier: None,
+                ..Default::default()
             })
         );
     }
@@ -567,6 +1494,225 @@ mod tests {
         assert_eq!(from_str("a tsp flour").to_string(), "1 tsp flour");
     }
     #[test]
+    fn test_fast_matches_verbose() {
+        for input in [
+            "1 cup (125.5 grams) AP flour, sifted",
+            "1 tablespoon plus 1 teaspoon olive oil",
+            "2 x 200g flour",
+            "up to 4 days",
+            "egg",
+        ] {
+            let ip = IngredientParser::new(false);
+            assert_eq!(
+                ip.clone().parse_ingredient_fast(input).unwrap(),
+                ip.parse_ingredient(input).unwrap()
+            );
+        }
+    }
+    #[test]
+    fn test_plus_different_pieces() {
+        // can't be added, so only the first is the amount
+        for (input, first) in [
+            ("1 cup plus 2 cloves garlic", Measure::parse_new("cup", 1.0)),
+            (
+                "1 clove plus 1 stick butter",
+                Measure::parse_new("clove", 1.0),
+            ),
+        ] {
+            let (_, i) = IngredientParser::new(false)
+                .parse_ingredient(input)
+                .unwrap();
+            assert_eq!(i.amounts, vec![first], "{input}");
+        }
+    }
+    #[test]
+    fn test_lenient() {
+        for (input, name, modifier) in [
+            ("1 cup plus 2 cloves garlic", "garlic", "plus 2 cloves"),
+            (
+                "1 clove plus 1 stick butter, softened",
+                "butter",
+                "plus 1 stick, softened",
+            ),
+        ] {
+            let p = parse_lenient(input);
+            assert_eq!(p.ingredient.name, name, "{input}");
+            assert_eq!(p.ingredient.modifier.as_deref(), Some(modifier), "{input}");
+            assert_eq!(p.ingredient.amounts.len(), 1, "{input}");
+            assert!(p.confidence < 1.0, "{}", input);
+        }
+    }
+    #[test]
+    fn test_numbered_names() {
+        for (input, name) in [
+            ("1 tsp 5-spice powder", "5-spice powder"),
+            ("5-spice powder", "5-spice powder"),
+            ("1 can 7-Up", "7-Up"),
+            ("1 cup half-and-half", "half-and-half"),
+        ] {
+            let p = IngredientParser::new(false).parse_lenient(input);
+            assert_eq!(p.ingredient.name, name, "{input}");
+            assert!(
+                !p.warnings.iter().any(|w| w.contains("numbers")),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            from_str("1 tsp 5-spice powder").amounts,
+            vec![Measure::parse_new("tsp", 1.0)]
+        );
+        assert_eq!(from_str("7-Up").amounts, vec![]);
+    }
+    #[test]
+    fn test_notes() {
+        let i = from_str("2 eggs (large), beaten");
+        assert_eq!(i.note, Some("large".to_string()));
+        assert_eq!(i.modifier, Some("beaten".to_string()));
+        assert_eq!(i.to_string(), "2 whole eggs (large), beaten");
+        assert_eq!(
+            from_str("2 tbsp lemon juice (from about 2 lemons)").note,
+            Some("from about 2 lemons".to_string())
+        );
+        let i = from_str("1 cup peas, thawed (see note), chopped");
+        assert_eq!(i.note, Some("see note".to_string()));
+        assert_eq!(i.modifier, Some("thawed, chopped".to_string()));
+        // only amounts, percentages and temperatures aren't notes
+        assert_eq!(from_str("1 cup flour (125 g)").note, None);
+        assert_eq!(from_str("350 g water (62%)").note, None);
+        assert_eq!(from_str("1 cup butter, melted (110°F)").note, None);
+    }
+    #[test]
+    fn test_footnotes() {
+        for (input, footnote, modifier) in [
+            ("1 cup cashews*", "*", None),
+            ("1 cup cashews **", "**", None),
+            ("2 tbsp butter†, melted", "†", Some("melted")),
+            ("1 cup flour, sifted*", "*", Some("sifted")),
+        ] {
+            let i = from_str(input);
+            assert_eq!(i.footnote.as_deref(), Some(footnote), "{input}");
+            assert_eq!(i.modifier.as_deref(), modifier, "{input}");
+            assert!(!i.name.contains(FOOTNOTE_MARKERS), "{}", input);
+        }
+        assert_eq!(
+            from_str("2 tbsp butter†, melted").to_string(),
+            "2 tbsp butter†, melted"
+        );
+        assert_eq!(from_str("1 cup cashews").footnote, None);
+    }
+    #[test]
+    fn test_unicode() {
+        let en = IngredientParser::new(false);
+        for (input, name) in [
+            ("1 cup crème fraîche", "crème fraîche"),
+            ("2 jalapeños, minced", "jalapeños"),
+            ("100 g мука", "мука"),
+            ("2 cups 鸡蛋面", "鸡蛋面"),
+        ] {
+            assert_eq!(en.clone().from_str(input).name, name, "{input}");
+        }
+        // a unit is the whole word, not the ascii letters it starts with
+        assert_eq!(en.from_str("1 Löffel Zucker").name, "Löffel Zucker");
+
+        let de = IngredientParser::new(false).with_locale(Locale::De);
+        assert_eq!(
+            de.clone().from_str("2 Esslöffel Weißmehl"),
+            Ingredient {
+                name: "Weißmehl".to_string(),
+                amounts: vec![Measure::parse_new("tbsp", 2.0)],
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            de.from_str("3 Stück Äpfel").amounts,
+            vec![Measure::parse_new("stück", 3.0)]
+        );
+        let fr = IngredientParser::new(false).with_locale(Locale::Fr);
+        assert_eq!(
+            fr.clone().from_str("1 càs huile d'olive").amounts,
+            vec![Measure::parse_new("tbsp", 1.0)]
+        );
+        assert_eq!(fr.from_str("1 boîte tomates").name, "tomates");
+    }
+    #[test]
+    fn test_repeated_amounts() {
+        for (input, amounts) in [
+            ("1 cup (1 cup; 240 ml) milk", "1 cup / 240 ml milk"),
+            ("1 cup (16 tbsp) butter", "1 cup butter"),
+            ("2 tbsp oil (2 tbsp)", "2 tbsp oil"),
+            ("1-2 cups (1 cup) flour", "1 - 2 cups / 1 cup flour"),
+        ] {
+            assert_eq!(from_str(input).to_string(), amounts, "{input}");
+        }
+        assert_eq!(
+            (IngredientParser::new(false)).must_parse_amount("1 cup (1 cup)"),
+            vec![Measure::parse_new("cup", 1.0)]
+        );
+    }
+    #[test]
+    fn test_multiplier() {
+        let each = Some(Multiplier {
+            count: 2.0,
+            each: Measure::parse_new("g", 400.0),
+        });
+        for input in [
+            "2 x 400 g cans chopped tomatoes",
+            "2×400g cans chopped tomatoes",
+            "2x 400 g cans chopped tomatoes",
+        ] {
+            let i = from_str(input);
+            assert_eq!(i.multiplier, each, "{input}");
+            assert_eq!(
+                i.amounts,
+                vec![
+                    Measure::parse_new("g", 800.0),
+                    Measure::parse_new("can", 2.0)
+                ],
+                "{input}"
+            );
+            assert_eq!(i.name, "tomatoes", "{input}");
+        }
+        assert_eq!(from_str("2 xl eggs").name, "xl eggs");
+        assert_eq!(from_str("2 xl eggs").multiplier, None);
+    }
+    #[test]
+    fn test_sized_count() {
+        let each = Some(Multiplier {
+            count: 2.0,
+            each: Measure::parse_new("oz", 15.0),
+        });
+        for (input, modifier) in [
+            ("2 (15-ounce) cans black beans, drained", Some("drained")),
+            ("2 (15 oz) cans black beans", None),
+            ("2 cans (15 oz each) black beans, drained", Some("drained")),
+        ] {
+            let i = from_str(input);
+            assert_eq!(i.name, "black beans", "{}", input);
+            assert_eq!(i.modifier.as_deref(), modifier, "{}", input);
+            assert_eq!(i.multiplier, each, "{}", input);
+            assert_eq!(
+                i.amounts,
+                vec![
+                    Measure::parse_new("oz", 30.0),
+                    Measure::parse_new("can", 2.0)
+                ],
+                "{}",
+                input
+            );
+        }
+        let i = from_str("2 cans (14 oz each) tomatoes");
+        assert_eq!(i.name, "tomatoes");
+        assert_eq!(i.amounts[0], Measure::parse_new("oz", 28.0));
+        let i = from_str("1 (28-ounce) can whole tomatoes");
+        assert_eq!(i.name, "whole tomatoes");
+        assert_eq!(i.amounts[1], Measure::parse_new("can", 1.0));
+        // the size of the whole lot, not of each can
+        let i = from_str("2 cans (28 oz) tomatoes");
+        assert_eq!(i.multiplier, None);
+        assert_eq!(i.name, "tomatoes");
+    }
+    #[test]
     fn test_with_parens() {
         assert_eq!(
             from_str("1 cup (125.5 grams) AP flour, sifted").to_string(),
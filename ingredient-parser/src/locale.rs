@@ -0,0 +1,242 @@
+use std::str::FromStr;
+
+use crate::unit::{singular, Unit};
+
+/// Language of the recipe text, which determines the extra vocabulary
+/// [crate::IngredientParser::with_locale] recognizes on top of the english defaults.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+impl Locale {
+    /// non-standard units, same as [crate::IngredientParser::with_units]
+    pub fn units(&self) -> &'static [&'static str] {
+        match self {
+            Locale::En => &[],
+            Locale::De => &[
+                "prise", "bund", "dose", "zehe", "becher", "packung", "scheibe", "stück", "msp",
+            ],
+            Locale::Fr => &["pincée", "botte", "boîte", "gousse", "tranche", "sachet"],
+            Locale::Es => &["pizca", "manojo", "lata", "diente", "rebanada", "sobre"],
+        }
+    }
+    /// names of standard units, mapped to the english name [crate::unit::Unit] understands
+    pub fn unit_aliases(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => &[],
+            Locale::De => &[
+                ("el", "tbsp"),
+                ("tl", "tsp"),
+                ("esslöffel", "tbsp"),
+                ("teelöffel", "tsp"),
+                ("gramm", "g"),
+                ("kilogramm", "kg"),
+                ("liter", "l"),
+                ("milliliter", "ml"),
+                ("tasse", "cup"),
+            ],
+            Locale::Fr => &[
+                ("càs", "tbsp"),
+                ("càc", "tsp"),
+                ("gramme", "g"),
+                ("litre", "l"),
+                ("tasse", "cup"),
+            ],
+            Locale::Es => &[
+                ("cucharada", "tbsp"),
+                ("cucharadita", "tsp"),
+                ("cda", "tbsp"),
+                ("cdta", "tsp"),
+                ("gramo", "g"),
+                ("kilo", "kg"),
+                ("litro", "l"),
+                ("taza", "cup"),
+            ],
+        }
+    }
+    /// same as [crate::IngredientParser::with_number_words]
+    pub fn number_words(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            Locale::En => &[],
+            Locale::De => &[("ein", 1.0), ("eine", 1.0), ("einen", 1.0), ("zwei", 2.0)],
+            Locale::Fr => &[("un", 1.0), ("une", 1.0), ("deux", 2.0)],
+            Locale::Es => &[("un", 1.0), ("una", 1.0), ("dos", 2.0)],
+        }
+    }
+    /// How a unit is written, `None` for the english names of [Unit::to_str].
+    /// The short forms are the ones [Locale::unit_aliases] reads back.
+    /// Pieces like `clove` are translated too, and `whole` is left out.
+    pub fn unit_name(&self, unit: &Unit, long: bool, plural: bool) -> Option<String> {
+        // short, long, long plural
+        let (short, singular, plural_form) = match (self, unit) {
+            (Locale::En, _) => return None,
+            (Locale::De, Unit::Gram) => ("g", "Gramm", "Gramm"),
+            (Locale::De, Unit::Kilogram) => ("kg", "Kilogramm", "Kilogramm"),
+            (Locale::De, Unit::Liter) => ("l", "Liter", "Liter"),
+            (Locale::De, Unit::Milliliter) => ("ml", "Milliliter", "Milliliter"),
+            (Locale::De, Unit::Teaspoon) => ("TL", "Teelöffel", "Teelöffel"),
+            (Locale::De, Unit::Tablespoon) => ("EL", "Esslöffel", "Esslöffel"),
+            (Locale::De, Unit::Cup) => ("Tasse", "Tasse", "Tassen"),
+            (Locale::De, Unit::Ounce) => ("oz", "Unze", "Unzen"),
+            (Locale::De, Unit::Pound) => ("lb", "Pfund", "Pfund"),
+            (Locale::Fr, Unit::Gram) => ("g", "gramme", "grammes"),
+            (Locale::Fr, Unit::Kilogram) => ("kg", "kilogramme", "kilogrammes"),
+            (Locale::Fr, Unit::Liter) => ("l", "litre", "litres"),
+            (Locale::Fr, Unit::Milliliter) => ("ml", "millilitre", "millilitres"),
+            (Locale::Fr, Unit::Teaspoon) => ("càc", "cuillère à café", "cuillères à café"),
+            (Locale::Fr, Unit::Tablespoon) => ("càs", "cuillère à soupe", "cuillères à soupe"),
+            (Locale::Fr, Unit::Cup) => ("tasse", "tasse", "tasses"),
+            (Locale::Fr, Unit::Ounce) => ("oz", "once", "onces"),
+            (Locale::Fr, Unit::Pound) => ("lb", "livre", "livres"),
+            (Locale::Es, Unit::Gram) => ("g", "gramo", "gramos"),
+            (Locale::Es, Unit::Kilogram) => ("kg", "kilogramo", "kilogramos"),
+            (Locale::Es, Unit::Liter) => ("l", "litro", "litros"),
+            (Locale::Es, Unit::Milliliter) => ("ml", "mililitro", "mililitros"),
+            (Locale::Es, Unit::Teaspoon) => ("cdta", "cucharadita", "cucharaditas"),
+            (Locale::Es, Unit::Tablespoon) => ("cda", "cucharada", "cucharadas"),
+            (Locale::Es, Unit::Cup) => ("taza", "taza", "tazas"),
+            (Locale::Es, Unit::Ounce) => ("oz", "onza", "onzas"),
+            (Locale::Es, Unit::Pound) => ("lb", "libra", "libras"),
+            (_, Unit::Other(piece)) => return self.piece_name(piece, plural),
+            _ => return None,
+        };
+        Some(
+            match (long, plural) {
+                (false, _) if *unit != Unit::Cup => short,
+                (_, true) => plural_form,
+                (_, false) => singular,
+            }
+            .to_string(),
+        )
+    }
+    // the [crate::unit::COUNT_UNITS], with the ones that only say it's a count left out
+    fn piece_name(&self, piece: &str, plural: bool) -> Option<String> {
+        let piece = singular(piece);
+        if matches!(piece.as_str(), "whole" | "each" | "count") {
+            return Some(String::new());
+        }
+        #[rustfmt::skip]
+        let (one, many) = match (self, piece.as_str()) {
+            (Locale::De, "clove") => ("Zehe", "Zehen"),
+            (Locale::De, "can" | "tin") => ("Dose", "Dosen"),
+            (Locale::De, "stick" | "stalk") => ("Stange", "Stangen"),
+            (Locale::De, "slice") => ("Scheibe", "Scheiben"),
+            (Locale::De, "sprig") => ("Zweig", "Zweige"),
+            (Locale::De, "piece") => ("Stück", "Stück"),
+            (Locale::De, "head") => ("Kopf", "Köpfe"),
+            (Locale::De, "bunch") => ("Bund", "Bund"),
+            (Locale::De, "packet" | "package") => ("Packung", "Packungen"),
+            (Locale::De, "strand") => ("Faden", "Fäden"),
+            (Locale::De, "fillet") => ("Filet", "Filets"),
+            (Locale::Fr, "clove") => ("gousse", "gousses"),
+            (Locale::Fr, "can" | "tin") => ("boîte", "boîtes"),
+            (Locale::Fr, "stick") => ("bâton", "bâtons"),
+            (Locale::Fr, "stalk") => ("branche", "branches"),
+            (Locale::Fr, "slice") => ("tranche", "tranches"),
+            (Locale::Fr, "sprig" | "strand") => ("brin", "brins"),
+            (Locale::Fr, "piece") => ("morceau", "morceaux"),
+            (Locale::Fr, "head") => ("tête", "têtes"),
+            (Locale::Fr, "bunch") => ("botte", "bottes"),
+            (Locale::Fr, "packet" | "package") => ("sachet", "sachets"),
+            (Locale::Fr, "fillet") => ("filet", "filets"),
+            (Locale::Es, "clove") => ("diente", "dientes"),
+            (Locale::Es, "can" | "tin") => ("lata", "latas"),
+            (Locale::Es, "stick") => ("barra", "barras"),
+            (Locale::Es, "stalk") => ("tallo", "tallos"),
+            (Locale::Es, "slice") => ("rebanada", "rebanadas"),
+            (Locale::Es, "sprig") => ("ramita", "ramitas"),
+            (Locale::Es, "piece") => ("trozo", "trozos"),
+            (Locale::Es, "head") => ("cabeza", "cabezas"),
+            (Locale::Es, "bunch") => ("manojo", "manojos"),
+            (Locale::Es, "packet" | "package") => ("sobre", "sobres"),
+            (Locale::Es, "strand") => ("hebra", "hebras"),
+            (Locale::Es, "fillet") => ("filete", "filetes"),
+            // the locale's own, like `Prise`
+            _ => return None,
+        };
+        Some(
+            match plural {
+                true => many,
+                false => one,
+            }
+            .to_string(),
+        )
+    }
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::De | Locale::Fr | Locale::Es => ',',
+        }
+    }
+    /// What goes between a unit and the name, e.g. the `de` in `200 g de farine`.
+    pub fn unit_connective(&self, name: &str) -> &'static str {
+        match self {
+            Locale::Fr if name.starts_with(|c: char| "aeiouhâéèêîôœAEIOUHÉ".contains(c)) => {
+                "d'"
+            }
+            Locale::Fr | Locale::Es => "de ",
+            Locale::En | Locale::De => "",
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// accepts language codes with or without a region, e.g. `de` or `de-AT`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lang = s
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Ok(match lang.as_str() {
+            "en" => Locale::En,
+            "de" => Locale::De,
+            "fr" => Locale::Fr,
+            "es" => Locale::Es,
+            _ => return Err(format!("unsupported locale: {s}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{unit::Measure, Ingredient, IngredientParser};
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!(Locale::from_str("de-AT"), Ok(Locale::De));
+        assert_eq!(Locale::from_str("ES"), Ok(Locale::Es));
+        assert!(Locale::from_str("xx").is_err());
+    }
+    #[test]
+    fn test_parse_german() {
+        let ip = IngredientParser::new(false).with_locale(Locale::De);
+        assert_eq!(
+            ip.clone().from_str("2 EL Zucker"),
+            Ingredient {
+                name: "Zucker".to_string(),
+                amounts: vec![Measure::parse_new("tbsp", 2.0)],
+                modifier: None,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            ip.from_str("eine Prise Salz"),
+            Ingredient {
+                name: "Salz".to_string(),
+                amounts: vec![Measure::parse_new("prise", 1.0)],
+                modifier: None,
+                ..Default::default()
+            }
+        );
+    }
+}
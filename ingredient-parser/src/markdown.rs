@@ -0,0 +1,109 @@
+//! Ingredient lists as [RecipeMD](https://recipemd.org) markdown.
+use crate::{display::DisplayOptions, unit::Measure, Ingredient};
+
+/// Renders a RecipeMD ingredient list, one `- *amount* name` item per ingredient.
+/// RecipeMD only has room for one amount, so any others follow the name in parentheses.
+/// ```
+/// use ingredient::{display::DisplayOptions, from_str, markdown::ingredient_list};
+/// let list = ingredient_list(
+///     &[from_str("2 tbsp / 30 g butter, melted"), from_str("salt")],
+///     &DisplayOptions::default(),
+/// );
+/// assert_eq!(list, "- *2 tbsp* butter, melted (30 g)\n- salt\n");
+/// ```
+pub fn ingredient_list(ingredients: &[Ingredient], options: &DisplayOptions) -> String {
+    ingredients
+        .iter()
+        .map(|i| format!("{}\n", list_item(i, options)))
+        .collect()
+}
+
+/// Renders named ingredient groups, each under its own heading.
+/// An empty group name leaves the group's ingredients without a heading.
+/// ```
+/// use ingredient::{display::DisplayOptions, from_str, markdown::grouped_ingredient_list};
+/// let md = grouped_ingredient_list(
+///     &[
+///         ("Dough".to_string(), vec![from_str("500 g flour")]),
+///         ("Filling".to_string(), vec![from_str("2 apples")]),
+///     ],
+///     &DisplayOptions::default(),
+/// );
+/// assert_eq!(md, "## Dough\n\n- *500 g* flour\n\n## Filling\n\n- *2* apples\n");
+/// ```
+pub fn grouped_ingredient_list(
+    groups: &[(String, Vec<Ingredient>)],
+    options: &DisplayOptions,
+) -> String {
+    groups
+        .iter()
+        .map(|(name, ingredients)| {
+            let list = ingredient_list(ingredients, options);
+            match name.trim() {
+                "" => list,
+                name => format!("## {}\n\n{}", escape(name), list),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list_item(i: &Ingredient, options: &DisplayOptions) -> String {
+    let description = escape(&options.describe(i));
+    let mut amounts = i.amounts.iter().map(|a| match a.inferred_unit() {
+        // `2 eggs` rather than `2 whole eggs`
+        true => {
+            let (value, upper, _) = a.values();
+            options.format_measure(&Measure::from_parts("", value, upper))
+        }
+        false => options.format_measure(a),
+    });
+    let first = match amounts.next() {
+        Some(a) => a,
+        None => return format!("- {description}"),
+    };
+    let others: Vec<String> = amounts.collect();
+    match others.is_empty() || !options.all_amounts {
+        true => format!("- *{first}* {description}"),
+        false => format!("- *{first}* {description} ({})", others.join(", ")),
+    }
+}
+
+// so names like `*optional*` don't turn into emphasis
+fn escape(s: &str) -> String {
+    s.replace('*', "\\*").replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_str;
+
+    #[test]
+    fn test_ingredient_list() {
+        let o = DisplayOptions::new().range_separator("-");
+        assert_eq!(
+            ingredient_list(
+                &[
+                    from_str("1-2 cups flour, sifted"),
+                    from_str("1 tsp salt, divided"),
+                    from_str("butter or margarine"),
+                ],
+                &o
+            ),
+            "- *1-2 cups* flour, sifted\n- *1 tsp* salt, divided\n- butter or margarine\n"
+        );
+        assert_eq!(ingredient_list(&[], &o), "");
+    }
+    #[test]
+    fn test_ungrouped() {
+        let md = grouped_ingredient_list(
+            &[
+                ("".to_string(), vec![from_str("1 egg")]),
+                ("For the_glaze".to_string(), vec![from_str("1 cup sugar")]),
+            ],
+            &DisplayOptions::default(),
+        );
+        assert_eq!(md, "- *1* egg\n\n## For the\\_glaze\n\n- *1 cup* sugar\n");
+    }
+}
@@ -0,0 +1,95 @@
+//! Fuzzy matching of ingredient names, for grouping the same ingredient written different ways.
+use std::collections::HashSet;
+
+use crate::Ingredient;
+
+/// common abbreviations, expanded before comparing
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("ap", "all purpose"),
+    ("evoo", "extra virgin olive oil"),
+    ("xvoo", "extra virgin olive oil"),
+    ("choc", "chocolate"),
+    ("pwd", "powder"),
+    ("pkg", "package"),
+];
+
+/// How alike two ingredients' names are, from `0.0` (nothing in common) to `1.0` (same ingredient).
+/// ```
+/// use ingredient::{from_str, matching::similarity};
+/// let ap = from_str("1 cup AP flour");
+/// assert_eq!(similarity(&ap, &from_str("all-purpose flour")), 1.0);
+/// assert_eq!(similarity(&ap, &from_str("2 cups all purpose flour, sifted")), 1.0);
+/// assert!(similarity(&ap, &from_str("sugar")) < 0.5);
+/// ```
+pub fn similarity(a: &Ingredient, b: &Ingredient) -> f32 {
+    name_similarity(&a.normalized_name(), &b.normalized_name())
+}
+
+/// Same as [similarity], for bare names.
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let a = canonical_tokens(a);
+    let b = canonical_tokens(b);
+    if a == b {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let a_set: HashSet<&String> = a.iter().collect();
+    let b_set: HashSet<&String> = b.iter().collect();
+    let overlap = a_set.intersection(&b_set).count() as f32 / a_set.union(&b_set).count() as f32;
+
+    let (a, b) = (a.join(" "), b.join(" "));
+    let max_len = a.chars().count().max(b.chars().count());
+    let edit = 1.0 - levenshtein(&a, &b) as f32 / max_len as f32;
+
+    (overlap + edit) / 2.0
+}
+
+fn canonical_tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+        .map(|t| t.trim_matches('.'))
+        .filter(|t| !t.is_empty())
+        .flat_map(|t| {
+            let expanded = ABBREVIATIONS
+                .iter()
+                .find(|(abbr, _)| *abbr == t)
+                .map_or(t, |(_, full)| *full);
+            expanded.split(' ').map(str::to_string).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("flour", "flour"), 0);
+    }
+    #[test]
+    fn test_name_similarity() {
+        assert_eq!(name_similarity("EVOO", "extra-virgin olive oil"), 1.0);
+        assert!(name_similarity("brown sugar", "light brown sugar") > 0.6);
+        assert!(name_similarity("brown sugar", "light brown sugar") < 1.0);
+        assert_eq!(name_similarity("", "salt"), 0.0);
+    }
+}
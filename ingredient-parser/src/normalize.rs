@@ -0,0 +1,225 @@
+//! Canonical ingredient names, for matching the same ingredient across recipes.
+use crate::unit::{singular, Measure, Unit};
+use crate::Ingredient;
+
+/// values are kept to this many parts of a unit, so that they can be compared exactly
+const VALUE_SCALE: f64 = 1000.0;
+
+/// brand names that get stripped from the front of a name
+pub const BRANDS: &[&str] = &[
+    "diamond crystal",
+    "morton",
+    "maldon",
+    "king arthur",
+    "gold medal",
+    "bob's red mill",
+    "domino",
+    "kerrygold",
+    "land o'lakes",
+    "philadelphia",
+    "hellmann's",
+    "heinz",
+    "kikkoman",
+    "goya",
+    "ghirardelli",
+    "nestlé toll house",
+    "better than bouillon",
+];
+
+/// words describing size or condition that don't change what the ingredient is
+pub const DESCRIPTORS: &[&str] = &[
+    "extra-large",
+    "extra large",
+    "large",
+    "medium",
+    "small",
+    "jumbo",
+    "big",
+    "fresh",
+    "ripe",
+    "yellow",
+    "organic",
+];
+
+/// Lowercases `name`, strips leading brands and descriptors from it and makes its last
+/// word singular (see [singular_name]), returning that along with what was stripped.
+/// ```
+/// use ingredient::normalize::normalize_name;
+/// assert_eq!(
+///     normalize_name("Diamond Crystal kosher salt"),
+///     ("kosher salt".to_string(), vec!["diamond crystal".to_string()])
+/// );
+/// assert_eq!(
+///     normalize_name("large yellow onion"),
+///     ("onion".to_string(), vec!["large".to_string(), "yellow".to_string()])
+/// );
+/// assert_eq!(normalize_name("Ripe Tomatoes").0, normalize_name("tomato").0);
+/// ```
+pub fn normalize_name(name: &str) -> (String, Vec<String>) {
+    let mut rest = name.trim().to_lowercase();
+    let mut stripped = vec![];
+    while let Some(prefix) = BRANDS
+        .iter()
+        .chain(DESCRIPTORS.iter())
+        .find(|p| strip_word(&rest, p).is_some())
+    {
+        rest = strip_word(&rest, prefix).unwrap_or_default().to_string();
+        stripped.push(prefix.to_string());
+    }
+    (singular_name(&rest), stripped)
+}
+
+/// Makes the last word of `name` singular, the same as [singular] does for units
+/// but also `tomatoes` to `tomato` and `cherries` to `cherry`. Words that only look
+/// plural, like `molasses` or `hummus`, are left alone.
+/// ```
+/// use ingredient::normalize::singular_name;
+/// assert_eq!(singular_name("cherry tomatoes"), "cherry tomato");
+/// assert_eq!(singular_name("peaches"), "peach");
+/// assert_eq!(singular_name("couscous"), "couscous");
+/// ```
+pub fn singular_name(name: &str) -> String {
+    let (rest, last) = match name.rsplit_once(' ') {
+        Some((rest, last)) => (format!("{rest} "), last),
+        None => (String::new(), name),
+    };
+    let last = match last {
+        "molasses" => last.to_string(),
+        l if l.ends_with("ss") || l.ends_with("us") => l.to_string(),
+        l if l.ends_with("oes") => l[..l.len() - 2].to_string(),
+        // `berries` and `anchovies`, but `cookies`
+        l if l.ends_with("ries") || l.ends_with("vies") => format!("{}y", &l[..l.len() - 3]),
+        l => singular(l),
+    };
+    rest + &last
+}
+
+/// The name with its last word made plural, the reverse of [singular_name].
+/// ```
+/// use ingredient::normalize::plural_name;
+/// assert_eq!(plural_name("lemon"), "lemons");
+/// assert_eq!(plural_name("peach"), "peaches");
+/// assert_eq!(plural_name("sour cherry"), "sour cherries");
+/// ```
+pub fn plural_name(name: &str) -> String {
+    if let Some(stem) = name
+        .strip_suffix('y')
+        .filter(|stem| !stem.ends_with(['a', 'e', 'i', 'o', 'u']))
+    {
+        return format!("{stem}ies");
+    }
+    match ["ch", "sh", "s", "x"].iter().any(|e| name.ends_with(e)) {
+        true => format!("{name}es"),
+        false => format!("{name}s"),
+    }
+}
+
+/// An [Ingredient] reduced to what identifies it, which (unlike [Ingredient])
+/// implements [Eq] and [Hash], for use as a `HashMap` key.
+/// See [Ingredient::normalized].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct NormalizedIngredient {
+    /// see [Ingredient::normalized_name]
+    pub name: String,
+    /// in base units, sorted
+    pub amounts: Vec<NormalizedMeasure>,
+}
+
+/// A [Measure] in its base unit (see [Measure::normalize]), with values in thousandths.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct NormalizedMeasure {
+    pub unit: Unit,
+    pub value: i64,
+    pub upper_value: Option<i64>,
+}
+
+impl From<&Measure> for NormalizedMeasure {
+    fn from(m: &Measure) -> Self {
+        let m = m.normalize();
+        let (value, upper, _) = m.values();
+        let scale = |v: f64| (v * VALUE_SCALE).round() as i64;
+        NormalizedMeasure {
+            unit: m.unit(),
+            value: scale(value),
+            upper_value: upper.map(scale),
+        }
+    }
+}
+
+impl From<&Ingredient> for NormalizedIngredient {
+    fn from(i: &Ingredient) -> Self {
+        let mut amounts: Vec<NormalizedMeasure> = i.amounts.iter().map(Into::into).collect();
+        amounts.sort();
+        NormalizedIngredient {
+            name: i.normalized_name(),
+            amounts,
+        }
+    }
+}
+
+// strips `word` from the start of `s`, only if it's followed by more words
+pub(crate) fn strip_word<'a>(s: &'a str, word: &str) -> Option<&'a str> {
+    s.strip_prefix(word)?
+        .strip_prefix(' ')
+        .map(str::trim_start)
+        .filter(|r| !r.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(
+            normalize_name("Morton Kosher Salt"),
+            ("kosher salt".to_string(), vec!["morton".to_string()])
+        );
+        // needs to leave something behind
+        assert_eq!(normalize_name("large"), ("large".to_string(), vec![]));
+        // only whole words
+        assert_eq!(normalize_name("smallage"), ("smallage".to_string(), vec![]));
+    }
+    #[test]
+    fn test_singular_name() {
+        for (plural, one) in [
+            ("eggs", "egg"),
+            ("carrots", "carrot"),
+            ("Large Onions", "onion"),
+            ("potatoes", "potato"),
+            ("blueberries", "blueberry"),
+            ("anchovies", "anchovy"),
+            ("cookies", "cookie"),
+            ("radishes", "radish"),
+            ("green beans", "green bean"),
+        ] {
+            assert_eq!(normalize_name(plural).0, normalize_name(one).0);
+        }
+        for word in ["molasses", "hummus", "swiss", "asparagus"] {
+            assert_eq!(singular_name(word), word);
+        }
+    }
+    #[test]
+    fn test_normalized_ingredient() {
+        use crate::from_str;
+        assert_eq!(
+            from_str("1 cup / 120 g flour").normalized(),
+            from_str("120 grams / 1 cup Gold Medal flour").normalized()
+        );
+        assert_eq!(
+            from_str("1 egg").normalized().name,
+            from_str("2 large eggs").normalized().name
+        );
+        let n = from_str("1-2 tbsp butter").normalized();
+        assert_eq!(
+            n.amounts,
+            vec![NormalizedMeasure {
+                unit: Unit::Teaspoon,
+                value: 3000,
+                upper_value: Some(6000),
+            }]
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Calories and macros for parsed ingredients, behind the `nutrition` feature.
+use std::collections::HashMap;
+use std::ops::Add;
+
+use crate::matching::name_similarity;
+use crate::normalize::singular_name;
+use crate::unit::{MeasureKind, Unit};
+use crate::Ingredient;
+
+/// how close a name has to be to a known food, see [name_similarity]
+const MIN_SIMILARITY: f32 = 0.85;
+const TSP_TO_CUP: f64 = 48.0;
+const ML_TO_CUP: f64 = 236.588;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct Nutrition {
+    pub calories: f64,
+    /// grams
+    pub protein: f64,
+    /// grams
+    pub fat: f64,
+    /// grams
+    pub carbohydrates: f64,
+}
+
+impl Nutrition {
+    pub fn scale(self, factor: f64) -> Nutrition {
+        Nutrition {
+            calories: self.calories * factor,
+            protein: self.protein * factor,
+            fat: self.fat * factor,
+            carbohydrates: self.carbohydrates * factor,
+        }
+    }
+}
+
+impl Add for Nutrition {
+    type Output = Nutrition;
+    fn add(self, other: Nutrition) -> Nutrition {
+        Nutrition {
+            calories: self.calories + other.calories,
+            protein: self.protein + other.protein,
+            fat: self.fat + other.fat,
+            carbohydrates: self.carbohydrates + other.carbohydrates,
+        }
+    }
+}
+
+/// Looks up nutrition for ingredients. Implementors only need to provide
+/// [NutritionResolver::food], the conversion to grams is shared.
+pub trait NutritionResolver {
+    /// the food for a [Ingredient::normalized_name]
+    fn food(&self, normalized_name: &str) -> Option<Food>;
+
+    /// Nutrition for the ingredient's amount, if its weight can be worked out.
+    fn resolve(&self, ingredient: &Ingredient) -> Option<Nutrition> {
+        let food = self.food(&ingredient.normalized_name())?;
+        let grams = ingredient.amounts.iter().find_map(|m| food.grams(m))?;
+        Some(food.per_100g.scale(grams / 100.0))
+    }
+
+    /// Sum over a recipe, skipping ingredients that can't be resolved.
+    fn resolve_all(&self, ingredients: &[Ingredient]) -> Nutrition {
+        ingredients
+            .iter()
+            .filter_map(|i| self.resolve(i))
+            .fold(Nutrition::default(), Add::add)
+    }
+}
+
+/// A food and what's needed to weigh it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Food {
+    /// FoodData Central description, e.g. `Wheat flour, white, all-purpose, enriched`
+    pub description: String,
+    pub per_100g: Nutrition,
+    /// weight of one piece, for counted amounts like `2 eggs`
+    pub grams_per_piece: Option<f64>,
+    /// what the pieces are counted in, e.g. `clove` for garlic,
+    /// or `None` when they're the food itself, as with eggs
+    pub piece: Option<String>,
+    /// weight of one cup, for volume amounts
+    pub grams_per_cup: Option<f64>,
+}
+
+impl Food {
+    fn grams(&self, m: &crate::unit::Measure) -> Option<f64> {
+        let normalized = m.normalize();
+        let (value, _, _) = normalized.values();
+        match (m.kind().ok()?, normalized.unit()) {
+            (MeasureKind::Weight, _) => Some(value),
+            (MeasureKind::Volume, Unit::Teaspoon) => Some(value / TSP_TO_CUP * self.grams_per_cup?),
+            (MeasureKind::Volume, _) => Some(value / ML_TO_CUP * self.grams_per_cup?),
+            // `2 large eggs` has `large` as the unit, so anything unconvertible is a piece
+            (MeasureKind::Other, _) => Some(value * self.grams_per_piece?),
+            // but a `can` of tomatoes or a `head` of garlic isn't one tomato or one clove
+            (MeasureKind::Count, _) => {
+                let (_, unit) = m.as_count()?;
+                let generic = matches!(unit.as_str(), "whole" | "each" | "count" | "piece");
+                match &self.piece {
+                    Some(piece) if *piece == unit => Some(value * self.grams_per_piece?),
+                    None if generic => Some(value * self.grams_per_piece?),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// [NutritionResolver] for foods described as in USDA FoodData Central.
+/// It doesn't ship the FDC data itself: [FdcResolver::bundled] is a small table of
+/// pantry staples, with values rounded from SR Legacy, and other foods (say, from an
+/// FDC download) can be added with [FdcResolver::with_food].
+/// ```
+/// use ingredient::{from_str, nutrition::{FdcResolver, NutritionResolver}};
+/// let fdc = FdcResolver::bundled();
+/// let n = fdc.resolve(&from_str("100 g all-purpose flour")).unwrap();
+/// assert_eq!(n.calories, 364.0);
+/// let n = fdc.resolve(&from_str("2 large eggs")).unwrap();
+/// assert_eq!(n.calories.round(), 143.0);
+/// assert_eq!(fdc.resolve(&from_str("1 cup unobtainium")), None);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FdcResolver {
+    foods: HashMap<String, Food>,
+}
+
+// name, description, kcal, protein, fat, carbs (per 100 g), grams per piece, grams per cup
+type BundledFood = (
+    &'static str,
+    &'static str,
+    [f64; 4],
+    Option<f64>,
+    Option<f64>,
+);
+// the foods that aren't counted in themselves
+const BUNDLED_PIECES: &[(&str, &str)] = &[("garlic", "clove")];
+#[rustfmt::skip]
+const BUNDLED: &[BundledFood] = &[
+    ("all purpose flour", "Wheat flour, white, all-purpose, enriched, bleached", [364.0, 10.3, 1.0, 76.3], None, Some(125.0)),
+    ("flour", "Wheat flour, white, all-purpose, enriched, bleached", [364.0, 10.3, 1.0, 76.3], None, Some(125.0)),
+    ("bread flour", "Wheat flour, white, bread, enriched", [361.0, 12.0, 1.7, 72.5], None, Some(137.0)),
+    ("whole wheat flour", "Flour, whole wheat, unenriched", [340.0, 13.2, 2.5, 72.0], None, Some(120.0)),
+    ("sugar", "Sugars, granulated", [387.0, 0.0, 0.0, 100.0], None, Some(200.0)),
+    ("brown sugar", "Sugars, brown", [380.0, 0.1, 0.0, 98.1], None, Some(220.0)),
+    ("honey", "Honey", [304.0, 0.3, 0.0, 82.4], None, Some(339.0)),
+    ("butter", "Butter, salted", [717.0, 0.9, 81.1, 0.1], None, Some(227.0)),
+    ("unsalted butter", "Butter, without salt", [717.0, 0.9, 81.1, 0.1], None, Some(227.0)),
+    ("egg", "Egg, whole, raw, fresh", [143.0, 12.6, 9.5, 0.7], Some(50.0), Some(243.0)),
+    ("milk", "Milk, whole, 3.25% milkfat", [61.0, 3.2, 3.3, 4.8], None, Some(244.0)),
+    ("heavy cream", "Cream, fluid, heavy whipping", [340.0, 2.8, 36.1, 2.8], None, Some(238.0)),
+    ("cheddar cheese", "Cheese, cheddar", [403.0, 24.9, 33.1, 1.3], None, Some(113.0)),
+    ("olive oil", "Oil, olive, salad or cooking", [884.0, 0.0, 100.0, 0.0], None, Some(216.0)),
+    ("vegetable oil", "Oil, vegetable, canola", [884.0, 0.0, 100.0, 0.0], None, Some(218.0)),
+    ("water", "Water, tap, municipal", [0.0, 0.0, 0.0, 0.0], None, Some(237.0)),
+    ("salt", "Salt, table", [0.0, 0.0, 0.0, 0.0], None, Some(292.0)),
+    ("white rice", "Rice, white, long-grain, regular, raw, enriched", [365.0, 7.1, 0.7, 80.0], None, Some(185.0)),
+    ("rolled oats", "Oats", [379.0, 13.2, 6.5, 67.7], None, Some(81.0)),
+    ("cocoa powder", "Cocoa, dry powder, unsweetened", [228.0, 19.6, 13.7, 57.9], None, Some(86.0)),
+    ("active dry yeast", "Leavening agents, yeast, baker's, active dry", [325.0, 40.4, 7.6, 41.2], None, None),
+    ("chicken breast", "Chicken, broilers or fryers, breast, meat only, raw", [120.0, 22.5, 2.6, 0.0], Some(174.0), None),
+    ("onion", "Onions, raw", [40.0, 1.1, 0.1, 9.3], Some(110.0), Some(160.0)),
+    ("garlic", "Garlic, raw", [149.0, 6.4, 0.5, 33.1], Some(3.0), Some(136.0)),
+    ("carrot", "Carrots, raw", [41.0, 0.9, 0.2, 9.6], Some(61.0), Some(128.0)),
+    ("potato", "Potatoes, flesh and skin, raw", [77.0, 2.0, 0.1, 17.5], Some(213.0), Some(150.0)),
+    ("tomato", "Tomatoes, red, ripe, raw", [18.0, 0.9, 0.2, 3.9], Some(123.0), Some(180.0)),
+];
+
+impl FdcResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Preloaded with approximate values for 27 common ingredients, see [FdcResolver].
+    pub fn bundled() -> Self {
+        BUNDLED
+            .iter()
+            .fold(Self::new(), |r, (name, desc, n, piece, cup)| {
+                r.with_food(
+                    name,
+                    Food {
+                        description: desc.to_string(),
+                        per_100g: Nutrition {
+                            calories: n[0],
+                            protein: n[1],
+                            fat: n[2],
+                            carbohydrates: n[3],
+                        },
+                        grams_per_piece: *piece,
+                        piece: BUNDLED_PIECES
+                            .iter()
+                            .find(|(food, _)| food == name)
+                            .map(|(_, piece)| piece.to_string()),
+                        grams_per_cup: *cup,
+                    },
+                )
+            })
+    }
+    /// Adds or replaces a food, keyed by its normalized name.
+    pub fn with_food(mut self, name: &str, food: Food) -> Self {
+        self.foods.insert(name.to_lowercase(), food);
+        self
+    }
+}
+
+impl NutritionResolver for FdcResolver {
+    fn food(&self, normalized_name: &str) -> Option<Food> {
+        let singular = singular_name(normalized_name);
+        if let Some(f) = self
+            .foods
+            .get(normalized_name)
+            .or(self.foods.get(&singular))
+        {
+            return Some(f.clone());
+        }
+        self.foods
+            .iter()
+            .map(|(name, f)| (name_similarity(name, &singular), f))
+            .filter(|(score, _)| *score >= MIN_SIMILARITY)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, f)| f.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_str;
+
+    #[test]
+    fn test_volume_and_count() {
+        let fdc = FdcResolver::bundled();
+        let n = fdc.resolve(&from_str("1 cup sugar")).unwrap();
+        assert_eq!(n.calories.round(), 774.0);
+        let n = fdc.resolve(&from_str("2 cloves garlic")).unwrap();
+        assert_eq!(n.calories.round(), 9.0);
+        // no weight for a packet of yeast
+        assert_eq!(fdc.resolve(&from_str("1 packet active dry yeast")), None);
+        let n = fdc.resolve(&from_str("2 tomatoes")).unwrap();
+        assert_eq!(n.calories.round(), 44.0);
+        // not one tomato or one clove
+        assert_eq!(fdc.resolve(&from_str("1 can tomatoes")), None);
+        assert_eq!(fdc.resolve(&from_str("1 head garlic")), None);
+        assert_eq!(fdc.resolve(&from_str("2 garlic")), None);
+    }
+    #[test]
+    fn test_resolve_all() {
+        let fdc = FdcResolver::bundled();
+        let total = fdc.resolve_all(&[
+            from_str("100 g flour"),
+            from_str("1 tbsp olive oil"),
+            from_str("1 pinch mystery spice"),
+        ]);
+        assert_eq!(total.calories.round(), 483.0);
+    }
+}
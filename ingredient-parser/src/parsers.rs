@@ -0,0 +1,61 @@
+//! Building blocks of [IngredientParser], for embedding in larger [nom] grammars.
+//! ```
+//! use ingredient::{parsers, unit::Measure, IngredientParser};
+//! use nom::{bytes::complete::tag, sequence::preceded};
+//! let ip = IngredientParser::new(false);
+//! assert_eq!(
+//!     preceded(tag("add "), parsers::amount1(&ip))("add 2 cups water"),
+//!     Ok((" water", Measure::parse_new("cups", 2.0)))
+//! );
+//! ```
+use crate::{unit::Measure, IngredientParser, Res};
+
+pub use crate::fraction::fraction_number;
+
+/// parses a number, which can be a decimal, fraction, or number word such as `one`.
+pub fn num(ip: &IngredientParser) -> impl Fn(&str) -> Res<&str, f64> + '_ {
+    move |input| ip.clone().num(input)
+}
+
+/// parses a single amount, e.g. `1 gram` or `1-2 cups`.
+pub fn amount1(ip: &IngredientParser) -> impl Fn(&str) -> Res<&str, Measure> + '_ {
+    move |input| ip.clone().amount1(input)
+}
+
+/// parses one or more amounts, e.g. `12 grams` or `120 grams / 1 cup`.
+pub fn amount2(ip: &IngredientParser) -> impl Fn(&str) -> Res<&str, Vec<Measure>> + '_ {
+    move |input| ip.clone().many_amount(input)
+}
+
+/// parses amounts wrapped in parentheses, e.g. `(1 cup; 120 grams)`.
+pub fn amt_parens(ip: &IngredientParser) -> impl Fn(&str) -> Res<&str, Vec<Measure>> + '_ {
+    move |input| ip.clone().amt_parens(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, sequence::tuple};
+
+    #[test]
+    fn test_parsers() {
+        let ip = IngredientParser::new(false);
+        assert_eq!(num(&ip)("1 ½"), Ok(("", 1.5)));
+        assert_eq!(num(&ip)("one"), Ok(("", 1.0)));
+        assert_eq!(fraction_number("⅓"), Ok(("", 1.0 / 3.0)));
+        assert_eq!(
+            tuple((amount2(&ip), tag(", then "), amt_parens(&ip)))("1 cup / 2 tbsp, then (30 ml)"),
+            Ok((
+                "",
+                (
+                    vec![
+                        Measure::parse_new("cup", 1.0),
+                        Measure::parse_new("tbsp", 2.0)
+                    ],
+                    ", then ",
+                    vec![Measure::parse_new("ml", 30.0)]
+                )
+            ))
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! A whole recipe with its ingredients parsed, as produced by the `recipe-scraper` crate
+//! or built by hand, for the formatters and aggregators of this crate to work on.
+use std::time::Duration;
+
+use crate::{
+    aggregate::aggregate, display::DisplayOptions, markdown::grouped_ingredient_list,
+    recipe_yield::Yield, Ingredient,
+};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct Recipe {
+    pub name: String,
+    /// the ingredients under the headings of their sections, e.g. `For the glaze`,
+    /// with `None` for the ones that aren't in one
+    pub ingredient_groups: Vec<(Option<String>, Vec<Ingredient>)>,
+    pub steps: Vec<String>,
+    #[cfg_attr(feature = "serde-derive", serde(rename = "yield"))]
+    pub yield_: Option<Yield>,
+    pub prep_time: Option<Duration>,
+    pub cook_time: Option<Duration>,
+    pub total_time: Option<Duration>,
+    /// where it came from
+    pub url: Option<String>,
+    pub image: Option<String>,
+}
+
+impl Recipe {
+    /// Every ingredient, in order, whichever group it's in.
+    pub fn ingredients(&self) -> impl Iterator<Item = &Ingredient> {
+        self.ingredient_groups.iter().flat_map(|(_, i)| i.iter())
+    }
+
+    /// Its ingredients combined by [aggregate], so each one is listed once per kind of amount.
+    /// ```
+    /// use ingredient::{from_str, recipe::Recipe, unit::Measure};
+    /// let recipe = Recipe {
+    ///     ingredient_groups: vec![
+    ///         (None, vec![from_str("1 cup flour")]),
+    ///         (Some("Topping".to_string()), vec![from_str("2 tbsp flour")]),
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// let list = recipe.shopping_list();
+    /// assert_eq!(list.len(), 1);
+    /// assert_eq!(list[0].amounts, vec![Measure::parse_new("cup", 1.125)]);
+    /// ```
+    pub fn shopping_list(&self) -> Vec<Ingredient> {
+        aggregate(self.ingredients().cloned().collect())
+    }
+
+    /// Its ingredients as a markdown list, under a heading for each group,
+    /// see [grouped_ingredient_list].
+    pub fn ingredient_list(&self, options: &DisplayOptions) -> String {
+        let groups: Vec<(String, Vec<Ingredient>)> = self
+            .ingredient_groups
+            .iter()
+            .map(|(name, i)| (name.clone().unwrap_or_default(), i.clone()))
+            .collect();
+        grouped_ingredient_list(&groups, options)
+    }
+}
@@ -0,0 +1,141 @@
+//! Yield lines, such as `Makes about 24 cookies` or `Serves 4-6`.
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::tag_no_case,
+    character::complete::{char, not_line_ending, space0, space1},
+    combinator::opt,
+    error::{context, VerboseError},
+    sequence::tuple,
+    IResult,
+};
+
+use crate::{IngredientParser, ParseErr};
+
+/// How much a recipe makes, as read by [IngredientParser::parse_yield], and as kept
+/// by [crate::recipe::Recipe] and the `recipe-scraper` crate for scaling recipes.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct Yield {
+    pub value: f64,
+    pub upper: Option<f64>,
+    /// what's being made, e.g. `cookies`, or `servings` when not given
+    pub unit: String,
+    /// the line it was read from, e.g. `Makes about 24 cookies`
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub raw: String,
+}
+
+impl IngredientParser {
+    /// Parses a yield line, e.g. `Serves 4 to 6` or `Yield: 1 loaf`.
+    /// ```
+    /// use ingredient::{recipe_yield::Yield, IngredientParser};
+    /// let ip = IngredientParser::new(false);
+    /// let y = ip.parse_yield("Makes about 24 cookies").unwrap();
+    /// assert_eq!((y.value, y.upper, y.unit.as_str()), (24.0, None, "cookies"));
+    /// assert_eq!(y.raw, "Makes about 24 cookies");
+    /// assert_eq!(
+    ///     ip.parse_yield(" Serves 4-6").unwrap(),
+    ///     Yield {
+    ///         value: 4.0,
+    ///         upper: Some(6.0),
+    ///         unit: "servings".to_string(),
+    ///         raw: "Serves 4-6".to_string()
+    ///     }
+    /// );
+    /// ```
+    pub fn parse_yield(&self, input: &str) -> Result<Yield> {
+        match self
+            .clone()
+            .yield_line::<VerboseError<&str>>(crate::util::normalize_unicode(input).trim())
+        {
+            // as written, rather than normalized
+            Ok((_, y)) => Ok(Yield {
+                raw: input.trim().to_string(),
+                ..y
+            }),
+            Err(e) => Err(anyhow::anyhow!(
+                "parse_yield on '{}' failed: {:?}",
+                input,
+                e
+            )),
+        }
+    }
+
+    fn yield_line<'a, E: ParseErr<'a>>(&self, input: &'a str) -> IResult<&'a str, Yield, E> {
+        context(
+            "yield_line",
+            tuple((
+                opt(tuple((
+                    alt((
+                        tag_no_case("makes"),
+                        tag_no_case("serves"),
+                        tag_no_case("feeds"),
+                        tag_no_case("yields"),
+                        tag_no_case("yield"),
+                        tag_no_case("servings"),
+                        tag_no_case("serving size"),
+                    )),
+                    opt(char(':')),
+                    space1,
+                ))),
+                opt(tuple((
+                    alt((tag_no_case("about"), tag_no_case("approximately"))),
+                    space1,
+                ))),
+                |a| self.get_value(a),
+                space0,
+                not_line_ending,
+            )),
+        )(input)
+        .map(|(next_input, (_, _, (value, upper), _, unit))| {
+            let unit = match unit.trim() {
+                "" => "servings".to_string(),
+                u => u.to_string(),
+            };
+            let raw = input[..input.len() - next_input.len()].to_string();
+            (
+                next_input,
+                Yield {
+                    value,
+                    upper,
+                    unit,
+                    raw,
+                },
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yield() {
+        let ip = IngredientParser::new(false);
+        let y = |value, upper: Option<f64>, unit: &str, raw: &str| Yield {
+            value,
+            upper,
+            unit: unit.to_string(),
+            raw: raw.to_string(),
+        };
+        assert_eq!(
+            ip.parse_yield("Yield: 1 loaf").unwrap(),
+            y(1.0, None, "loaf", "Yield: 1 loaf")
+        );
+        assert_eq!(
+            ip.parse_yield("Serves 4 to 6").unwrap(),
+            y(4.0, Some(6.0), "servings", "Serves 4 to 6")
+        );
+        assert_eq!(
+            ip.parse_yield("12 muffins\n").unwrap(),
+            y(12.0, None, "muffins", "12 muffins")
+        );
+        assert_eq!(
+            ip.parse_yield("Servings: 8").unwrap(),
+            y(8.0, None, "servings", "Servings: 8")
+        );
+        assert!(ip.parse_yield("varies").is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! Temperatures mentioned alongside an ingredient, e.g. `water, 43°C`.
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, tag_no_case},
+    character::complete::{one_of, space0},
+    combinator::{map, opt},
+    error::{Error, ErrorKind},
+    sequence::tuple,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    fraction::decimal,
+    unit::{Measure, Unit},
+    util::num_without_zeroes,
+};
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct Temperature {
+    pub value: f64,
+    pub scale: TemperatureScale,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub enum TemperatureScale {
+    Fahrenheit,
+    Celsius,
+}
+
+impl Temperature {
+    /// The temperature an amount such as `110°F` stands for, if it is one.
+    pub fn from_measure(m: &Measure) -> Option<Temperature> {
+        let scale = match m.unit() {
+            Unit::Farhenheit => TemperatureScale::Fahrenheit,
+            Unit::Celcius => TemperatureScale::Celsius,
+            _ => return None,
+        };
+        Some(Temperature {
+            value: m.values().0,
+            scale,
+        })
+    }
+    pub fn to_celsius(self) -> Temperature {
+        match self.scale {
+            TemperatureScale::Celsius => self,
+            TemperatureScale::Fahrenheit => Temperature {
+                value: (self.value - 32.0) * 5.0 / 9.0,
+                scale: TemperatureScale::Celsius,
+            },
+        }
+    }
+    pub fn to_fahrenheit(self) -> Temperature {
+        match self.scale {
+            TemperatureScale::Fahrenheit => self,
+            TemperatureScale::Celsius => Temperature {
+                value: self.value * 9.0 / 5.0 + 32.0,
+                scale: TemperatureScale::Fahrenheit,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let scale = match self.scale {
+            TemperatureScale::Fahrenheit => 'F',
+            TemperatureScale::Celsius => 'C',
+        };
+        write!(f, "{}°{}", num_without_zeroes(self.value), scale)
+    }
+}
+
+/// Finds the first temperature in some free text, such as a modifier.
+/// ```
+/// use ingredient::temperature::{find_temperature, Temperature, TemperatureScale};
+/// assert_eq!(
+///     find_temperature("melted and cooled to 110°F"),
+///     Some(Temperature { value: 110.0, scale: TemperatureScale::Fahrenheit })
+/// );
+/// assert_eq!(find_temperature("cut into 2 pieces"), None);
+/// ```
+pub fn find_temperature(s: &str) -> Option<Temperature> {
+    let mut prev: Option<char> = None;
+    for (idx, c) in s.char_indices() {
+        let at_boundary = !matches!(prev, Some(p) if p.is_ascii_digit() || p == '.' || p == '-');
+        prev = Some(c);
+        if !at_boundary || !c.is_ascii_digit() {
+            continue;
+        }
+        if let Ok((_, t)) = temperature(&s[idx..]) {
+            return Some(t);
+        }
+    }
+    None
+}
+
+fn temperature(input: &str) -> IResult<&str, Temperature> {
+    let (rest, (value, _, marker, _, (scale, written_out))) = tuple((
+        decimal,
+        space0,
+        opt(alt((
+            tag("°"),
+            tag("º"),
+            tag_no_case("degrees"),
+            tag_no_case("deg"),
+        ))),
+        space0,
+        alt((
+            map(tag_no_case("fahrenheit"), |_| ('F', true)),
+            map(
+                alt((tag_no_case("celsius"), tag_no_case("celcius"))),
+                |_| ('C', true),
+            ),
+            map(one_of("FCfc"), |c| (c, false)),
+        )),
+    ))(input)?;
+    // a lone `C` or `F` needs a degree sign, as `2 C` is usually cups
+    if rest.starts_with(char::is_alphabetic) || (marker.is_none() && !written_out) {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    }
+    let scale = match scale.to_ascii_uppercase() {
+        'F' => TemperatureScale::Fahrenheit,
+        _ => TemperatureScale::Celsius,
+    };
+    Ok((rest, Temperature { value, scale }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_temperature() {
+        let c = |value| {
+            Some(Temperature {
+                value,
+                scale: TemperatureScale::Celsius,
+            })
+        };
+        let f = |value| Temperature {
+            value,
+            scale: TemperatureScale::Fahrenheit,
+        };
+        assert_eq!(find_temperature("43°C"), c(43.0));
+        assert_eq!(find_temperature("heated to 43 ºc"), c(43.0));
+        assert_eq!(find_temperature("about 37.5 degrees celsius"), c(37.5));
+        assert_eq!(find_temperature("2 cups"), None);
+        assert_eq!(find_temperature("2 f"), None);
+        assert_eq!(find_temperature("2 Cups"), None);
+        assert_eq!(find_temperature("about 2 C"), None);
+        assert_eq!(find_temperature("110F"), None);
+        assert_eq!(find_temperature("110 deg F"), Some(f(110.0)));
+        assert_eq!(find_temperature("110 Fahrenheit"), Some(f(110.0)));
+    }
+    #[test]
+    fn test_convert() {
+        let f = Temperature {
+            value: 212.0,
+            scale: TemperatureScale::Fahrenheit,
+        };
+        assert_eq!(f.to_celsius().value, 100.0);
+        assert_eq!(f.to_celsius().to_fahrenheit(), f);
+    }
+}
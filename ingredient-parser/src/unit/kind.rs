@@ -12,6 +12,8 @@ pub enum MeasureKind {
     Time,
     Temperature,
     Length,
+    /// discrete pieces, e.g. cloves or slices
+    Count,
 }
 impl MeasureKind {
     pub fn unit(&self) -> Unit {
@@ -24,9 +26,17 @@ impl MeasureKind {
             MeasureKind::Time => Unit::Second,
             MeasureKind::Temperature => Unit::Farhenheit,
             MeasureKind::Length => Unit::Inch,
+            MeasureKind::Count => Unit::Other("piece".to_string()),
         }
     }
 }
+/// Whether a unit is metric or US customary, see [crate::Ingredient::prefer].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum MeasurementSystem {
+    Metric,
+    Imperial,
+}
+
 impl FromStr for MeasureKind {
     type Err = ();
 
@@ -39,6 +49,7 @@ impl FromStr for MeasureKind {
             "time" => Self::Time,
             "temperature" => Self::Temperature,
             "length" => Self::Length,
+            "count" => Self::Count,
             _ => Self::Other,
         })
     }
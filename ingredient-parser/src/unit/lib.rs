@@ -2,6 +2,8 @@ use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
 
+use super::MeasurementSystem;
+
 pub fn is_valid(units: HashSet<String>, s: &str) -> bool {
     if !matches!(Unit::from_str(&singular(s)).unwrap(), Unit::Other(_)) {
         // anything other than `other`
@@ -11,10 +13,21 @@ pub fn is_valid(units: HashSet<String>, s: &str) -> bool {
 }
 
 pub fn is_addon_unit(units: HashSet<String>, s: &str) -> bool {
-    units.contains(&s.to_lowercase())
+    units.contains(&s.to_lowercase()) || units.contains(&singular(s))
+}
+
+/// units that name a discrete piece of something rather than a measurement,
+/// e.g. `2 cloves garlic` is two pieces of type `clove`.
+pub const COUNT_UNITS: &[&str] = &[
+    "clove", "stick", "slice", "can", "tin", "sprig", "piece", "head", "bunch", "stalk", "packet",
+    "package", "strand", "fillet", "whole", "each", "count",
+];
+
+pub fn is_count_unit(s: &str) -> bool {
+    COUNT_UNITS.contains(&singular(s).as_str())
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, PartialOrd, Ord, Debug, Eq, Hash, Serialize, Deserialize)]
 pub enum Unit {
     Gram,
     Kilogram,
@@ -45,6 +58,28 @@ pub enum Unit {
 }
 
 impl Unit {
+    /// whether this unit counts discrete pieces, see [COUNT_UNITS]
+    pub fn is_count(&self) -> bool {
+        matches!(self, Unit::Other(x) if is_count_unit(x))
+    }
+    /// which system the unit belongs to, `None` for units both use (minutes) or neither does (cloves)
+    pub fn system(&self) -> Option<MeasurementSystem> {
+        match self {
+            Unit::Gram | Unit::Kilogram | Unit::Liter | Unit::Milliliter | Unit::Celcius => {
+                Some(MeasurementSystem::Metric)
+            }
+            Unit::Teaspoon
+            | Unit::Tablespoon
+            | Unit::Cup
+            | Unit::Quart
+            | Unit::FluidOunce
+            | Unit::Ounce
+            | Unit::Pound
+            | Unit::Farhenheit
+            | Unit::Inch => Some(MeasurementSystem::Imperial),
+            _ => None,
+        }
+    }
     pub fn normalize(self) -> Unit {
         //todo
         match self {
@@ -79,6 +114,34 @@ impl Unit {
         }
         .to_string()
     }
+    /// spelled out version of [Unit::to_str], e.g. `tablespoon` instead of `tbsp`
+    pub fn to_long_str(self) -> String {
+        match self {
+            Unit::Gram => "gram",
+            Unit::Kilogram => "kilogram",
+            Unit::Liter => "liter",
+            Unit::Milliliter => "milliliter",
+            Unit::Teaspoon => "teaspoon",
+            Unit::Tablespoon => "tablespoon",
+            Unit::Cup => "cup",
+            Unit::Quart => "quart",
+            Unit::FluidOunce => "fluid ounce",
+            Unit::Ounce => "ounce",
+            Unit::Pound => "pound",
+            Unit::Cent => "cent",
+            Unit::Dollar => "dollar",
+            Unit::KCal => "calorie",
+            Unit::Day => "day",
+            Unit::Hour => "hour",
+            Unit::Minute => "minute",
+            Unit::Second => "second",
+            Unit::Celcius => "°C",
+            Unit::Farhenheit => "°F",
+            Unit::Inch => "inch",
+            Unit::Other(s) => return singular(&s),
+        }
+        .to_string()
+    }
 }
 
 impl FromStr for Unit {
@@ -126,9 +189,25 @@ impl fmt::Display for Unit {
 
 pub fn singular(s: &str) -> String {
     let s2 = s.to_lowercase();
+    // `bunches`, `dashes`, `inches`
+    if ["ches", "shes", "xes"].iter().any(|e| s2.ends_with(e)) {
+        return s2[..s2.len() - 2].to_string();
+    }
     s2.strip_suffix('s').unwrap_or(&s2).to_string()
 }
 
+/// Colloquial units, and roughly what they amount to in metric.
+#[rustfmt::skip]
+pub const INFORMAL_UNITS: &[(&str, f64, &str)] = &[
+    ("handful", 30.0, "g"),
+    ("knob", 15.0, "g"),
+    ("splash", 15.0, "ml"),
+    ("glug", 15.0, "ml"),
+    ("dollop", 30.0, "ml"),
+    ("dash", 0.6, "ml"),
+    ("bunch", 50.0, "g"),
+];
+
 #[cfg(test)]
 mod tests {
 
@@ -139,14 +218,37 @@ mod tests {
         assert!(is_valid(HashSet::from([]), "fl oz"));
         assert!(!is_valid(HashSet::from([]), "slice"));
         assert!(is_valid(HashSet::from(["slice".to_string()]), "slice"),);
+        assert!(is_valid(HashSet::from(["slice".to_string()]), "Slices"),);
         assert!(is_valid(HashSet::from([]), "TABLESPOONS"));
         assert!(!is_valid(HashSet::from([]), "foo"));
     }
     #[test]
+    fn test_singular() {
+        assert_eq!(singular("Cups"), "cup");
+        assert_eq!(singular("bunches"), "bunch");
+        assert_eq!(singular("dashes"), "dash");
+        assert_eq!(Unit::from_str(&singular("inches")).unwrap(), Unit::Inch);
+    }
+    #[test]
+    fn test_is_count() {
+        assert!(is_count_unit("Cloves"));
+        assert!(Unit::from_str("sprig").unwrap().is_count());
+        assert!(!Unit::from_str("foo").unwrap().is_count());
+        assert!(!Unit::from_str("tbsp").unwrap().is_count());
+    }
+    #[test]
+    fn test_system() {
+        assert_eq!(Unit::Kilogram.system(), Some(MeasurementSystem::Metric));
+        assert_eq!(Unit::Tablespoon.system(), Some(MeasurementSystem::Imperial));
+        assert_eq!(Unit::Minute.system(), None);
+        assert_eq!(Unit::from_str("clove").unwrap().system(), None);
+    }
+    #[test]
     fn test_back_forth() {
         assert_eq!(Unit::from_str("oz").unwrap(), Unit::Ounce);
         assert_eq!(Unit::from_str("gram").unwrap().to_str(), "g");
         assert_eq!(Unit::from_str("foo").unwrap().to_str(), "foo");
+        assert_eq!(Unit::from_str("tbsp").unwrap().to_long_str(), "tablespoon");
         assert_eq!(
             format!("{}", Unit::from_str("foo").unwrap()),
             "Other(\"foo\")"
@@ -1,6 +1,6 @@
+use crate::display::DisplayOptions;
 use crate::unit::singular;
-use crate::unit::{kind::MeasureKind, Unit};
-use crate::util::num_without_zeroes;
+use crate::unit::{kind::MeasureKind, MeasurementSystem, Unit, INFORMAL_UNITS};
 use crate::IngredientParser;
 use anyhow::Result;
 use petgraph::Graph;
@@ -42,11 +42,155 @@ pub fn add_time_amounts(a: Vec<Measure>) -> Measure {
     m.denormalize()
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
+// `remote = "Self"` turns the derives into inherent functions, which the manual impls
+// below wrap so that a measure can also be read from a string, as with [crate::Ingredient].
+/// An amount of a [Unit], or a range of them.
+///
+/// It's serialized as a struct, or, with [as_string], as a string such as `"1 - 2 cups"`.
+/// Either can be deserialized:
+/// ```
+/// use ingredient::unit::Measure;
+/// let m: Vec<Measure> = serde_json::from_str(
+///     r#"["1-2 cups", {"unit": "Cup", "value": 1.0, "upper_value": 2.0}]"#,
+/// ).unwrap();
+/// assert_eq!(m[0], Measure::parse_new_with_upper("cups", 1.0, 2.0));
+/// assert_eq!(m[0], m[1]);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct Measure {
     unit: Unit,
     value: f64,
     upper_value: Option<f64>,
+    /// the unit wasn't written, e.g. `whole` for `1 egg`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    inferred_unit: bool,
+    /// a limit rather than an amount, see [Measure::bound]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bound: Option<Bound>,
+    /// where it came from, see [Measure::source]
+    #[serde(default, skip_serializing_if = "Source::is_inline")]
+    source: Source,
+}
+
+/// Where a [Measure] came from, so the author's own measurements can be told apart
+/// from ones worked out from them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Debug, Default, Serialize, Deserialize)]
+pub enum Source {
+    /// written as the amount, e.g. both of `1 cup / 240 ml`
+    #[default]
+    Inline,
+    /// written in parentheses as an equivalent, e.g. the `240 ml` of `1 cup (240 ml)`
+    Parenthetical,
+    /// converted from another measure, e.g. by [Measure::to_system]
+    Derived,
+}
+
+impl Source {
+    fn is_inline(&self) -> bool {
+        *self == Source::Inline
+    }
+}
+
+/// Which way a [Measure] limits the amount, e.g. `at least 2 cups`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+pub enum Bound {
+    /// `at least` or `no less than`, the value is the minimum
+    AtLeast,
+    /// `up to`, `at most` or `no more than`, from `0` to the upper value
+    AtMost,
+}
+
+impl Serialize for Measure {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Measure::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Measure {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StringOrStruct;
+        impl<'de> serde::de::Visitor<'de> for StringOrStruct {
+            type Value = Measure;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an amount string or struct")
+            }
+            // the first amount, e.g. `1 cup` of `1 cup / 240 ml`
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Measure, E> {
+                IngredientParser::new(false)
+                    .parse_amount(value)
+                    .map_err(E::custom)?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| E::custom(format!("no amount in '{value}'")))
+            }
+            fn visit_map<M: serde::de::MapAccess<'de>>(self, map: M) -> Result<Measure, M::Error> {
+                Measure::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+            }
+        }
+        deserializer.deserialize_any(StringOrStruct)
+    }
+}
+
+/// For `#[serde(with = "ingredient::unit::as_string")]` on a [Measure], to write it as
+/// its `Display` string, e.g. `"1 - 2 cups"`, which keeps 2 decimal places.
+/// Either form is read back.
+/// ```
+/// use ingredient::unit::{self, Measure};
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Step {
+///     #[serde(with = "unit::as_string")]
+///     time: Measure,
+///     #[serde(with = "unit::as_strings")]
+///     amounts: Vec<Measure>,
+/// }
+/// let step = Step {
+///     time: Measure::parse_str("5-10 minutes"),
+///     amounts: vec![Measure::parse_str("1 cup"), Measure::parse_str("240 ml")],
+/// };
+/// let json = serde_json::to_string(&step).unwrap();
+/// assert_eq!(json, r#"{"time":"5 - 10 minutes","amounts":["1 cup","240 ml"]}"#);
+/// let back: Step = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back.time, step.time);
+/// assert_eq!(back.amounts, step.amounts);
+/// ```
+pub mod as_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Measure;
+
+    pub fn serialize<S: Serializer>(m: &Measure, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(m)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Measure, D::Error> {
+        <Measure as Deserialize>::deserialize(deserializer)
+    }
+}
+
+/// Like [as_string], for a list of them.
+pub mod as_strings {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Measure;
+
+    pub fn serialize<S: Serializer>(m: &[Measure], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(m.iter().map(|m| m.to_string()))
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Measure>, D::Error> {
+        Vec::<Measure>::deserialize(deserializer)
+    }
+}
+
+// `inferred_unit` and `source` are left out, `1 egg` and `1 whole egg` are the same amount
+impl PartialEq for Measure {
+    fn eq(&self, other: &Measure) -> bool {
+        self.unit == other.unit
+            && self.value == other.value
+            && self.upper_value == other.upper_value
+            && self.bound == other.bound
+    }
 }
 
 // multiplication factors
@@ -61,6 +205,11 @@ const CENTS_TO_DOLLAR: f64 = 100.0;
 const SEC_TO_MIN: f64 = 60.0;
 const SEC_TO_HOUR: f64 = 3600.0;
 const SEC_TO_DAY: f64 = 86400.0;
+const TSP_TO_ML: f64 = 4.92892;
+
+/// how far apart [Measure::approx_eq] allows values to be, relative to the larger one.
+/// Recipes round when converting, a cup is written as anything from 236 to 250 ml.
+const APPROX_TOLERANCE: f64 = 0.025;
 
 impl Measure {
     pub fn new_with_upper(unit: Unit, value: f64, upper_value: Option<f64>) -> Measure {
@@ -68,6 +217,9 @@ impl Measure {
             unit,
             value,
             upper_value,
+            inferred_unit: false,
+            bound: None,
+            source: Source::Inline,
         }
     }
     pub fn from_string(s: String) -> Measure {
@@ -79,9 +231,88 @@ impl Measure {
     pub fn unit(&self) -> Unit {
         self.unit.clone()
     }
+    /// whether the unit was filled in by the parser, see [IngredientParser::bare_count_unit]
+    pub fn inferred_unit(&self) -> bool {
+        self.inferred_unit
+    }
+    pub(crate) fn with_inferred_unit(mut self) -> Measure {
+        self.inferred_unit = true;
+        self
+    }
+    /// Whether it's a minimum or maximum rather than the amount to use.
+    /// ```
+    /// use ingredient::unit::{Bound, Measure};
+    /// assert_eq!(Measure::parse_str("at least 2 cups").bound(), Some(Bound::AtLeast));
+    /// assert_eq!(Measure::parse_str("no more than 1 tsp").bound(), Some(Bound::AtMost));
+    /// assert_eq!(Measure::parse_str("2 cups").bound(), None);
+    /// assert_eq!((Measure::parse_str("at least 2 cups") * 2.0).to_string(), "at least 4 cups");
+    /// ```
+    pub fn bound(&self) -> Option<Bound> {
+        self.bound
+    }
+    pub fn with_bound(mut self, bound: Bound) -> Measure {
+        self.bound = Some(bound);
+        self
+    }
+    /// Whether it was written inline, in parentheses, or worked out from another measure.
+    /// ```
+    /// use ingredient::{from_str, unit::{MeasurementSystem, Source}};
+    /// let i = from_str("1 cup (240 ml) milk");
+    /// assert_eq!(i.amounts[0].source(), Source::Inline);
+    /// assert_eq!(i.amounts[1].source(), Source::Parenthetical);
+    /// let m = i.amounts[0].to_system(MeasurementSystem::Metric).unwrap();
+    /// assert_eq!(m.source(), Source::Derived);
+    /// ```
+    pub fn source(&self) -> Source {
+        self.source
+    }
+    pub fn with_source(mut self, source: Source) -> Measure {
+        self.source = source;
+        self
+    }
     pub fn values(&self) -> (f64, Option<f64>, String) {
         (self.value, self.upper_value, self.unit_as_string())
     }
+    /// For times, the (lower bound of the) amount as a [std::time::Duration].
+    pub fn duration(&self) -> Option<std::time::Duration> {
+        match self.kind().ok()? {
+            // `None` when it's too long to be a duration
+            MeasureKind::Time => {
+                std::time::Duration::try_from_secs_f64(self.normalize().value.max(0.0)).ok()
+            }
+            _ => None,
+        }
+    }
+    /// For counts of discrete pieces (`2 cloves`), the number of pieces and the piece type.
+    pub fn as_count(&self) -> Option<(f64, String)> {
+        match &self.unit {
+            Unit::Other(x) if self.unit.is_count() => Some((self.value, singular(x))),
+            _ => None,
+        }
+    }
+    /// For colloquial units such as `handful` or `splash`, a rough metric equivalent,
+    /// see [crate::unit::INFORMAL_UNITS].
+    /// ```
+    /// use ingredient::unit::Measure;
+    /// let m = Measure::parse_str("2 knobs");
+    /// assert_eq!(m.approximate_metric(), Some(Measure::parse_str("30 g")));
+    /// assert_eq!(Measure::parse_str("1 cup").approximate_metric(), None);
+    /// ```
+    pub fn approximate_metric(&self) -> Option<Measure> {
+        let name = match &self.unit {
+            Unit::Other(x) => singular(x),
+            _ => return None,
+        };
+        let (_, factor, unit) = INFORMAL_UNITS.iter().find(|(u, _, _)| *u == name)?;
+        Some(
+            Measure::from_parts(
+                unit,
+                self.value * factor,
+                self.upper_value.map(|u| u * factor),
+            )
+            .with_source(Source::Derived),
+        )
+    }
     pub fn normalize(&self) -> Measure {
         let (unit, factor) = match &self.unit {
             Unit::Teaspoon
@@ -96,7 +327,10 @@ impl Measure {
             Unit::Other(x) => {
                 let x2 = x.clone();
                 let u2 = singular(&x2);
-                return Measure::new_with_upper(Unit::Other(u2), self.value, self.upper_value);
+                return Measure {
+                    unit: Unit::Other(u2),
+                    ..self.clone()
+                };
             }
 
             Unit::Kilogram => (Unit::Gram, G_TO_K),
@@ -121,6 +355,9 @@ impl Measure {
             unit,
             value: self.value * factor,
             upper_value: self.upper_value.map(|x| x * factor),
+            inferred_unit: self.inferred_unit,
+            bound: self.bound,
+            source: self.source,
         }
     }
     pub fn add(&self, b: Measure) -> Result<Measure> {
@@ -139,6 +376,13 @@ impl Measure {
         }
         let left = self.normalize();
         let right = b.normalize();
+        if left.unit.is_count() && left.unit != right.unit {
+            return Err(anyhow::anyhow!(
+                "Cannot add different kinds of pieces: {:?} {:?}",
+                self,
+                b
+            ));
+        }
 
         Ok(Measure {
             unit: left.unit.clone(),
@@ -149,6 +393,9 @@ impl Measure {
                 (None, Some(b)) => Some(left.value + b),
                 (Some(a), None) => Some(a + right.value),
             },
+            inferred_unit: left.inferred_unit && right.inferred_unit,
+            bound: None,
+            source: Source::Inline,
         })
     }
     pub fn parse_new(unit: &str, value: f64) -> Measure {
@@ -162,6 +409,9 @@ impl Measure {
             unit: Unit::from_str(singular(unit).as_ref()).unwrap(),
             value,
             upper_value,
+            inferred_unit: false,
+            bound: None,
+            source: Source::Inline,
         }
     }
 
@@ -174,6 +424,7 @@ impl Measure {
             Unit::Second => Ok(MeasureKind::Time),
             Unit::Farhenheit | Unit::Celcius => Ok(MeasureKind::Temperature), // todo: convert to farhenheit?
             Unit::Inch => Ok(MeasureKind::Length),
+            Unit::Other(_) if self.unit.is_count() => Ok(MeasureKind::Count),
             Unit::Other(_) => Ok(MeasureKind::Other),
             Unit::Kilogram
             | Unit::Liter
@@ -231,6 +482,9 @@ impl Measure {
             unit: u,
             value: self.value / f,
             upper_value: self.upper_value.map(|x| x / f),
+            inferred_unit: self.inferred_unit,
+            bound: self.bound,
+            source: self.source,
         }
     }
 
@@ -274,30 +528,395 @@ impl Measure {
                 .map(|x| (x * factor * 100.0).round() / 100.0),
         );
         debug!("{:?} -> {:?} ({} hops)", input, result, steps.len());
-        Some(result.denormalize())
+        Some(result.denormalize().with_source(Source::Derived))
     }
-    fn unit_as_string(&self) -> String {
-        let measure = self.clone(); //.denormalize();
-        let mut s = singular(&measure.unit().to_str());
-        if (measure.unit() == Unit::Cup || measure.unit() == Unit::Minute)
-            && (self.value > 1.0 || self.upper_value.unwrap_or_default() > 1.0)
-        {
-            s.push('s');
+    // normalized, and with volumes all in teaspoons, so that any two measures of
+    // the same kind are in the same unit.
+    fn to_base(&self) -> Measure {
+        let m = self.normalize();
+        match m.unit {
+            Unit::Milliliter => Measure {
+                unit: Unit::Teaspoon,
+                value: m.value / TSP_TO_ML,
+                upper_value: m.upper_value.map(|x| x / TSP_TO_ML),
+                inferred_unit: m.inferred_unit,
+                bound: m.bound,
+                source: m.source,
+            },
+            _ => m,
+        }
+    }
+    /// Converts weights and volumes to the best fitting unit of `system`, e.g. `8 oz` to `226.8 g`.
+    /// Measures that are already in `system` are left as they are.
+    /// ```
+    /// use ingredient::unit::{Measure, MeasurementSystem};
+    /// let m = Measure::parse_str("8 oz");
+    /// assert_eq!(m.to_system(MeasurementSystem::Metric).unwrap().to_string(), "226.8 g");
+    /// let m = Measure::parse_str("240 ml");
+    /// assert_eq!(m.to_system(MeasurementSystem::Imperial).unwrap().to_string(), "1.01 cups");
+    /// assert_eq!(Measure::parse_str("2 cloves").to_system(MeasurementSystem::Metric), None);
+    /// ```
+    pub fn to_system(&self, system: MeasurementSystem) -> Option<Measure> {
+        let from = self.unit.system()?;
+        if from == system {
+            return Some(self.clone());
+        }
+        let base = self.to_base();
+        let (unit, factor) = match (&base.unit, system) {
+            (Unit::Gram, MeasurementSystem::Metric) => match base.value {
+                v if v < G_TO_K => (Unit::Gram, 1.0),
+                _ => (Unit::Kilogram, 1.0 / G_TO_K),
+            },
+            (Unit::Gram, MeasurementSystem::Imperial) => match base.value / GRAM_TO_OZ {
+                v if v < OZ_TO_LB => (Unit::Ounce, 1.0 / GRAM_TO_OZ),
+                _ => (Unit::Pound, 1.0 / (GRAM_TO_OZ * OZ_TO_LB)),
+            },
+            (Unit::Teaspoon, MeasurementSystem::Metric) => match base.value * TSP_TO_ML {
+                v if v < G_TO_K => (Unit::Milliliter, TSP_TO_ML),
+                _ => (Unit::Liter, TSP_TO_ML / G_TO_K),
+            },
+            (Unit::Teaspoon, MeasurementSystem::Imperial) => {
+                return Some(base.denormalize().with_source(Source::Derived))
+            }
+            // temperatures and lengths
+            _ => return None,
+        };
+        Some(Measure {
+            unit,
+            value: base.value * factor,
+            upper_value: base.upper_value.map(|u| u * factor),
+            inferred_unit: false,
+            bound: base.bound,
+            source: Source::Derived,
+        })
+    }
+    /// Compares across units, e.g. `2 cups` is more than `400 ml`. Measures of
+    /// different kinds, or different non-standard units, can't be compared.
+    /// ```
+    /// use ingredient::unit::Measure;
+    /// use std::cmp::Ordering;
+    /// let cups = Measure::parse_str("2 cups");
+    /// assert_eq!(cups.compare(&Measure::parse_str("400 ml")), Some(Ordering::Greater));
+    /// assert_eq!(cups.compare(&Measure::parse_str("1 quart")), Some(Ordering::Less));
+    /// assert_eq!(cups.compare(&Measure::parse_str("2 grams")), None);
+    /// ```
+    pub fn compare(&self, other: &Measure) -> Option<std::cmp::Ordering> {
+        let (a, b) = (self.to_base(), other.to_base());
+        if a.unit != b.unit {
+            return None;
         }
-        s
+        a.value
+            .partial_cmp(&b.value)
+            .map(|o| o.then(a.upper_value.partial_cmp(&b.upper_value).unwrap_or(o)))
+    }
+    /// Whether two measures are about the same amount once converted,
+    /// e.g. `2 cups` and `480 ml`.
+    /// ```
+    /// use ingredient::unit::Measure;
+    /// assert!(Measure::parse_str("2 cups").approx_eq(&Measure::parse_str("480 ml")));
+    /// assert!(!Measure::parse_str("2 cups").approx_eq(&Measure::parse_str("1 cup")));
+    /// ```
+    pub fn approx_eq(&self, other: &Measure) -> bool {
+        let (a, b) = (self.to_base(), other.to_base());
+        let close = |x: f64, y: f64| (x - y).abs() <= x.abs().max(y.abs()) * APPROX_TOLERANCE;
+        a.unit == b.unit
+            && close(a.value, b.value)
+            && match (a.upper_value, b.upper_value) {
+                (Some(x), Some(y)) => close(x, y),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+    fn unit_as_string(&self) -> String {
+        DisplayOptions::default().unit_label(
+            &self.unit,
+            self.value.max(self.upper_value.unwrap_or_default()),
+        )
+    }
+    /// Formats with custom [DisplayOptions], `Display` uses the defaults.
+    pub fn format(&self, options: &DisplayOptions) -> String {
+        options.format_measure(self)
     }
 }
 
-impl fmt::Display for Measure {
+/// Why two measures couldn't be combined, see [Measure::checked_add].
+#[derive(Clone, PartialEq, Debug)]
+pub enum ArithmeticError {
+    /// e.g. cups and grams, which need [Measure::convert_measure_via_mappings] first
+    IncompatibleUnits(Unit, Unit),
+    /// subtracting more than there is
+    Negative,
+}
+
+impl fmt::Display for ArithmeticError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let measure = self.clone().denormalize();
-        write!(f, "{}", num_without_zeroes(measure.value)).unwrap();
-        if let Some(u) = measure.upper_value {
-            if u != 0.0 {
-                write!(f, " - {}", num_without_zeroes(u)).unwrap();
+        match self {
+            ArithmeticError::IncompatibleUnits(a, b) => write!(
+                f,
+                "cannot combine {} and {}",
+                a.clone().to_str(),
+                b.clone().to_str()
+            ),
+            ArithmeticError::Negative => write!(f, "result would be negative"),
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+impl Measure {
+    /// Adds two measures, converting `other` to the unit of `self`.
+    /// Ranges add bound by bound, a plain value counting as both bounds.
+    /// ```
+    /// use ingredient::unit::{ArithmeticError, Measure, Unit};
+    /// let cup = Measure::parse_str("1 cup");
+    /// assert_eq!(&cup + &Measure::parse_str("4 tbsp"), Ok(Measure::parse_str("1.25 cups")));
+    /// assert_eq!(
+    ///     &cup + &Measure::parse_str("1-2 tbsp"),
+    ///     Ok(Measure::parse_str("1.0625-1.125 cups"))
+    /// );
+    /// assert_eq!(
+    ///     &cup + &Measure::parse_str("1 g"),
+    ///     Err(ArithmeticError::IncompatibleUnits(Unit::Cup, Unit::Gram))
+    /// );
+    /// ```
+    pub fn checked_add(&self, other: &Measure) -> Result<Measure, ArithmeticError> {
+        let (a, b) = self.base_pair(other)?;
+        let (a_upper, b_upper) = (a.upper_value, b.upper_value);
+        let upper = match (a_upper, b_upper) {
+            (None, None) => None,
+            _ => Some(a_upper.unwrap_or(a.value) + b_upper.unwrap_or(b.value)),
+        };
+        Ok(self.with_base_values(a.value + b.value, upper, other))
+    }
+    /// Subtracts `other`, bound by bound for ranges, e.g. `3-4 cups` less `1 cup` is `2-3 cups`.
+    /// ```
+    /// use ingredient::unit::{ArithmeticError, Measure};
+    /// let m = Measure::parse_str("1 lb");
+    /// assert_eq!(&m - &Measure::parse_str("8 oz"), Ok(Measure::parse_str("0.5 lb")));
+    /// assert_eq!(&m - &Measure::parse_str("2 lb"), Err(ArithmeticError::Negative));
+    /// ```
+    pub fn checked_sub(&self, other: &Measure) -> Result<Measure, ArithmeticError> {
+        let (a, b) = self.base_pair(other)?;
+        let lower = a.value - b.value;
+        let upper = match (a.upper_value, b.upper_value) {
+            (None, None) => None,
+            (a_upper, b_upper) => Some(a_upper.unwrap_or(a.value) - b_upper.unwrap_or(b.value)),
+        };
+        // `2-3 cups` less `0-2 cups` would otherwise end up as `2-1 cups`
+        let (lower, upper) = match upper {
+            Some(u) if u < lower => (u, Some(lower)),
+            Some(u) if u == lower => (lower, None),
+            u => (lower, u),
+        };
+        if lower < 0.0 {
+            return Err(ArithmeticError::Negative);
+        }
+        Ok(self.with_base_values(lower, upper, other))
+    }
+    // both sides in the same base unit, if they can be combined at all
+    fn base_pair(&self, other: &Measure) -> Result<(Measure, Measure), ArithmeticError> {
+        let (a, b) = (self.to_base(), other.to_base());
+        // a unit without conversions (`2 large`) or pieces (`2 cloves`) only lines up with itself
+        if a.unit != b.unit {
+            return Err(ArithmeticError::IncompatibleUnits(
+                self.unit(),
+                other.unit(),
+            ));
+        }
+        Ok((a, b))
+    }
+    // back from the base unit to the unit of `self`
+    fn with_base_values(&self, value: f64, upper: Option<f64>, other: &Measure) -> Measure {
+        let factor = Measure::new_with_upper(self.unit(), 1.0, None)
+            .to_base()
+            .value;
+        Measure {
+            unit: self.unit(),
+            value: value / factor,
+            upper_value: upper.map(|u| u / factor),
+            inferred_unit: self.inferred_unit && other.inferred_unit,
+            bound: None,
+            source: self.source,
+        }
+    }
+}
+
+impl Measure {
+    /// Snaps the amount to something that can be measured out in a kitchen,
+    /// e.g. after scaling a recipe: `0.667 cups` becomes `⅔ cup`, `13 g` becomes `15 g`.
+    /// Spoons and cups are converted between each other to the one that fits best.
+    /// ```
+    /// use ingredient::unit::Measure;
+    /// let round = |s: &str| Measure::parse_str(s).round_to_kitchen_fraction().to_string();
+    /// assert_eq!(round("0.667 cups"), "0.67 cup");
+    /// assert_eq!(round("4 tbsp"), "0.25 cup");
+    /// assert_eq!(round("0.3 tsp"), "0.25 tsp");
+    /// assert_eq!(round("1.4-1.6 tbsp"), "1.5 tbsp");
+    /// assert_eq!(round("113.4 g"), "115 g");
+    /// ```
+    pub fn round_to_kitchen_fraction(&self) -> Measure {
+        let (unit, steps): (Unit, &[f64]) = match self.unit {
+            Unit::Teaspoon | Unit::Tablespoon | Unit::Cup | Unit::Quart => {
+                // by the most it can be, the least of `up to 2 cups` is none at all
+                let tsp = self.normalize();
+                match tsp.upper_value.unwrap_or(tsp.value) {
+                    0.0 => (self.unit(), &EIGHTHS),
+                    t if t < TSP_TO_TBSP => (Unit::Teaspoon, &EIGHTHS),
+                    t if t < TSP_TO_CUP / 4.0 => (Unit::Tablespoon, &HALVES),
+                    _ => (Unit::Cup, &CUP_FRACTIONS),
+                }
+            }
+            Unit::FluidOunce | Unit::Ounce => (self.unit(), &HALVES),
+            Unit::Pound | Unit::Kilogram | Unit::Liter => (self.unit(), &QUARTERS),
+            Unit::Gram | Unit::Milliliter => {
+                let round = |v: f64| match v {
+                    v if v < 10.0 => v.round().max(1.0),
+                    v => (v / 5.0).round() * 5.0,
+                };
+                return self.with_rounded_values(self.unit(), round);
             }
+            Unit::Other(_) => (self.unit(), &QUARTERS),
+            // not something that gets measured out
+            _ => return self.clone(),
+        };
+        self.with_rounded_values(unit, |v| snap_to_fraction(v, steps))
+    }
+    /// Rounds each bound to `significant_figures`, e.g. for converted amounts like
+    /// `155.55556 g`, keeping the unit. A range whose bounds round to the same value
+    /// becomes that one value.
+    /// ```
+    /// use ingredient::unit::Measure;
+    /// let round = |s: &str, n| Measure::parse_str(s).rounded(n).to_string();
+    /// assert_eq!(round("155.55556 g", 3), "156 g");
+    /// assert_eq!(round("1234 g", 2), "1200 g");
+    /// assert_eq!(round("0.0333 tsp", 1), "0.03 tsp");
+    /// assert_eq!(round("148-152 ml", 2), "150 ml");
+    /// assert_eq!(round("148-252 ml", 1), "100 - 300 ml");
+    /// ```
+    pub fn rounded(&self, significant_figures: usize) -> Measure {
+        self.with_rounded_values(self.unit(), |v| {
+            round_to_significant_figures(v, significant_figures)
+        })
+    }
+    // converts to `unit` and rounds each bound, collapsing a range that rounds to one value
+    fn with_rounded_values(&self, unit: Unit, round: impl Fn(f64) -> f64) -> Measure {
+        let factor = |u: &Unit| {
+            Measure::new_with_upper(u.clone(), 1.0, None)
+                .normalize()
+                .value
+        };
+        let ratio = factor(&self.unit) / factor(&unit);
+        // none, e.g. the least of `up to 2 cups`, stays none
+        let round = |v: f64| match v {
+            0.0 => v,
+            v => round(v),
+        };
+        let value = round(self.value * ratio);
+        let upper_value = self
+            .upper_value
+            .map(|u| round(u * ratio))
+            .filter(|u| *u != value);
+        Measure {
+            unit,
+            value,
+            upper_value,
+            inferred_unit: self.inferred_unit,
+            bound: self.bound,
+            source: self.source,
+        }
+    }
+}
+
+const EIGHTHS: [f64; 9] = [0.0, 0.125, 0.25, 0.375, 0.5, 0.625, 0.75, 0.875, 1.0];
+const QUARTERS: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+const HALVES: [f64; 3] = [0.0, 0.5, 1.0];
+// measuring cups come in thirds as well as quarters
+const CUP_FRACTIONS: [f64; 7] = [0.0, 0.25, 1.0 / 3.0, 0.5, 2.0 / 3.0, 0.75, 1.0];
+
+// `v` with only its first `n` digits, `0` leaves it as it is
+fn round_to_significant_figures(v: f64, n: usize) -> f64 {
+    if v == 0.0 || n == 0 || !v.is_finite() {
+        return v;
+    }
+    // which power of ten the last kept digit is
+    let exp = v.abs().log10().floor() as i32 + 1 - n as i32;
+    match exp {
+        e if e < 0 => {
+            let scale = 10f64.powi(-e);
+            (v * scale).round() / scale
+        }
+        e => {
+            let scale = 10f64.powi(e);
+            (v / scale).round() * scale
+        }
+    }
+}
+
+// nearest whole number plus one of `steps`, never rounding something down to nothing
+fn snap_to_fraction(v: f64, steps: &[f64]) -> f64 {
+    let whole = v.floor();
+    let frac = steps
+        .iter()
+        .copied()
+        .filter(|s| whole + s > 0.0)
+        .min_by(|a, b| (v - whole - a).abs().total_cmp(&(v - whole - b).abs()))
+        .unwrap_or_default();
+    whole + frac
+}
+
+impl std::ops::Add for &Measure {
+    type Output = Result<Measure, ArithmeticError>;
+    fn add(self, other: &Measure) -> Self::Output {
+        self.checked_add(other)
+    }
+}
+
+impl std::ops::Sub for &Measure {
+    type Output = Result<Measure, ArithmeticError>;
+    fn sub(self, other: &Measure) -> Self::Output {
+        self.checked_sub(other)
+    }
+}
+
+/// Scales the amount, ranges included.
+/// ```
+/// use ingredient::unit::Measure;
+/// assert_eq!(Measure::parse_str("1-2 cups") * 1.5, Measure::parse_str("1.5-3 cups"));
+/// assert_eq!(Measure::parse_str("3 eggs") / 3.0, Measure::parse_str("1 egg"));
+/// ```
+impl std::ops::Mul<f64> for Measure {
+    type Output = Measure;
+    fn mul(self, factor: f64) -> Measure {
+        Measure {
+            value: self.value * factor,
+            upper_value: self.upper_value.map(|u| u * factor),
+            ..self
+        }
+    }
+}
+
+impl std::ops::Div<f64> for Measure {
+    type Output = Measure;
+    fn div(self, divisor: f64) -> Measure {
+        self * (1.0 / divisor)
+    }
+}
+
+/// Orders by [Measure::compare], but only considers measures that are `==` equal,
+/// so `1 cup` and `16 tbsp` are incomparable rather than equal.
+impl PartialOrd for Measure {
+    fn partial_cmp(&self, other: &Measure) -> Option<std::cmp::Ordering> {
+        match self.compare(other)? {
+            std::cmp::Ordering::Equal if self != other => None,
+            o => Some(o),
         }
-        write!(f, " {}", self.unit_as_string())
+    }
+}
+
+impl fmt::Display for Measure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(&DisplayOptions::default()))
     }
 }
 
@@ -461,6 +1080,41 @@ mod tests {
         );
     }
     #[test]
+    fn test_arithmetic() {
+        let m = Measure::parse_str;
+        assert_eq!(&m("2 cloves") + &m("1 clove"), Ok(m("3 cloves")));
+        assert!((&m("2 cloves") + &m("1 slice")).is_err());
+        assert!((&m("1 cup") + &m("240 ml"))
+            .unwrap()
+            .approx_eq(&m("2 cups")));
+        assert_eq!(&m("2-3 cups") - &m("0-2 cups"), Ok(m("1-2 cups")));
+        assert_eq!(&m("2-3 cups") - &m("1-2 cups"), Ok(m("1 cup")));
+        assert_eq!(m("2 large") * 2.0, m("4 large"));
+    }
+    #[test]
+    fn test_round_to_kitchen_fraction() {
+        let round = |s: &str| Measure::parse_str(s).round_to_kitchen_fraction();
+        assert_eq!(round("0.01 tsp"), Measure::parse_str("0.125 tsp"));
+        assert_eq!(round("2.9 tsp"), Measure::parse_str("2.875 tsp"));
+        assert_eq!(round("3 tsp"), Measure::parse_str("1 tbsp"));
+        assert_eq!(round("10 tbsp"), Measure::parse_new("cup", 2.0 / 3.0));
+        assert_eq!(round("1.1 lb"), Measure::parse_str("1 lb"));
+        assert_eq!(round("2.6 g"), Measure::parse_str("3 g"));
+        assert_eq!(round("0.3 g"), Measure::parse_str("1 g"));
+        assert_eq!(round("1.3 eggs"), Measure::parse_str("1.25 eggs"));
+        assert_eq!(round("0.1 eggs"), Measure::parse_str("0.25 eggs"));
+        assert_eq!(round("25.3 minutes"), Measure::parse_str("25.3 minutes"));
+        assert_eq!(round("0 cups"), Measure::parse_str("0 cups"));
+        assert_eq!(round("0 g"), Measure::parse_str("0 g"));
+        assert_eq!(round("up to 2 cups"), Measure::parse_str("up to 2 cups"));
+        assert_eq!(round("up to 3 tbsp"), Measure::parse_str("up to 3 tbsp"));
+        assert_eq!(round("up to 10 g"), Measure::parse_str("up to 10 g"));
+        assert_eq!(
+            round("2-6 tbsp"),
+            Measure::new_with_upper(Unit::Cup, 0.25, Some(1.0 / 3.0))
+        );
+    }
+    #[test]
     fn test_print_graph() {
         let g = make_graph(vec![
             (
@@ -489,4 +1143,49 @@ mod tests {
         assert_eq!(Measure::parse_str("2 cup").unit_as_string(), "cups");
         assert_eq!(Measure::parse_str("3 grams").unit_as_string(), "g");
     }
+    #[test]
+    fn test_count() {
+        let cloves = Measure::parse_str("2 cloves");
+        assert_eq!(cloves.kind().unwrap(), MeasureKind::Count);
+        assert_eq!(cloves.as_count(), Some((2.0, "clove".to_string())));
+        assert_eq!(Measure::parse_str("1 cup").as_count(), None);
+        assert_eq!(
+            cloves.add(Measure::parse_str("1 clove")).unwrap(),
+            Measure::parse_new("clove", 3.0)
+        );
+        assert!(cloves.add(Measure::parse_str("1 stick")).is_err());
+    }
+    #[test]
+    fn test_compare() {
+        use std::cmp::Ordering;
+        let tbsp = Measure::parse_str("16 tbsp");
+        let cup = Measure::parse_str("1 cup");
+        assert_eq!(tbsp.compare(&cup), Some(Ordering::Equal));
+        assert_eq!(tbsp.partial_cmp(&cup), None);
+        assert!(Measure::parse_str("1 lb") > Measure::parse_str("400 g"));
+        assert!(Measure::parse_str("1 kg") < Measure::parse_str("3 lb"));
+        assert_eq!(
+            Measure::parse_str("2 cloves").compare(&Measure::parse_str("1 stick")),
+            None
+        );
+        assert!(Measure::parse_str("1-2 cups").approx_eq(&Measure::parse_str("240-480 ml")));
+        assert!(!Measure::parse_str("1-2 cups").approx_eq(&Measure::parse_str("240 ml")));
+    }
+    #[test]
+    fn test_duration() {
+        use std::time::Duration;
+        assert_eq!(
+            Measure::parse_str("2 hours").duration(),
+            Some(Duration::from_secs(7200))
+        );
+        assert_eq!(
+            Measure::parse_str("20-25 minutes").duration(),
+            Some(Duration::from_secs(1200))
+        );
+        assert_eq!(Measure::parse_str("2 cups").duration(), None);
+        assert_eq!(
+            Measure::parse_str("99999999999999999999 hours").duration(),
+            None
+        );
+    }
 }
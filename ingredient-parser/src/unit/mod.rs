@@ -7,3 +7,6 @@ pub use kind::*;
 pub(crate) mod measure;
 
 pub use measure::*;
+
+#[cfg(feature = "uom")]
+pub mod quantity;
@@ -0,0 +1,93 @@
+//! Conversions between [Measure] and the typed quantities of [uom], for weights and volumes.
+//! ```
+//! use ingredient::{from_str, unit::{quantity::Quantity, Measure}};
+//! use uom::si::{f64::Mass, mass::{gram, pound}};
+//!
+//! let flour = from_str("2 lb flour");
+//! let Some(Quantity::Mass(m)) = flour.amounts[0].to_uom() else { panic!() };
+//! assert!((m.get::<gram>() - 907.18).abs() < 0.01);
+//! assert_eq!(Measure::from(Mass::new::<pound>(1.0)).to_string(), "453.59 g");
+//! ```
+use uom::si::{
+    f64::{Mass, Volume},
+    mass, volume,
+};
+
+use super::{Measure, Unit};
+
+/// A [Measure] as a [uom] quantity.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Quantity {
+    Mass(Mass),
+    Volume(Volume),
+}
+
+impl Measure {
+    /// The amount as a [Quantity], or `None` when it isn't a weight or a volume.
+    /// A range gives its lower bound.
+    pub fn to_uom(&self) -> Option<Quantity> {
+        let (v, _, _) = self.values();
+        Some(match self.unit() {
+            Unit::Gram => Quantity::Mass(Mass::new::<mass::gram>(v)),
+            Unit::Kilogram => Quantity::Mass(Mass::new::<mass::kilogram>(v)),
+            Unit::Ounce => Quantity::Mass(Mass::new::<mass::ounce>(v)),
+            Unit::Pound => Quantity::Mass(Mass::new::<mass::pound>(v)),
+            Unit::Milliliter => Quantity::Volume(Volume::new::<volume::milliliter>(v)),
+            Unit::Liter => Quantity::Volume(Volume::new::<volume::liter>(v)),
+            Unit::Teaspoon => Quantity::Volume(Volume::new::<volume::teaspoon>(v)),
+            Unit::Tablespoon => Quantity::Volume(Volume::new::<volume::tablespoon>(v)),
+            Unit::Cup => Quantity::Volume(Volume::new::<volume::cup>(v)),
+            Unit::Quart => Quantity::Volume(Volume::new::<volume::quart_liquid>(v)),
+            Unit::FluidOunce => Quantity::Volume(Volume::new::<volume::fluid_ounce>(v)),
+            _ => return None,
+        })
+    }
+}
+
+/// In grams.
+impl From<Mass> for Measure {
+    fn from(m: Mass) -> Self {
+        Measure::new_with_upper(Unit::Gram, m.get::<mass::gram>(), None)
+    }
+}
+
+/// In milliliters.
+impl From<Volume> for Measure {
+    fn from(v: Volume) -> Self {
+        Measure::new_with_upper(Unit::Milliliter, v.get::<volume::milliliter>(), None)
+    }
+}
+
+impl From<Quantity> for Measure {
+    fn from(q: Quantity) -> Self {
+        match q {
+            Quantity::Mass(m) => m.into(),
+            Quantity::Volume(v) => v.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for (s, back) in [
+            ("250 g", "250 g"),
+            ("1.5 kg", "1500 g"),
+            ("2 cups", "473.18 ml"),
+            ("1 tbsp", "14.79 ml"),
+            ("1 l", "1000 ml"),
+        ] {
+            let q = Measure::parse_str(s).to_uom().unwrap();
+            assert_eq!(Measure::from(q).to_string(), back, "{s}");
+        }
+        assert_eq!(
+            Measure::parse_str("1-2 cups").to_uom(),
+            Measure::parse_str("1 cup").to_uom()
+        );
+        assert_eq!(Measure::parse_str("3 whole").to_uom(), None);
+        assert_eq!(Measure::parse_str("10 minutes").to_uom(), None);
+    }
+}
@@ -1,7 +1,68 @@
 pub fn num_without_zeroes(val: f64) -> String {
-    let mut val = format!("{val:.2}");
-    val = val.trim_end_matches('0').trim_end_matches('.').to_string();
-    val
+    num_with_precision(val, 2)
+}
+/// formats with at most `precision` decimal places, trimming trailing zeroes
+pub fn num_with_precision(val: f64, precision: usize) -> String {
+    let val = format!("{val:.precision$}");
+    match val.contains('.') {
+        true => val.trim_end_matches('0').trim_end_matches('.').to_string(),
+        false => val,
+    }
+}
+
+// combining mark, then the base letters it can be composed with, and the results
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+    ('\u{300}', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+    ('\u{301}', "aeiouyncAEIOUYNC", "áéíóúýńćÁÉÍÓÚÝŃĆ"),
+    ('\u{302}', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+    ('\u{303}', "anoANO", "ãñõÃÑÕ"),
+    ('\u{308}', "aeiouyAEIOU", "äëïöüÿÄËÏÖÜ"),
+    ('\u{327}', "cC", "çÇ"),
+];
+
+fn compose(base: char, mark: char) -> Option<char> {
+    let (_, bases, composed) = COMPOSITIONS.iter().find(|(m, _, _)| *m == mark)?;
+    let idx = bases.chars().position(|b| b == base)?;
+    composed.chars().nth(idx)
+}
+
+/// Rewrites the unicode that shows up in copy-pasted recipes into what the parser
+/// understands: unusual spaces become plain spaces, dashes between numbers become `-`,
+/// curly quotes become straight ones, and decomposed accents are recomposed.
+/// ```
+/// use ingredient::util::normalize_unicode;
+/// assert_eq!(normalize_unicode("1\u{a0}cup"), "1 cup");
+/// assert_eq!(normalize_unicode("2 — 3 cups"), "2 - 3 cups");
+/// assert_eq!(normalize_unicode("¼–½ tsp"), "¼-½ tsp");
+/// assert_eq!(normalize_unicode("cre\u{300}me frai\u{302}che"), "crème fraîche");
+/// ```
+pub fn normalize_unicode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let after_digit = out
+            .trim_end()
+            .ends_with(|p: char| p.is_ascii_digit() || crate::fraction::is_frac_char(p));
+        let c = match c {
+            '\u{a0}' | '\u{2007}' | '\u{202f}' | '\u{2000}'..='\u{200a}' => ' ',
+            '\u{2010}'..='\u{2015}' | '\u{2212}' if after_digit => '-',
+            '\u{2018}' | '\u{2019}' | '\u{2032}' => '\'',
+            '\u{201c}' | '\u{201d}' | '\u{2033}' => '"',
+            '\u{2044}' => '/',
+            '\u{300}'..='\u{36f}' => match out.pop() {
+                Some(base) => match compose(base, c) {
+                    Some(composed) => composed,
+                    None => {
+                        out.push(base);
+                        c
+                    }
+                },
+                None => c,
+            },
+            c => c,
+        };
+        out.push(c);
+    }
+    out
 }
 
 #[cfg(test)]
@@ -13,5 +74,19 @@ mod tests {
         assert_eq!(num_without_zeroes(1.1), "1.1");
         assert_eq!(num_without_zeroes(1.01), "1.01");
         assert_eq!(num_without_zeroes(1.234), "1.23");
+        assert_eq!(num_with_precision(10.0, 0), "10");
+        assert_eq!(num_with_precision(155.555, 1), "155.6");
+    }
+    #[test]
+    fn test_normalize_unicode() {
+        assert_eq!(normalize_unicode("1\u{202f}½ cups"), "1 ½ cups");
+        assert_eq!(normalize_unicode("1–2 tbsp"), "1-2 tbsp");
+        assert_eq!(normalize_unicode("salt — to taste"), "salt — to taste");
+        assert_eq!(
+            normalize_unicode("confectioners’ sugar"),
+            "confectioners' sugar"
+        );
+        assert_eq!(normalize_unicode("1\u{2044}2 cup"), "1/2 cup");
+        assert_eq!(normalize_unicode("\u{301}a"), "\u{301}a");
     }
 }
@@ -8,6 +8,7 @@ fn test_many() {
                 name: "all purpose flour".to_string(),
                 amounts: vec![Measure::parse_new("cups", 12.0)],
                 modifier: Some("lightly sifted".to_string()),
+                ..Default::default()
             },
         ),
         (
@@ -19,6 +20,7 @@ fn test_many() {
                     Measure::parse_new("grams", 200.0),
                 ],
                 modifier: Some("cut into pieces".to_string()),
+                ..Default::default()
             },
         ),
         (
@@ -27,6 +29,7 @@ fn test_many() {
                 name: "vegetable stock".to_string(),
                 amounts: vec![Measure::parse_new("cups", 6.0)],
                 modifier: Some("more if needed".to_string()),
+                ..Default::default()
             },
         ),
         (
@@ -35,6 +38,7 @@ fn test_many() {
                 name: "crème fraîche".to_string(),
                 amounts: vec![Measure::parse_new("cup", 0.25)],
                 modifier: None,
+                ..Default::default()
             },
         ),
         (
@@ -46,6 +50,7 @@ fn test_many() {
                     Measure::parse_new("ml", 167.0),
                 ],
                 modifier: None,
+                ..Default::default()
             },
         ),
         (
@@ -54,6 +59,7 @@ fn test_many() {
                 name: "black pepper".to_string(),
                 amounts: vec![Measure::parse_new("tsp", 1.0)],
                 modifier: Some("freshly ground".to_string()),
+                ..Default::default()
             },
         ),
         (
@@ -62,6 +68,7 @@ fn test_many() {
                 name: "pepper".to_string(),
                 amounts: vec![Measure::parse_new("tsp", 1.0)],
                 modifier: Some("chopped".to_string()),
+                ..Default::default()
             },
         ),
     ];
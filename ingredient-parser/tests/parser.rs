@@ -1,4 +1,6 @@
-use ingredient::{self, ingredient::Ingredient, unit::Measure, IngredientParser};
+use ingredient::{
+    self, ingredient::Ingredient, unit::Measure, Flexibility, IngredientParser, Multiplier,
+};
 macro_rules! test_parse_ingredient {
     ($test_name:ident, $input:expr, $expected_output:expr) => {
         #[test]
@@ -26,7 +28,19 @@ test_parsing_equals!(test_amount_range, "1/2-1 cup", "1/2 - 1 cup");
 test_parsing_equals!(test_amount_range_vfrac, "2¼-2.5 cups", "2 ¼ - 2.5 cups");
 test_parsing_equals!(test_amount_range_g, "78g to 104g", "78g - 104g");
 test_parsing_equals!(test_unitless, "1 cinnamon stick", "1 whole cinnamon stick");
-test_parsing_equals!(multiply, "2 x 200g flour", "400g flour");
+test_parse_ingredient!(
+    multiply,
+    "2 x 200g flour",
+    Ingredient {
+        name: "flour".to_string(),
+        amounts: vec![Measure::parse_new("g", 400.0)],
+        multiplier: Some(Multiplier {
+            count: 2.0,
+            each: Measure::parse_new("g", 200.0)
+        }),
+        ..Default::default()
+    }
+);
 
 test_parse_ingredient!(
     ingredient_parse_no_amounts,
@@ -35,6 +49,7 @@ test_parse_ingredient!(
         name: "egg".to_string(),
         amounts: vec![],
         modifier: None,
+        ..Default::default()
     }
 );
 test_parse_ingredient!(
@@ -44,6 +59,7 @@ test_parse_ingredient!(
         name: "egg".to_string(),
         amounts: vec![Measure::parse_new("whole", 1.0)],
         modifier: None,
+        ..Default::default()
     }
 );
 test_parse_ingredient!(
@@ -53,6 +69,7 @@ test_parse_ingredient!(
         name: "cinnamon stick".to_string(),
         amounts: vec![Measure::parse_new("whole", 1.0)],
         modifier: Some("crushed".to_string()),
+        ..Default::default()
     }
 );
 test_parse_ingredient!(
@@ -61,7 +78,8 @@ test_parse_ingredient!(
     Ingredient {
         name: "olive oil".to_string(),
         amounts: vec![Measure::parse_new("teaspoon", 4.0),],
-        modifier: None
+        modifier: None,
+        ..Default::default()
     }
 );
 test_parse_ingredient!(
@@ -71,6 +89,7 @@ test_parse_ingredient!(
         name: "all purpose flour".to_string(),
         amounts: vec![Measure::parse_new("cups", 12.0)],
         modifier: Some("lightly sifted".to_string()),
+        ..Default::default()
     }
 );
 
@@ -84,6 +103,7 @@ test_parse_ingredient!(
             Measure::parse_new("grams", 155.5),
         ],
         modifier: None,
+        ..Default::default()
     }
 );
 
@@ -97,7 +117,8 @@ test_parse_ingredient!(
             Measure::parse_new("packet", 1.0),
             Measure::parse_new("teaspoons", 2.0),
         ],
-        modifier: None
+        modifier: None,
+        ..Default::default()
     }
 );
 test_parse_ingredient!(
@@ -110,7 +131,8 @@ test_parse_ingredient!(
             Measure::parse_new("sticks", 1.5),
             Measure::parse_new("g", 168.75),
         ],
-        modifier: None
+        modifier: None,
+        ..Default::default()
     }
 );
 test_parse_ingredient!(
@@ -123,7 +145,8 @@ test_parse_ingredient!(
             Measure::parse_new("sticks", 2.0),
             Measure::parse_new("g", 227.0),
         ],
-        modifier: Some("room temperature".to_string())
+        modifier: Some("room temperature".to_string()),
+        ..Default::default()
     }
 );
 
@@ -133,7 +156,8 @@ test_parse_ingredient!(
     Ingredient {
         name: "nutmeg".to_string(),
         amounts: vec![Measure::parse_new("pinch", 1.0),],
-        modifier: None
+        modifier: None,
+        ..Default::default()
     }
 );
 
@@ -149,7 +173,8 @@ test_parse_ingredient!(
     Ingredient {
         name: "garlic".to_string(),
         amounts: vec![Measure::parse_new("clove", 1.0),],
-        modifier: Some("grated".to_string())
+        modifier: Some("grated".to_string()),
+        ..Default::default()
     }
 );
 // todo: doesn't work
@@ -170,6 +195,7 @@ fn test_no_ingredient_amounts() {
             name: "apples".to_string(),
             amounts: vec![],
             modifier: None,
+            ..Default::default()
         }
         .to_string(),
         "n/a apples"
@@ -203,7 +229,8 @@ fn test_weird_chars() {
                         Measure::parse_new("cups", 2.0),
                         Measure::parse_new("grams", 240.0)
                     ],
-                    modifier: Some("sifted".to_string())
+                    modifier: Some("sifted".to_string()),
+                    ..Default::default()
                 }
             ))
         );
@@ -218,7 +245,8 @@ fn test_parse_ing_upepr_range() {
             Ingredient {
                 name: "cornmeal".to_string(),
                 amounts: vec![Measure::parse_new_with_upper("g", 78.0, 104.0),],
-                modifier: None
+                modifier: None,
+                ..Default::default()
             }
         ))
     );
@@ -240,8 +268,464 @@ fn test_unit_period_mixed_case() {
             Ingredient {
                 name: "garlic".to_string(),
                 amounts: vec![Measure::parse_new("cloves", 12.0),],
-                modifier: Some("peeled".to_string())
+                modifier: Some("peeled".to_string()),
+                ..Default::default()
             }
         ))
     );
 }
+#[test]
+fn test_custom_vocabulary() {
+    let ip = IngredientParser::new(false)
+        .with_units(["knob"])
+        .with_adjectives(["softened"])
+        .with_number_words([("dozen", 12.0)]);
+    assert_eq!(
+        ip.clone().parse_ingredient("1 knob softened butter"),
+        Ok((
+            "",
+            Ingredient {
+                name: "butter".to_string(),
+                amounts: vec![Measure::parse_new("knob", 1.0)],
+                modifier: Some("softened".to_string()),
+                ..Default::default()
+            }
+        ))
+    );
+    assert_eq!(
+        ip.parse_ingredient("dozen eggs"),
+        Ok((
+            "",
+            Ingredient {
+                name: "eggs".to_string(),
+                amounts: vec![Measure::parse_new("whole", 12.0)],
+                modifier: None,
+                ..Default::default()
+            }
+        ))
+    );
+}
+test_parse_ingredient!(
+    test_inverted_zest,
+    "zest of 1 lemon",
+    Ingredient {
+        name: "lemon zest".to_string(),
+        amounts: vec![Measure::parse_new("lemon", 1.0)],
+        modifier: None,
+        ..Default::default()
+    }
+);
+test_parse_ingredient!(
+    test_inverted_juice,
+    "Juice of 2 limes, strained",
+    Ingredient {
+        name: "lime juice".to_string(),
+        amounts: vec![Measure::parse_new("lime", 2.0)],
+        modifier: Some("strained".to_string()),
+        ..Default::default()
+    }
+);
+#[test]
+fn test_inverted() {
+    let i = ingredient::from_str("zest of 1 large lemon, finely grated");
+    assert_eq!(i.name, "lemon zest");
+    assert_eq!(i.amounts, vec![Measure::parse_new("lemon", 1.0)]);
+    assert_eq!(i.modifier.as_deref(), Some("large, finely grated"));
+    let i = ingredient::from_str("juice of 2 Meyer lemons");
+    assert_eq!(i.name, "meyer lemon juice");
+    assert_eq!(i.amounts, vec![Measure::parse_new("lemon", 2.0)]);
+    assert_eq!(ingredient::from_str("Juice Of 1 lime").name, "lime juice");
+
+    for line in [
+        "zest of 1 lemon",
+        "juice of 2 meyer lemons",
+        "zest and juice of 1 orange, to serve",
+        "juice of 1 - 2 limes",
+    ] {
+        assert_eq!(ingredient::from_str(line).to_string(), line);
+    }
+    assert_eq!(
+        ingredient::from_str("Zest of 1 large lemon").to_string(),
+        "zest of 1 lemon, large"
+    );
+}
+#[test]
+fn test_count_units() {
+    let ing = ingredient::from_str("3 sprigs thyme");
+    assert_eq!(ing.name, "thyme");
+    assert_eq!(ing.amounts[0].as_count(), Some((3.0, "sprig".to_string())));
+    let ing = ingredient::from_str("1 stick butter");
+    assert_eq!(ing.amounts[0].as_count(), Some((1.0, "stick".to_string())));
+}
+test_parse_ingredient!(
+    test_alternatives,
+    "1 cup heavy cream or crème fraîche",
+    Ingredient {
+        name: "heavy cream".to_string(),
+        amounts: vec![Measure::parse_new("cup", 1.0)],
+        modifier: None,
+        alternatives: vec!["crème fraîche".to_string()],
+        ..Default::default()
+    }
+);
+#[test]
+fn test_alternatives_display() {
+    let i = ingredient::from_str("2 tbsp butter, or margarine");
+    assert_eq!(i.name, "butter");
+    assert_eq!(i.alternatives, vec!["margarine".to_string()]);
+    assert_eq!(i.modifier, None);
+    assert_eq!(i.to_string(), "2 tbsp butter or margarine");
+}
+test_parse_ingredient!(
+    test_divided,
+    "1 cup sugar, divided",
+    Ingredient {
+        name: "sugar".to_string(),
+        amounts: vec![Measure::parse_new("cup", 1.0)],
+        modifier: None,
+        divided: true,
+        ..Default::default()
+    }
+);
+test_parse_ingredient!(
+    test_extra_for,
+    "2 tbsp olive oil, divided, plus more for greasing",
+    Ingredient {
+        name: "olive oil".to_string(),
+        amounts: vec![Measure::parse_new("tbsp", 2.0)],
+        modifier: None,
+        divided: true,
+        extra_for: Some("greasing".to_string()),
+        ..Default::default()
+    }
+);
+#[test]
+fn test_extra_for_in_name() {
+    let i = ingredient::from_str("1/2 cup flour plus more for dusting");
+    assert_eq!(i.name, "flour");
+    assert_eq!(i.extra_for, Some("dusting".to_string()));
+    assert_eq!(i.to_string(), "0.5 cup flour, plus more for dusting");
+}
+#[test]
+fn test_normalized_name() {
+    let i = ingredient::from_str("1 tsp Diamond Crystal kosher salt");
+    assert_eq!(i.normalized_name(), "kosher salt");
+    assert_eq!(i.name_descriptors(), vec!["diamond crystal"]);
+    let i = ingredient::from_str("1 cup ripe yellow mango, diced");
+    assert_eq!(i.normalized_name(), "mango");
+    assert_eq!(i.name_descriptors(), vec!["ripe", "yellow"]);
+}
+#[test]
+fn test_temperature() {
+    use ingredient::temperature::{Temperature, TemperatureScale};
+    let i = ingredient::from_str("1 cup water, 43°C");
+    assert_eq!(
+        i.temperature,
+        Some(Temperature {
+            value: 43.0,
+            scale: TemperatureScale::Celsius
+        })
+    );
+    assert_eq!(i.modifier, Some("43°C".to_string()));
+    let i = ingredient::from_str("4 tbsp butter, melted and cooled to 110°F");
+    assert_eq!(i.temperature.unwrap().value, 110.0);
+    assert_eq!(ingredient::from_str("1 cup flour").temperature, None);
+    // cups, not celsius
+    assert_eq!(
+        ingredient::from_str("1 C flour, about 2 C").temperature,
+        None
+    );
+    let i = ingredient::from_str("1 cup warm water (110°F)");
+    assert_eq!(i.amounts, vec![Measure::parse_new("cup", 1.0)]);
+    assert_eq!(
+        i.temperature,
+        Some(Temperature {
+            value: 110.0,
+            scale: TemperatureScale::Fahrenheit
+        })
+    );
+    assert_eq!(i.to_string(), "1 cup warm water (110°F)");
+}
+#[test]
+fn test_bakers_percentage() {
+    let i = ingredient::from_str("500 g bread flour (100%)");
+    assert_eq!(i.name, "bread flour");
+    assert_eq!(i.percentage, Some(100.0));
+    assert_eq!(i.modifier, None);
+    let i = ingredient::from_str("310 g water, lukewarm (62% hydration)");
+    assert_eq!(i.percentage, Some(62.0));
+    assert_eq!(i.modifier, Some("lukewarm".to_string()));
+}
+#[test]
+fn test_unicode_spaces_and_dashes() {
+    assert_eq!(
+        ingredient::from_str("1\u{a0}–\u{a0}2\u{a0}cups cre\u{300}me frai\u{302}che"),
+        Ingredient {
+            name: "crème fraîche".to_string(),
+            amounts: vec![Measure::parse_new_with_upper("cups", 1.0, 2.0)],
+            modifier: None,
+            ..Default::default()
+        }
+    );
+}
+test_parse_ingredient!(
+    test_each,
+    "2 each eggs",
+    Ingredient {
+        name: "eggs".to_string(),
+        amounts: vec![Measure::parse_new("each", 2.0)],
+        modifier: None,
+        ..Default::default()
+    }
+);
+test_parse_ingredient!(
+    test_ea_alias,
+    "12 ea. tortillas",
+    Ingredient {
+        name: "tortillas".to_string(),
+        amounts: vec![Measure::parse_new("each", 12.0)],
+        modifier: None,
+        ..Default::default()
+    }
+);
+#[test]
+fn test_inferred_unit() {
+    let i = ingredient::from_str("1 egg");
+    assert_eq!(i.amounts, vec![Measure::parse_new("whole", 1.0)]);
+    assert!(i.amounts[0].inferred_unit());
+    assert!(!ingredient::from_str("1 whole egg").amounts[0].inferred_unit());
+    let i = IngredientParser::new(false)
+        .without_bare_count_unit()
+        .from_str("1 egg");
+    assert!(!i.amounts[0].inferred_unit());
+    assert_eq!(i.to_string(), "1 egg");
+}
+test_parse_ingredient!(
+    test_half_a_cup,
+    "half a cup of milk",
+    Ingredient {
+        name: "milk".to_string(),
+        amounts: vec![Measure::parse_new("cup", 0.5)],
+        modifier: None,
+        ..Default::default()
+    }
+);
+test_parsing_equals!(
+    test_a_quarter_of_a_cup,
+    "a quarter of a cup sugar",
+    "0.25 cup sugar"
+);
+test_parsing_equals!(test_an_article, "an onion", "1 onion");
+
+test_parsing_equals!(no_space_cup, "1cup sugar", "1 cup sugar");
+test_parsing_equals!(no_space_tbsp_period, "2tbsp. butter", "2 tbsp butter");
+test_parsing_equals!(no_space_fraction, "1/2tsp salt", "1/2 tsp salt");
+test_parsing_equals!(no_space_range, "1-2tbsp honey", "1-2 tbsp honey");
+test_parsing_equals!(no_space_fl_oz, "8fl oz milk", "8 fl. oz. milk");
+
+#[test]
+fn test_no_space_units() {
+    let ip = IngredientParser::new(false);
+    assert_eq!(
+        ip.clone().from_str("8floz milk").amounts,
+        vec![Measure::parse_new("fl oz", 8.0)]
+    );
+    // not a unit unless it's the whole word
+    assert_eq!(ip.from_str("1cupsugar").name, "cupsugar");
+}
+
+test_parsing_equals!(
+    range_vfrac_both,
+    "¼–½ teaspoon salt",
+    "0.25-0.5 teaspoon salt"
+);
+test_parsing_equals!(range_vfrac_em_dash, "½—1 cup milk", "0.5-1 cup milk");
+test_parsing_equals!(
+    range_mixed_vfrac,
+    "1⅓-1½ cups flour",
+    "1 1/3 - 1 1/2 cups flour"
+);
+test_parsing_equals!(
+    range_mixed_vfrac_spaced,
+    "1 ⅓ - 1 ½ cups flour",
+    "1⅓-1½ cups flour"
+);
+test_parsing_equals!(range_eighths, "⅛–⅜ tsp salt", "0.125-0.375 tsp salt");
+test_parsing_equals!(
+    range_vfrac_to,
+    "1½ to 2⅔ cups milk",
+    "1 1/2 to 2 2/3 cups milk"
+);
+
+#[test]
+fn test_vulgar_fraction_range_amounts() {
+    let ip = IngredientParser::new(false);
+    assert_eq!(
+        ip.must_parse_amount("¼ – ½ tsp"),
+        vec![Measure::parse_new_with_upper("tsp", 0.25, 0.5)]
+    );
+    assert_eq!(
+        ip.must_parse_amount("1⅕-1⅘ cups"),
+        vec![Measure::parse_new_with_upper("cups", 1.2, 1.8)]
+    );
+}
+
+#[test]
+fn test_no_float_syntax() {
+    let ip = IngredientParser::new(false);
+    // nom's `double` would take these as an exponent, infinity and not-a-number
+    assert_eq!(
+        ip.clone().from_str("3e eggs").amounts,
+        vec![Measure::parse_new("whole", 3.0)]
+    );
+    assert_eq!(ip.clone().from_str("infinity cups").name, "infinity cups");
+    assert_eq!(ip.clone().from_str("nan").amounts, vec![]);
+    // nor a fraction over zero
+    for line in ["1/0 cup flour", "0/0 cup flour"] {
+        let i = ip.clone().from_str(line);
+        assert!(
+            i.amounts.iter().all(|a| a.values().0.is_finite()),
+            "{}",
+            line
+        );
+    }
+}
+
+#[test]
+fn test_informal_units() {
+    let ip = IngredientParser::new(false);
+    let i = ip.clone().from_str("a handful of spinach");
+    assert_eq!(i.name, "spinach");
+    assert_eq!(i.amounts, vec![Measure::parse_new("handful", 1.0)]);
+    let i = ip.clone().from_str("knob of butter");
+    assert_eq!(i.name, "butter");
+    assert_eq!(
+        i.amounts[0].approximate_metric(),
+        Some(Measure::parse_new("g", 15.0))
+    );
+    let i = ip.from_str("2 bunches cilantro");
+    assert_eq!(i.name, "cilantro");
+    assert_eq!(i.amounts, vec![Measure::parse_new("bunch", 2.0)]);
+}
+
+test_parse_ingredient!(
+    test_purpose,
+    "lime wedges, for serving",
+    Ingredient {
+        name: "lime wedges".to_string(),
+        purpose: Some("for serving".to_string()),
+        ..Default::default()
+    }
+);
+
+#[test]
+fn test_purpose_variants() {
+    let ip = IngredientParser::new(false);
+    let i = ip.clone().from_str("1 tbsp butter, softened, for the pan");
+    assert_eq!(i.modifier, Some("softened".to_string()));
+    assert_eq!(i.purpose, Some("for the pan".to_string()));
+    assert_eq!(i.to_string(), "1 tbsp butter, softened, for the pan");
+    let i = ip.clone().from_str("flour for dusting");
+    assert_eq!(
+        (i.name.as_str(), i.purpose),
+        ("flour", Some("for dusting".to_string()))
+    );
+    let i = ip.clone().from_str("cilantro (to serve)");
+    assert_eq!(i.purpose, Some("to serve".to_string()));
+    // only known purposes are pulled out of the name
+    assert_eq!(ip.from_str("2 cups stock for soup").name, "stock for soup");
+}
+
+test_parsing_equals!(
+    equivalence_equals,
+    "1 cup = 120 g flour",
+    "1 cup / 120 g flour"
+);
+test_parsing_equals!(
+    equivalence_equals_tight,
+    "1 cup=120g flour",
+    "1 cup / 120 g flour"
+);
+test_parsing_equals!(
+    equivalence_pipe,
+    "1 cup | 120 g flour",
+    "1 cup / 120 g flour"
+);
+test_parsing_equals!(
+    equivalence_double_slash,
+    "1 cup // 120 g flour",
+    "1 cup / 120 g flour"
+);
+test_parsing_equals!(
+    equivalence_or,
+    "1 cup or 240 ml milk",
+    "1 cup / 240 ml milk"
+);
+test_parsing_equals!(
+    equivalence_or_parens,
+    "1 cup (or 240 ml) milk",
+    "1 cup / 240 ml milk"
+);
+test_parsing_equals!(
+    range_units_twice_spaced,
+    "1 cup to 2 cups milk",
+    "1-2 cups milk"
+);
+test_parsing_equals!(range_or_still_range, "2 or 3 eggs", "2-3 eggs");
+
+test_parse_ingredient!(
+    test_or_to_taste,
+    "1 teaspoon chili flakes, or to taste",
+    Ingredient {
+        name: "chili flakes".to_string(),
+        amounts: vec![Measure::parse_new("tsp", 1.0)],
+        flexibility: Some(Flexibility::ToTaste),
+        ..Default::default()
+    }
+);
+
+#[test]
+fn test_flexibility() {
+    let ip = IngredientParser::new(false);
+    let i = ip.clone().from_str("2 tbsp honey, or more as needed");
+    assert_eq!(i.flexibility, Some(Flexibility::OrMore));
+    assert_eq!(i.modifier, None);
+    assert_eq!(i.to_string(), "2 tbsp honey, or more");
+    let i = ip.clone().from_str("pepper, freshly ground, to taste");
+    assert_eq!(i.modifier, Some("freshly ground".to_string()));
+    assert_eq!(i.flexibility, Some(Flexibility::ToTaste));
+    assert_eq!(
+        ip.clone().from_str("salt to taste").flexibility,
+        Some(Flexibility::ToTaste)
+    );
+    assert_eq!(
+        ip.clone().from_str("1 cup water, as needed").flexibility,
+        Some(Flexibility::AsNeeded)
+    );
+    // longer phrases aren't alternatives
+    for (input, modifier) in [
+        ("1 cup milk, or more if needed", "or more if needed"),
+        ("1 tsp salt, or less", "or less"),
+    ] {
+        let i = ip.clone().from_str(input);
+        assert_eq!(i.alternatives, Vec::<String>::new(), "{input}");
+        assert_eq!(i.modifier, Some(modifier.to_string()), "{input}");
+        assert_eq!(i.to_string(), input);
+    }
+}
+
+test_parse_ingredient!(
+    test_fraction_of_item,
+    "½ of a lemon",
+    Ingredient {
+        name: "lemon".to_string(),
+        amounts: vec![Measure::parse_new("whole", 0.5)],
+        ..Default::default()
+    }
+);
+test_parsing_equals!(
+    fraction_of_an,
+    "1/2 of an avocado, sliced",
+    "½ avocado, sliced"
+);
+test_parsing_equals!(fraction_word_of_an, "a quarter of an onion", "¼ onion");
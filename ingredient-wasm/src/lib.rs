@@ -1,6 +1,9 @@
 #![allow(deprecated)]
 
-use ingredient::{self, rich_text::RichParser, unit::Measure, IngredientParser};
+use ingredient::{
+    self, display::DisplayOptions, rich_text::RichParser, unit::Measure, Ingredient,
+    IngredientParser,
+};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(start)]
@@ -27,6 +30,13 @@ pub fn parse_ingredient(input: &str) -> IIngredient {
     JsValue::from_serde(&si).unwrap().into()
 }
 #[wasm_bindgen]
+pub fn parse_amount(input: &str) -> Result<IMeasures, JsValue> {
+    match IngredientParser::new(false).parse_amount(input) {
+        Ok(a) => Ok(JsValue::from_serde(&a).unwrap().into()),
+        Err(e) => Err(JsValue::from_str(&e.to_string())),
+    }
+}
+#[wasm_bindgen]
 pub fn parse_rich_text(r: String, ings: &JsValue) -> Result<RichItems, JsValue> {
     let ings2: Vec<String> = ings.into_serde().unwrap();
     let rtp = RichParser {
@@ -50,6 +60,21 @@ pub fn format_amount(amount: &IMeasure) -> String {
     }
 }
 
+#[wasm_bindgen]
+pub fn format_ingredient(ingredient: &IIngredient, options: &IDisplayOptions) -> String {
+    let options: DisplayOptions = options
+        .into_serde::<Option<DisplayOptions>>()
+        .unwrap_or_default()
+        .unwrap_or_default();
+    let i: Result<Ingredient, _> = ingredient.into_serde();
+    match i {
+        Ok(i) => i.format(&options),
+        Err(e) => {
+            format!("failed to format {ingredient:#?}: {e:?}")
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub fn scrape(body: String, url: String) -> Result<IScrapedRecipe, JsValue> {
     match recipe_scraper::scrape(body.as_str(), &url) {
@@ -68,6 +93,8 @@ extern "C" {
     pub type IMeasure;
     #[wasm_bindgen(typescript_type = "Measure[]")]
     pub type IMeasures;
+    #[wasm_bindgen(typescript_type = "DisplayOptions | undefined")]
+    pub type IDisplayOptions;
     #[wasm_bindgen(typescript_type = "RichItem[]")]
     pub type RichItems;
     #[wasm_bindgen(typescript_type = "ScrapedRecipe")]
@@ -78,21 +105,72 @@ extern "C" {
 const ITEXT_STYLE: &'static str = r#"
 interface Ingredient {
     amounts: Measure[];
+    alternatives?: string[];
+    divided?: boolean;
+    extra_for?: string;
+    footnote?: string;
+    purpose?: string;
+    flexibility?: "ToTaste" | "OrMore" | "AsNeeded";
     modifier?: string;
+    multiplier?: { count: number; each: Measure };
     name: string;
+    note?: string;
+    percentage?: number;
+    percentage_label?: string;
+    temperature?: { value: number; scale: "Fahrenheit" | "Celsius" };
 }
 interface Measure {
   unit: string;
   value: number;
   upper_value?: number;
+  inferred_unit?: boolean;
+  bound?: "AtLeast" | "AtMost";
+  source?: "Inline" | "Parenthetical" | "Derived";
+}
+interface DisplayOptions {
+  long_units?: boolean;
+  precision?: number;
+  significant_figures?: number;
+  pluralize?: boolean;
+  all_amounts?: boolean;
+  derived_amounts?: boolean;
+  range_separator?: string;
+  locale?: "En" | "De" | "Fr" | "Es";
 }
 
 interface ScrapedRecipe {
     image: string;
+    description?: string;
+    notes?: string;
+    language?: string;
     ingredients: string[];
     instructions: string[];
+    steps: { text: string; name?: string; image?: string; url?: string }[];
+    ingredient_groups?: [string | null, string[]][];
+    instruction_groups?: [string | null, string[]][];
     name: string;
+    yield?: {
+      raw: string;
+      parsed?: { value: number; upper?: number; unit: string; raw: string };
+    };
+    prep_time?: { secs: number; nanos: number };
+    cook_time?: { secs: number; nanos: number };
+    total_time?: { secs: number; nanos: number };
+    author?: string;
+    rating?: { value: number; count?: number };
+    cuisine: string[];
+    category: string[];
+    keywords: string[];
+    equipment: string[];
+    video?: {
+      name?: string;
+      content_url?: string;
+      embed_url?: string;
+      thumbnail?: string;
+    };
+    raw?: Record<string, unknown>;
     url: string;
+    canonical_url?: string;
 }
 
 export type RichItem =
@@ -0,0 +1,95 @@
+//! How long scraping the largest test pages takes, and how much memory it needs at most,
+//! e.g. `cargo bench -p recipe-scraper`.
+//! `parse and clone` is what every scrape cost before the parsed page stopped being cloned
+//! to look for its ld+json.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use recipe_scraper::{scrape, Html};
+
+const PAGES: &[(&str, &str)] = &[
+    (
+        "smittenkitchen",
+        include_str!("../test_data/smittenkitchen_crispy-tofu-pad-thai.html"),
+    ),
+    (
+        "seriouseats",
+        include_str!("../test_data/seriouseats_grilled_naan.html"),
+    ),
+    (
+        "nytimes",
+        include_str!("../test_data/nytimes_toll-house-chocolate-chip-cookies.html"),
+    ),
+];
+
+// counts what's allocated, for the peak memory of a scrape
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let now = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(now, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+// the most memory `f` had allocated at once
+fn peak_memory(f: impl FnOnce()) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    f();
+    PEAK.load(Ordering::Relaxed) - before
+}
+
+fn bench_scrape(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scrape");
+    for (name, page) in PAGES {
+        let url = format!("https://{name}.example.com");
+        let peak = peak_memory(|| {
+            scrape(page, &url).unwrap();
+        });
+        println!(
+            "{name}: {} KiB page, {} KiB peak while scraping",
+            page.len() / 1024,
+            peak / 1024
+        );
+        group.bench_function(*name, |b| b.iter(|| scrape(black_box(page), &url)));
+    }
+    group.finish();
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let (name, page) = PAGES[0];
+    let peak = peak_memory(|| {
+        let dom = Html::parse_document(page);
+        black_box(dom.clone());
+    });
+    println!("{name}: {} KiB peak to parse and clone", peak / 1024);
+    let mut group = c.benchmark_group(name);
+    group.bench_function("parse", |b| {
+        b.iter(|| Html::parse_document(black_box(page)))
+    });
+    group.bench_function("parse and clone", |b| {
+        b.iter(|| {
+            let dom = Html::parse_document(black_box(page));
+            black_box(dom.clone())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_scrape, bench_clone);
+criterion_main!(benches);
@@ -0,0 +1,295 @@
+//! Scraping saved pages and crawls, without any network layer.
+use std::path::Path;
+#[cfg(feature = "warc")]
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+};
+
+use crate::{ScrapeError, ScrapedRecipe, Scraper};
+
+impl Scraper {
+    /// Scrapes a page saved to `path`. Its url is the one the browser or SingleFile noted it was
+    /// saved from, or else the `file://` url of `path`.
+    pub fn scrape_file(&self, path: impl AsRef<Path>) -> Result<ScrapedRecipe, ScrapeError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| io_error(path, e))?;
+        let body = String::from_utf8_lossy(&bytes);
+        let url = match saved_from(&body) {
+            Some(url) => url,
+            None => file_url(path),
+        };
+        self.scrape(&body, &url)
+    }
+
+    /// Scrapes each html response in the WARC archive at `path` (which can be gzipped),
+    /// along with the url it was crawled from. Failing to read the archive part way ends it with an error.
+    #[cfg(feature = "warc")]
+    pub fn scrape_warc(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<impl Iterator<Item = (String, Result<ScrapedRecipe, ScrapeError>)> + '_, ScrapeError>
+    {
+        Ok(WarcPages::open(path)?.map(|page| match page {
+            Ok((url, body)) => {
+                let r = self.scrape(&body, &url);
+                (url, r)
+            }
+            Err(e) => (String::new(), Err(e)),
+        }))
+    }
+}
+
+// the url in the comment browsers (`saved from url=(0042)...`) or SingleFile (`url: ...`) leave
+fn saved_from(body: &str) -> Option<String> {
+    let head = &body[..body.len().min(4096)];
+    let url = match head.split_once("saved from url=(") {
+        Some((_, rest)) => rest.split_once(')')?.1,
+        None => {
+            head.split_once("Page saved with SingleFile")?
+                .1
+                .split_once("url:")?
+                .1
+        }
+    };
+    let url = url
+        .split(|c: char| c.is_whitespace())
+        .find(|s| !s.is_empty())?;
+    let url = url.trim_end_matches("-->");
+    (url.starts_with("http://") || url.starts_with("https://")).then(|| url.to_string())
+}
+
+fn file_url(path: &Path) -> String {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    format!("file://{}", path.display())
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> ScrapeError {
+    ScrapeError::Io(format!("{}: {e}", path.display()))
+}
+
+/// The html pages of a [WARC](https://iipc.github.io/warc-specifications/) archive,
+/// as `(url, body)`, read one record at a time.
+#[cfg(feature = "warc")]
+pub struct WarcPages<R> {
+    reader: R,
+    // after an error, as where the next record starts isn't known
+    done: bool,
+}
+
+#[cfg(feature = "warc")]
+impl WarcPages<Box<dyn BufRead>> {
+    /// Reads the archive at `path`, gunzipping it if it's a `.warc.gz`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ScrapeError> {
+        let path = path.as_ref();
+        let mut file = BufReader::new(File::open(path).map_err(|e| io_error(path, e))?);
+        let gzipped = file
+            .fill_buf()
+            .map_err(|e| io_error(path, e))?
+            .starts_with(&[0x1f, 0x8b]);
+        let reader: Box<dyn BufRead> = match gzipped {
+            // each record is its own gzip member
+            true => Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(file))),
+            false => Box::new(file),
+        };
+        Ok(WarcPages::new(reader))
+    }
+}
+
+#[cfg(feature = "warc")]
+impl<R: BufRead> WarcPages<R> {
+    pub fn new(reader: R) -> Self {
+        WarcPages {
+            reader,
+            done: false,
+        }
+    }
+
+    // the next record's headers and block, `None` at the end
+    #[allow(clippy::type_complexity)]
+    fn record(&mut self) -> std::io::Result<Option<(Vec<(String, String)>, Vec<u8>)>> {
+        let mut line = String::new();
+        // records are separated by blank lines
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            if !line.trim().is_empty() {
+                break;
+            }
+        }
+        if !line.starts_with("WARC/") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("expected a WARC record, found `{}`", line.trim()),
+            ));
+        }
+        let mut headers = vec![];
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+        let length = header(&headers, "content-length")
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(0);
+        let mut block = vec![0; length];
+        self.reader.read_exact(&mut block)?;
+        Ok(Some((headers, block)))
+    }
+}
+
+#[cfg(feature = "warc")]
+impl<R: BufRead> Iterator for WarcPages<R> {
+    type Item = Result<(String, String), ScrapeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (headers, block) = match self.record() {
+                Ok(Some(record)) => record,
+                Ok(None) => return None,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(ScrapeError::Io(format!("warc: {e}"))));
+                }
+            };
+            if header(&headers, "warc-type") != Some("response") {
+                continue;
+            }
+            let url = match header(&headers, "warc-target-uri") {
+                // WARC 1.0 has them in angle brackets
+                Some(url) => url
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string(),
+                None => continue,
+            };
+            if let Some(body) = html_body(&block) {
+                return Some(Ok((url, body)));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "warc")]
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+// the body of a successful http response with html in it
+#[cfg(feature = "warc")]
+fn html_body(response: &[u8]) -> Option<String> {
+    let split = response.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let mut body = response[split + 4..].to_vec();
+    let mut lines = head.lines();
+    let status: u16 = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(n, v)| (n.trim().to_lowercase(), v.trim().to_lowercase()))
+        .collect();
+    if !(200..300).contains(&status)
+        || !header(&headers, "content-type").is_some_and(|t| t.contains("html"))
+    {
+        return None;
+    }
+    // crawlers keep the response as it was sent
+    if header(&headers, "transfer-encoding").is_some_and(|t| t.contains("chunked")) {
+        body = dechunk(&body)?;
+    }
+    if header(&headers, "content-encoding").is_some_and(|e| e.contains("gzip")) {
+        let mut decoded = vec![];
+        flate2::read::GzDecoder::new(body.as_slice())
+            .read_to_end(&mut decoded)
+            .ok()?;
+        body = decoded;
+    }
+    Some(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(feature = "warc")]
+fn dechunk(mut chunked: &[u8]) -> Option<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let end = chunked.windows(2).position(|w| w == b"\r\n")?;
+        let size = std::str::from_utf8(&chunked[..end]).ok()?;
+        // chunk extensions follow a `;`
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        if size == 0 {
+            return Some(body);
+        }
+        let chunk = chunked.get(end + 2..end + 2 + size)?;
+        body.extend_from_slice(chunk);
+        chunked = chunked.get(end + 4 + size..)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_from() {
+        assert_eq!(
+            saved_from(
+                "<!DOCTYPE html>\n<!-- saved from url=(0033)https://example.com/toast -->\n<html>"
+            ),
+            Some("https://example.com/toast".to_string())
+        );
+        assert_eq!(
+            saved_from("<!DOCTYPE html> <html><!--\n Page saved with SingleFile \n url: https://example.com/toast \n saved date: Mon Jan 01 2024\n-->"),
+            Some("https://example.com/toast".to_string())
+        );
+        assert_eq!(saved_from("<html></html>"), None);
+    }
+
+    #[cfg(feature = "warc")]
+    #[test]
+    fn test_warc_pages() {
+        let record = |kind: &str, url: &str, block: &str| {
+            format!(
+                "WARC/1.1\r\nWARC-Type: {kind}\r\nWARC-Target-URI: {url}\r\nContent-Length: {}\r\n\r\n{block}\r\n\r\n",
+                block.len()
+            )
+        };
+        let warc = [
+            record("warcinfo", "", "software: wget"),
+            record("request", "https://example.com/a", "GET /a HTTP/1.1\r\n\r\n"),
+            record(
+                "response",
+                "<https://example.com/a>",
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n<p>a\r\n4\r\n</p>\r\n0\r\n\r\n",
+            ),
+            record(
+                "response",
+                "https://example.com/a.jpg",
+                "HTTP/1.1 200 OK\r\nContent-Type: image/jpeg\r\n\r\njpeg",
+            ),
+            record(
+                "response",
+                "https://example.com/missing",
+                "HTTP/1.1 404 Not Found\r\nContent-Type: text/html\r\n\r\n<p>missing</p>",
+            ),
+        ]
+        .concat();
+        let pages: Vec<_> = WarcPages::new(warc.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            pages,
+            vec![("https://example.com/a".to_string(), "<p>a</p>".to_string())]
+        );
+        let mut not_warc = WarcPages::new("not a warc".as_bytes());
+        assert!(not_warc.next().unwrap().is_err());
+        assert!(not_warc.next().is_none());
+    }
+}
@@ -0,0 +1,41 @@
+//! Fetching without an async runtime, for CLI tools and scripts.
+use crate::{ScrapeError, ScrapedRecipe, Scraper};
+
+impl Scraper {
+    /// Fetches `url` and scrapes it, blocking the current thread.
+    /// Not for use inside an async runtime, see `recipe_scraper_fetcher` instead.
+    pub fn scrape_url_blocking(&self, url: &str) -> Result<ScrapedRecipe, ScrapeError> {
+        let body = fetch_html(url)?;
+        self.scrape(&body, url)
+    }
+}
+
+fn fetch_html(url: &str) -> Result<String, ScrapeError> {
+    let r = reqwest::blocking::Client::new()
+        .get(url)
+        .header("user-agent", "recipe")
+        .send()
+        .map_err(|e| ScrapeError::Http(e.to_string()))?;
+    if !r.status().is_success() {
+        return Err(ScrapeError::Status {
+            url: url.to_string(),
+            status: r.status().as_u16(),
+        });
+    }
+    r.text().map_err(|e| ScrapeError::Http(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrape_url_blocking_errors() {
+        assert!(matches!(
+            Scraper::default()
+                .scrape_url_blocking("https://doesnotresolve.com")
+                .unwrap_err(),
+            ScrapeError::Http(_)
+        ));
+    }
+}
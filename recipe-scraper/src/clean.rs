@@ -0,0 +1,149 @@
+//! Cleaning up the text of instructions, which sites often leave html, ads and links to other recipes in.
+use scraper::Html;
+
+use crate::ScrapedRecipe;
+
+// whole steps that aren't part of the recipe, compared lowercase and without punctuation
+const BOILERPLATE: &[&str] = &[
+    "advertisement",
+    "ad",
+    "sponsored",
+    "jump to recipe",
+    "print recipe",
+    "pin recipe",
+    "save recipe",
+    "watch the video",
+];
+// steps starting with these aren't either
+const BOILERPLATE_PREFIXES: &[&str] = &[
+    "related:",
+    "related recipe",
+    "you might also like",
+    "you may also like",
+    "did you make this recipe",
+    "if you make this recipe",
+];
+// where a link to another recipe starts, to the end of the step
+const TRAILERS: &[&str] = &[
+    "Related:",
+    "RELATED:",
+    "Read more:",
+    "READ MORE:",
+    "Get the recipe:",
+];
+
+/// `s` without html, ads or links to other recipes, on one line.
+/// It's empty when there's nothing of the recipe in it.
+pub(crate) fn clean_instruction(s: &str) -> String {
+    let mut text = s.to_string();
+    // twice, for sites that escape their html once too often (`&lt;a href=...&gt;`)
+    for _ in 0..2 {
+        if text.contains('<') || text.contains('&') {
+            text = html_text(&text);
+        }
+    }
+    let mut text = text
+        .split_whitespace()
+        .filter(|w| !matches!(*w, "Advertisement" | "ADVERTISEMENT"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    for trailer in TRAILERS {
+        if let Some(idx) = text.find(trailer) {
+            text.truncate(idx);
+        }
+    }
+    let text = text.trim();
+    let lower = text
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    let is_boilerplate = BOILERPLATE.contains(&lower.as_str())
+        || BOILERPLATE_PREFIXES
+            .iter()
+            .any(|p| text.to_lowercase().starts_with(p));
+    match is_boilerplate || lower.is_empty() {
+        true => String::new(),
+        false => text.to_string(),
+    }
+}
+
+// the text of an html fragment, with its entities decoded,
+// and a space where a tag breaks up the text
+fn html_text(s: &str) -> String {
+    let mut spaced = s.to_string();
+    for tag in ["<br", "<p", "</p", "<li", "</li", "<div", "</div"] {
+        spaced = spaced.replace(tag, &format!(" {tag}"));
+    }
+    Html::parse_fragment(&spaced)
+        .root_element()
+        .text()
+        .collect::<String>()
+}
+
+impl ScrapedRecipe {
+    // cleans each step, dropping the ones left empty
+    pub(crate) fn clean_instructions(&mut self) {
+        let clean = |steps: &mut Vec<String>| {
+            *steps = steps
+                .iter()
+                .map(|s| clean_instruction(s))
+                .filter(|s| !s.is_empty())
+                .collect();
+        };
+        clean(&mut self.instructions);
+        for step in self.steps.iter_mut() {
+            step.text = clean_instruction(&step.text);
+        }
+        self.steps.retain(|s| !s.text.is_empty());
+        for (_, steps) in self.instruction_groups.iter_mut() {
+            clean(steps);
+        }
+        self.instruction_groups
+            .retain(|(name, steps)| name.is_some() || !steps.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_instruction() {
+        assert_eq!(
+            clean_instruction("Mix the <b>flour</b> &amp; sugar.<br>Then  add\n eggs."),
+            "Mix the flour & sugar. Then add eggs."
+        );
+        assert_eq!(
+            clean_instruction("Heat to 180&deg;C &ndash; or 350&#176;F."),
+            "Heat to 180°C – or 350°F."
+        );
+        assert_eq!(
+            clean_instruction("Use a &lt;a href='/mixer'&gt;mixer&lt;/a&gt;."),
+            "Use a mixer."
+        );
+        assert_eq!(
+            clean_instruction("1 < 2 cups, whichever"),
+            "1 < 2 cups, whichever"
+        );
+        assert_eq!(
+            clean_instruction("Bake for 20 minutes. Advertisement Let cool."),
+            "Bake for 20 minutes. Let cool."
+        );
+        assert_eq!(
+            clean_instruction("Serve warm. Related: The Best Brownies"),
+            "Serve warm."
+        );
+        for junk in [
+            "ADVERTISEMENT",
+            " ",
+            "Jump to Recipe",
+            "Did you make this recipe? Tag us!",
+            "<p></p>",
+        ] {
+            assert_eq!(clean_instruction(junk), "", "{junk}");
+        }
+        assert_eq!(
+            clean_instruction("Add the ad hoc garnish."),
+            "Add the ad hoc garnish."
+        );
+    }
+}
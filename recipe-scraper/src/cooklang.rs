@@ -0,0 +1,224 @@
+//! Writing a [ScrapedRecipe] as a [Cooklang](https://cooklang.org) `.cook` file.
+use std::time::Duration;
+
+use ingredient::ingredient::Ingredient;
+
+use crate::ScrapedRecipe;
+
+impl ScrapedRecipe {
+    /// A `.cook` file of it: `>>` metadata, then a paragraph per step, with `== Section ==`s
+    /// when it has [ScrapedRecipe::instruction_groups].
+    /// Each ingredient is marked up as `@name{qty%unit}` where a step first mentions it,
+    /// those no step mentions are marked up in a paragraph of their own before the steps,
+    /// as Cooklang only has ingredients inside steps.
+    pub fn to_cooklang(&self) -> String {
+        let mut cook = String::new();
+        let mut meta = |key: &str, value: &str| {
+            if !value.is_empty() {
+                cook.push_str(&format!(">> {key}: {}\n", one_line(value)));
+            }
+        };
+        meta("title", &self.name);
+        meta("source", self.canonical_url.as_ref().unwrap_or(&self.url));
+        meta("author", self.author.as_deref().unwrap_or_default());
+        meta(
+            "description",
+            self.description.as_deref().unwrap_or_default(),
+        );
+        meta("image", self.image.as_deref().unwrap_or_default());
+        meta(
+            "servings",
+            self.yield_.as_ref().map_or("", |y| y.raw.as_str()),
+        );
+        for (key, time) in [
+            ("prep time", self.prep_time),
+            ("cook time", self.cook_time),
+            ("time required", self.total_time),
+        ] {
+            meta(key, &time.map(minutes).unwrap_or_default());
+        }
+        meta("cuisine", &self.cuisine.join(", "));
+        meta("course", &self.category.join(", "));
+        meta("tags", &self.keywords.join(", "));
+
+        let ip = self.ingredient_parser();
+        let mut ingredients: Vec<Option<Ingredient>> = self
+            .ingredients
+            .iter()
+            .map(|i| Some(ip.clone().from_str(i)).filter(|i| !i.name.is_empty()))
+            .collect();
+        let groups = match self.instruction_groups.is_empty() {
+            true => vec![(None, self.instructions.clone())],
+            false => self.instruction_groups.clone(),
+        };
+        let mut sections = vec![];
+        for (name, steps) in groups {
+            let steps: Vec<String> = steps
+                .iter()
+                .map(|step| mark_up(step, &mut ingredients))
+                .collect();
+            sections.push((name, steps));
+        }
+
+        let unmentioned: Vec<String> = ingredients
+            .iter()
+            .flatten()
+            .map(|i| i.to_cooklang())
+            .collect();
+        if !unmentioned.is_empty() {
+            cook.push_str(&format!("\n{}\n", unmentioned.join(", ")));
+        }
+        for (name, steps) in sections {
+            if let Some(name) = name {
+                cook.push_str(&format!("\n== {} ==\n", one_line(&name)));
+            }
+            for step in steps {
+                cook.push_str(&format!("\n{}\n", one_line(&step)));
+            }
+        }
+        cook
+    }
+}
+
+// Replaces the first mention of each of `ingredients` in `step` with its Cooklang token,
+// taking it out of `ingredients` so later steps only mention it by name.
+// Longer names go first so that `brown sugar` isn't taken for `sugar`.
+fn mark_up(step: &str, ingredients: &mut [Option<Ingredient>]) -> String {
+    // ascii only, so that positions in `lower` are the same in `step`
+    let lower = step.to_ascii_lowercase();
+    let mut order: Vec<usize> = (0..ingredients.len()).collect();
+    order.sort_by_key(|&idx| {
+        std::cmp::Reverse(ingredients[idx].as_ref().map_or(0, |i| i.name.len()))
+    });
+    // (start, end, token), not overlapping
+    let mut found: Vec<(usize, usize, String)> = vec![];
+    for idx in order {
+        let Some(ingredient) = &ingredients[idx] else {
+            continue;
+        };
+        let name = ingredient.name.to_ascii_lowercase();
+        let mention = lower
+            .match_indices(&name)
+            .map(|(start, _)| start)
+            .find(|&start| {
+                let end = start + name.len();
+                is_word_boundary(&lower, start, end)
+                    && !found.iter().any(|(s, e, _)| start < *e && end > *s)
+            });
+        if let Some(start) = mention {
+            found.push((start, start + name.len(), ingredient.to_cooklang()));
+            ingredients[idx] = None;
+        }
+    }
+    found.sort_by_key(|(start, _, _)| *start);
+    let mut marked = String::new();
+    let mut last = 0;
+    for (start, end, token) in found {
+        marked.push_str(&step[last..start]);
+        marked.push_str(&token);
+        last = end;
+    }
+    marked.push_str(&step[last..]);
+    marked
+}
+
+// `-` counts as part of a word since Cooklang reads `@salt-free` as one ingredient
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word)
+        && !text[end..].chars().next().is_some_and(is_word)
+}
+
+// a blank line would start a new step
+fn one_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn minutes(d: Duration) -> String {
+    format!("{} minutes", (d.as_secs_f64() / 60.0).round())
+}
+
+#[cfg(test)]
+mod tests {
+    use ingredient::IngredientParser;
+
+    use super::*;
+
+    #[test]
+    fn test_to_cooklang() {
+        let recipe = ScrapedRecipe {
+            name: "Toast".to_string(),
+            url: "https://example.com/toast".to_string(),
+            ingredients: vec![
+                "2 slices bread".to_string(),
+                "1 tbsp butter".to_string(),
+                "1 tsp brown sugar".to_string(),
+                "salt".to_string(),
+            ],
+            instructions: vec![
+                "Toast the bread until golden.".to_string(),
+                "Spread with Butter, then sprinkle over the brown sugar.\n".to_string(),
+                "Put the bread on a plate.".to_string(),
+            ],
+            cook_time: Some(Duration::from_secs(5 * 60)),
+            keywords: vec!["breakfast".to_string(), "quick".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            recipe.to_cooklang(),
+            ">> title: Toast
+>> source: https://example.com/toast
+>> cook time: 5 minutes
+>> tags: breakfast, quick
+
+@salt
+
+Toast the @bread{2%slice} until golden.
+
+Spread with @butter{1%tbsp}, then sprinkle over the @brown sugar{1%tsp}.
+
+Put the bread on a plate.
+"
+        );
+
+        let ip = IngredientParser::new(false);
+        let cook = recipe.to_cooklang();
+        let mut names: Vec<String> = cook
+            .lines()
+            .flat_map(|step| ip.cooklang_ingredients(step))
+            .map(|i| i.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["bread", "brown sugar", "butter", "salt"]);
+    }
+
+    #[test]
+    fn test_to_cooklang_sections() {
+        let recipe = ScrapedRecipe {
+            name: "Cake".to_string(),
+            url: "https://example.com/cake".to_string(),
+            ingredients: vec!["1 cup sugar".to_string(), "2 eggs".to_string()],
+            instructions: vec!["Beat the eggs.".to_string(), "Add the sugar.".to_string()],
+            instruction_groups: vec![
+                (None, vec!["Beat the eggs.".to_string()]),
+                (
+                    Some("For the glaze".to_string()),
+                    vec!["Add the sugar.".to_string()],
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            recipe.to_cooklang(),
+            ">> title: Cake
+>> source: https://example.com/cake
+
+Beat the @eggs{2}.
+
+== For the glaze ==
+
+Add the @sugar{1%cup}.
+"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! ISO 8601 durations, which ld+json uses for times, e.g. `PT1H30M`.
+use std::time::Duration;
+
+/// Parses an ISO 8601 duration such as `PT1H30M` or `P1DT2H`.
+/// Sites get these wrong often enough that lowercase, spaces and a missing `T`
+/// (`P30M` for 30 minutes) are accepted. Years and months aren't, nor is anything else malformed.
+/// ```
+/// use recipe_scraper::duration::parse_duration;
+/// use std::time::Duration;
+/// assert_eq!(parse_duration("PT1H30M"), Some(Duration::from_secs(90 * 60)));
+/// assert_eq!(parse_duration("PT0.5H"), Some(Duration::from_secs(30 * 60)));
+/// assert_eq!(parse_duration("30 minutes"), None);
+/// ```
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s: String = s
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    let rest = s.strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, time),
+        // no date part
+        None if rest.contains('H') || rest.contains('S') || !rest.contains('D') => ("", rest),
+        None => (rest, ""),
+    };
+    let secs = parse_parts(date, &[('W', 604800.0), ('D', 86400.0)])?
+        + parse_parts(time, &[('H', 3600.0), ('M', 60.0), ('S', 1.0)])?;
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+    // too long to be a duration at all
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Formats `d` as an ISO 8601 duration, in hours, minutes and seconds.
+/// ```
+/// use recipe_scraper::duration::format_duration;
+/// use std::time::Duration;
+/// assert_eq!(format_duration(Duration::from_secs(90 * 60)), "PT1H30M");
+/// assert_eq!(format_duration(Duration::ZERO), "PT0S");
+/// ```
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (hours, mins, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    let mut s = "PT".to_string();
+    for (n, designator) in [(hours, 'H'), (mins, 'M'), (secs, 'S')] {
+        if n > 0 {
+            s.push_str(&format!("{n}{designator}"));
+        }
+    }
+    if s.len() == 2 {
+        s.push_str("0S");
+    }
+    s
+}
+
+// e.g. `1H30M`, with the designators allowed in `units`, in order
+fn parse_parts(s: &str, units: &[(char, f64)]) -> Option<f64> {
+    let mut total = 0.0;
+    let mut units = units.iter();
+    let mut num = String::new();
+    for c in s.chars() {
+        match c {
+            '0'..='9' | '.' | ',' => num.push(if c == ',' { '.' } else { c }),
+            _ => {
+                let (_, secs) = units.find(|(u, _)| *u == c)?;
+                total += num.parse::<f64>().ok()? * secs;
+                num.clear();
+            }
+        }
+    }
+    match num.is_empty() {
+        true => Some(total),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let mins = |m: u64| Some(Duration::from_secs(m * 60));
+        assert_eq!(parse_duration("PT20M"), mins(20));
+        assert_eq!(parse_duration("P0DT1H5M"), mins(65));
+        assert_eq!(parse_duration("P1D"), mins(24 * 60));
+        assert_eq!(parse_duration("pt 45m"), mins(45));
+        assert_eq!(parse_duration("P30M"), mins(30));
+        assert_eq!(parse_duration("PT90S"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("PT0S"), Some(Duration::ZERO));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("PT1M1H"), None);
+        assert_eq!(parse_duration("P1Y"), None);
+        assert_eq!(parse_duration("PTM"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("P99999999999999999999D"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        for d in [0, 45, 20 * 60, 65 * 60, 24 * 3600 + 1] {
+            let d = Duration::from_secs(d);
+            assert_eq!(parse_duration(&format_duration(d)), Some(d));
+        }
+        assert_eq!(format_duration(Duration::from_secs(3601)), "PT1H1S");
+    }
+}
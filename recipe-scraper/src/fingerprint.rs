@@ -0,0 +1,146 @@
+//! Telling when two scraped recipes are the same one, e.g. a recipe syndicated to
+//! several sites, or scraped from both its page and a mirror of it.
+use std::collections::HashSet;
+
+use crate::ScrapedRecipe;
+
+impl ScrapedRecipe {
+    /// A hash of what it's made of and how, as 16 hex digits, which is the same for copies
+    /// of the recipe whatever their name, url or images, and however their ingredients
+    /// are ordered or written (`2 Tbsp salt` and `6 tsp salt`), or their instructions
+    /// punctuated. It's the same from one run to the next, so it can be stored to check newly
+    /// scraped recipes against, but as it's made from how the ingredients are parsed, it can
+    /// change with a new version of this crate, and stored ones should be computed again then.
+    pub fn fingerprint(&self) -> String {
+        let ip = self.ingredient_parser();
+        let mut ingredients: Vec<String> = self
+            .ingredients
+            .iter()
+            .map(|i| serde_json::to_string(&ip.clone().from_str(i).normalized()).unwrap())
+            .collect();
+        ingredients.sort();
+        let text = format!(
+            "{}\n\n{}",
+            ingredients.join("\n"),
+            instruction_words(self).join(" ")
+        );
+        format!("{:016x}", fnv1a(text.as_bytes()))
+    }
+
+    /// How alike it is to `other`, from `0.0` for nothing in common to `1.0` for the same
+    /// ingredients and instruction words, for telling copies that were edited a little
+    /// apart from different recipes. Copies are usually over `0.8`.
+    pub fn similarity(&self, other: &ScrapedRecipe) -> f64 {
+        let names = |r: &ScrapedRecipe| -> HashSet<String> {
+            let ip = r.ingredient_parser();
+            r.ingredients
+                .iter()
+                .map(|i| ip.clone().from_str(i).normalized_name())
+                .collect()
+        };
+        let words =
+            |r: &ScrapedRecipe| -> HashSet<String> { instruction_words(r).into_iter().collect() };
+        let scores: Vec<f64> = [
+            jaccard(&names(self), &names(other)),
+            jaccard(&words(self), &words(other)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        match scores.is_empty() {
+            true => 0.0,
+            false => scores.iter().sum::<f64>() / scores.len() as f64,
+        }
+    }
+}
+
+fn instruction_words(r: &ScrapedRecipe) -> Vec<String> {
+    r.instructions
+        .iter()
+        .flat_map(|s| s.split(|c: char| !c.is_alphanumeric()))
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// `None` when neither has any
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> Option<f64> {
+    let union = a.union(b).count();
+    match union {
+        0 => None,
+        _ => Some(a.intersection(b).count() as f64 / union as f64),
+    }
+}
+
+/// FNV-1a, for hashes that are stored, as std's hashers aren't guaranteed to hash the same
+/// across releases: [ScrapedRecipe::fingerprint], or the file names of a cache.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(url: &str, ingredients: &[&str], instructions: &[&str]) -> ScrapedRecipe {
+        ScrapedRecipe {
+            url: url.to_string(),
+            ingredients: ingredients.iter().map(|s| s.to_string()).collect(),
+            instructions: instructions.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let original = recipe(
+            "https://example.com/toast",
+            &["2 slices bread", "2 Tbsp butter"],
+            &["Toast the bread.", "Spread with the butter."],
+        );
+        let mirror = recipe(
+            "https://mirror.example.org/recipes/toast",
+            &["6 tsp butter", "2 slices bread"],
+            &["Toast the bread", "Spread with the butter!"],
+        );
+        assert_eq!(original.fingerprint(), mirror.fingerprint());
+        assert_eq!(original.fingerprint().len(), 16);
+        let more_butter = recipe(
+            "https://example.com/toast",
+            &["2 slices bread", "3 Tbsp butter"],
+            &["Toast the bread.", "Spread with the butter."],
+        );
+        assert_ne!(original.fingerprint(), more_butter.fingerprint());
+        // the published FNV-1a test vectors, so stored hashes stay valid
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_similarity() {
+        let original = recipe(
+            "https://example.com/toast",
+            &["2 slices bread", "2 Tbsp butter"],
+            &["Toast the bread.", "Spread with the butter."],
+        );
+        assert_eq!(original.similarity(&original), 1.0);
+        let edited = recipe(
+            "https://example.org/toast",
+            &["3 slices bread", "2 tbsp butter"],
+            &["Toast the bread.", "Spread with the butter, then eat."],
+        );
+        assert!(original.similarity(&edited) > 0.8);
+        let soup = recipe(
+            "https://example.com/soup",
+            &["1 onion", "1 l stock"],
+            &["Simmer the onion in the stock."],
+        );
+        assert!(original.similarity(&soup) < 0.3);
+        assert_eq!(
+            ScrapedRecipe::default().similarity(&ScrapedRecipe::default()),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! What language a recipe is written in, for parsing its ingredients in it.
+use ingredient::{locale::Locale, IngredientParser};
+
+use crate::ScrapedRecipe;
+
+// common words that don't look like words of the others
+const WORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "with", "into", "until", "minutes", "add", "over", "then", "for",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "und", "der", "die", "das", "mit", "den", "bis", "minuten", "dann", "oder", "eine",
+            "auf",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "et", "le", "les", "des", "du", "avec", "dans", "puis", "pendant", "ajouter", "une",
+            "sur",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "y", "el", "los", "las", "con", "hasta", "minutos", "luego", "añadir", "una", "del",
+            "para",
+        ],
+    ),
+];
+
+impl ScrapedRecipe {
+    /// The [Locale] of its [ScrapedRecipe::language], `None` when it isn't known
+    /// or the ingredient parser has no vocabulary for it.
+    pub fn locale(&self) -> Option<Locale> {
+        self.language.as_deref()?.parse().ok()
+    }
+
+    /// An [IngredientParser] that also understands its language's units and number words,
+    /// see [IngredientParser::with_locale].
+    pub fn ingredient_parser(&self) -> IngredientParser {
+        self.parser(false)
+    }
+
+    pub(crate) fn parser(&self, is_rich_text: bool) -> IngredientParser {
+        let ip = IngredientParser::new(is_rich_text);
+        match self.locale() {
+            Some(locale) => ip.with_locale(locale),
+            None => ip,
+        }
+    }
+}
+
+/// The language most of `recipe`'s ingredients and instructions are in, going by how
+/// often each language's common words come up. `None` when there isn't enough text
+/// to go on, or no language clearly comes up most.
+pub(crate) fn guess_language(recipe: &ScrapedRecipe) -> Option<String> {
+    let text = recipe
+        .ingredients
+        .iter()
+        .chain(recipe.instructions.iter())
+        .map(|s| s.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut counts: Vec<(&str, usize)> = WORDS
+        .iter()
+        .map(|(lang, common)| {
+            let n = words.iter().filter(|w| common.contains(w)).count();
+            (*lang, n)
+        })
+        .collect();
+    counts.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    match counts.as_slice() {
+        [(lang, first), (_, second), ..] if *first >= 3 && *first >= second * 2 => {
+            Some(lang.to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(ingredients: &[&str], instructions: &[&str]) -> ScrapedRecipe {
+        ScrapedRecipe {
+            ingredients: ingredients.iter().map(|s| s.to_string()).collect(),
+            instructions: instructions.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_guess_language() {
+        let toast = recipe(
+            &["2 slices bread", "1 tbsp butter"],
+            &["Toast the bread until golden, then spread with the butter."],
+        );
+        assert_eq!(guess_language(&toast), Some("en".to_string()));
+        let kuchen = recipe(
+            &["200 g Mehl", "2 EL Zucker"],
+            &["Das Mehl mit dem Zucker mischen und dann in die Form geben, bis es fest ist."],
+        );
+        assert_eq!(guess_language(&kuchen), Some("de".to_string()));
+        let tarte = recipe(
+            &["200 g de farine", "1 pincée de sel"],
+            &["Mélanger la farine avec le sel dans un bol, puis ajouter les œufs et le lait."],
+        );
+        assert_eq!(guess_language(&tarte), Some("fr".to_string()));
+        let tortilla = recipe(
+            &["4 huevos", "1 pizca de sal"],
+            &["Batir los huevos con la sal y luego freír las patatas hasta que estén doradas."],
+        );
+        assert_eq!(guess_language(&tortilla), Some("es".to_string()));
+        assert_eq!(guess_language(&recipe(&["salt"], &[])), None);
+    }
+
+    #[test]
+    fn test_ingredient_parser() {
+        let mut r = recipe(&["2 EL Zucker"], &[]);
+        assert_eq!(r.locale(), None);
+        r.language = Some("de-AT".to_string());
+        assert_eq!(r.locale(), Some(Locale::De));
+        assert_eq!(
+            r.ingredient_parser().from_str("2 EL Zucker").to_string(),
+            "2 tbsp Zucker"
+        );
+        r.language = Some("ja".to_string());
+        assert_eq!(r.locale(), None);
+    }
+}
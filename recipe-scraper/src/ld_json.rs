@@ -0,0 +1,156 @@
+//! Writing a [ScrapedRecipe] back out as a schema.org Recipe, for storing or republishing it.
+use serde_json::{json, Map, Value};
+
+use crate::{duration::format_duration, ScrapedRecipe, ScrapedStep};
+
+impl ScrapedRecipe {
+    /// A schema.org [Recipe](https://schema.org/Recipe) ld+json document of it, which
+    /// [crate::scrape] reads back the same. Instructions are `HowToStep`s,
+    /// in `HowToSection`s when it has [ScrapedRecipe::instruction_groups].
+    /// Ingredient headings are kept as lines ending in `:`, which [crate::scrape] reads back.
+    pub fn to_ld_json(&self) -> Value {
+        let mut ld = Map::new();
+        let mut set = |key: &str, value: Value| {
+            if !(value.is_null() || value.as_array().is_some_and(Vec::is_empty)) {
+                ld.insert(key.to_string(), value);
+            }
+        };
+        set("@context", json!("https://schema.org"));
+        set("@type", json!("Recipe"));
+        set("name", json!(self.name));
+        set(
+            "url",
+            json!(self.canonical_url.as_ref().unwrap_or(&self.url)),
+        );
+        set("description", json!(self.description));
+        set("inLanguage", json!(self.language));
+        set("image", json!(self.image));
+        set(
+            "author",
+            json!(self
+                .author
+                .as_ref()
+                .map(|name| json!({"@type": "Person", "name": name}))),
+        );
+        set(
+            "recipeYield",
+            json!(self.yield_.as_ref().map(|y| y.raw.clone())),
+        );
+        for (key, time) in [
+            ("prepTime", self.prep_time),
+            ("cookTime", self.cook_time),
+            ("totalTime", self.total_time),
+        ] {
+            set(key, json!(time.map(format_duration)));
+        }
+        set("recipeCuisine", json!(self.cuisine));
+        set("recipeCategory", json!(self.category));
+        set(
+            "keywords",
+            match self.keywords.is_empty() {
+                true => Value::Null,
+                false => json!(self.keywords.join(", ")),
+            },
+        );
+        set(
+            "tool",
+            json!(self
+                .equipment
+                .iter()
+                .map(|name| json!({"@type": "HowToTool", "name": name}))
+                .collect::<Vec<_>>()),
+        );
+        set(
+            "aggregateRating",
+            json!(self.rating.as_ref().map(|r| {
+                let mut rating = json!({"@type": "AggregateRating", "ratingValue": r.value});
+                if let Some(count) = r.count {
+                    rating["ratingCount"] = json!(count);
+                }
+                rating
+            })),
+        );
+        set(
+            "video",
+            json!(self.video.as_ref().map(|v| {
+                let mut video = json!({"@type": "VideoObject"});
+                for (key, value) in [
+                    ("name", &v.name),
+                    ("contentUrl", &v.content_url),
+                    ("embedUrl", &v.embed_url),
+                    ("thumbnailUrl", &v.thumbnail),
+                ] {
+                    if let Some(value) = value {
+                        video[key] = json!(value);
+                    }
+                }
+                video
+            })),
+        );
+        set("recipeIngredient", json!(self.ld_ingredients()));
+        set("recipeInstructions", Value::Array(self.ld_instructions()));
+        Value::Object(ld)
+    }
+
+    // schema.org has no sections of ingredients, so their headings are written
+    // the way sites usually do, e.g. `For the glaze:`
+    pub(crate) fn ld_ingredients(&self) -> Vec<String> {
+        if self.ingredient_groups.is_empty() {
+            return self.ingredients.clone();
+        }
+        self.ingredient_groups
+            .iter()
+            .flat_map(|(name, lines)| {
+                name.iter()
+                    .map(|n| format!("{n}:"))
+                    .chain(lines.iter().cloned())
+            })
+            .collect()
+    }
+
+    fn ld_instructions(&self) -> Vec<Value> {
+        // recipes from before `steps` only have their text
+        let steps: Vec<ScrapedStep> = match self.steps.is_empty() {
+            true => self
+                .instructions
+                .iter()
+                .map(|text| ScrapedStep::new(text.clone()))
+                .collect(),
+            false => self.steps.clone(),
+        };
+        if self.instruction_groups.is_empty() {
+            return steps.iter().map(how_to_step).collect();
+        }
+        let mut steps = steps.iter();
+        self.instruction_groups
+            .iter()
+            .map(|(name, texts)| {
+                let section: Vec<Value> =
+                    steps.by_ref().take(texts.len()).map(how_to_step).collect();
+                match name {
+                    Some(name) => json!({
+                        "@type": "HowToSection",
+                        "name": name,
+                        "itemListElement": section,
+                    }),
+                    // steps before the first heading
+                    None => json!({"@type": "HowToSection", "itemListElement": section}),
+                }
+            })
+            .collect()
+    }
+}
+
+fn how_to_step(step: &ScrapedStep) -> Value {
+    let mut ld = json!({"@type": "HowToStep", "text": step.text});
+    for (key, value) in [
+        ("name", &step.name),
+        ("image", &step.image),
+        ("url", &step.url),
+    ] {
+        if let Some(value) = value {
+            ld[key] = json!(value);
+        }
+    }
+    ld
+}
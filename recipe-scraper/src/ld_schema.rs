@@ -1,38 +1,116 @@
-use serde::{Deserialize, Serialize};
+//! The shapes of schema.org Recipes in ld+json, for normalizing them yourself where
+//! [crate::scrape] falls short, e.g. from the nodes of [crate::extract_ld_json].
+//! Sites don't stick to the spec, so values that don't fit are left out rather than failing
+//! the whole recipe where they can be, and properties that aren't read into fields are ignored
+//! (but kept in [RootRecipe::raw]).
+//! ```
+//! use recipe_scraper::{extract_ld_json, ld_schema::{find_recipe, RootRecipe}};
+//! let page = r#"<script type="application/ld+json">
+//! {"@type": "Recipe", "name": "Toast", "recipeIngredient": "1 slice bread",
+//!  "recipeInstructions": "Toast it.", "suitableForDiet": "VegetarianDiet"}
+//! </script>"#;
+//! let nodes = extract_ld_json(page);
+//! let node = find_recipe(&nodes[0]).unwrap();
+//! let recipe: RootRecipe = serde_json::from_value(node.clone()).unwrap();
+//! assert_eq!(recipe.name, "Toast");
+//! assert_eq!(node["suitableForDiet"], "VegetarianDiet");
+//! ```
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+/// A `Recipe` node.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "recipe")]
 pub struct RootRecipe {
-    #[serde(rename = "@context")]
+    /// usually `https://schema.org`, but can be a list or an object
+    #[serde(rename = "@context", default, deserialize_with = "or_none")]
     pub context: Option<String>,
     // #[serde(rename = "@type")]
     // pub type_field: String,
+    #[serde(default, deserialize_with = "lenient_string")]
     pub name: String,
-    // pub description: String,
-    // pub author: Author,
+    #[serde(default)]
+    pub description: Option<Value>,
+    /// a language code, or a Language
+    #[serde(default)]
+    pub in_language: Option<Value>,
+    #[serde(default, deserialize_with = "or_none")]
     pub image: Option<ImageOrList>,
-    // pub total_time: String,
-    // pub recipe_yield: String,
-    // pub recipe_cuisine: String,
-    // pub recipe_category: String,
-    // pub keywords: String,
-    // pub aggregate_rating: AggregateRating,
-    pub recipe_ingredient: Vec<String>,
+    /// ISO 8601 durations, but they aren't always strings
+    #[serde(default)]
+    pub prep_time: Option<Value>,
+    #[serde(default)]
+    pub cook_time: Option<Value>,
+    #[serde(default)]
+    pub total_time: Option<Value>,
+    /// a string, number, or list of either
+    #[serde(default)]
+    pub recipe_yield: Option<Value>,
+    /// a name, a Person or Organization, or a list of those
+    #[serde(default)]
+    pub author: Option<Value>,
+    /// the same, but HowToTools
+    #[serde(default)]
+    pub tool: Option<Value>,
+    /// strings, comma separated strings, or lists of strings
+    #[serde(default)]
+    pub recipe_cuisine: Option<Value>,
+    #[serde(default)]
+    pub recipe_category: Option<Value>,
+    #[serde(default)]
+    pub keywords: Option<Value>,
+    /// numbers are sometimes strings
+    #[serde(default)]
+    pub aggregate_rating: Option<Value>,
+    /// a VideoObject, or a list of them
+    #[serde(default)]
+    pub video: Option<Value>,
+    /// a single line isn't always in a list
+    #[serde(deserialize_with = "one_or_many")]
+    pub recipe_ingredient: Vec<IngredientLine>,
     pub recipe_instructions: InstructionWrapper,
     // pub is_accessible_for_free: String,
     // pub has_part: HasPart,
     // pub publisher: Publisher,
+    /// the node it was read from, set by the scraper rather than deserialized,
+    /// see [crate::ScrapedRecipe::raw]
+    #[serde(skip)]
+    pub raw: Option<Value>,
+}
+
+/// a line of `recipeIngredient`, or a few sites' sections of them, like `HowToSection`s
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum IngredientLine {
+    Line(String),
+    Section(IngredientSection),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientSection {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(alias = "recipeIngredient")]
+    pub item_list_element: Vec<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecipeInstructionA {
-    #[serde(rename = "@context")]
+    #[serde(rename = "@context", default, deserialize_with = "or_none")]
     pub context: Option<String>,
-    #[serde(rename = "@type")]
+    /// `HowToStep`, when it's there
+    #[serde(rename = "@type", default)]
     pub type_field: String,
+    #[serde(deserialize_with = "lenient_string")]
     pub text: String,
+    #[serde(default, deserialize_with = "or_none")]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "or_none")]
+    pub image: Option<ImageOrList>,
+    #[serde(default, deserialize_with = "or_none")]
+    pub url: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -40,13 +118,15 @@ pub struct RecipeInstructionA {
 pub struct RecipeInstructionB {
     #[serde(rename = "@type")]
     pub type_field: String,
+    #[serde(default)]
     pub name: String,
-    pub item_list_element: Vec<ItemListElement>,
+    /// steps, or further sections
+    pub item_list_element: Vec<BOrWrapper>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct HasPart {
+pub(crate) struct HasPart {
     #[serde(rename = "@type")]
     pub type_field: String,
     pub is_accessible_for_free: String,
@@ -57,27 +137,34 @@ pub struct HasPart {
 #[serde(rename_all = "camelCase")]
 pub struct Image {
     pub url: String,
+    #[serde(default, deserialize_with = "lenient_number")]
     pub height: Option<i64>,
+    #[serde(default, deserialize_with = "lenient_number")]
     pub width: Option<i64>,
-    #[serde(rename = "@context")]
+    #[serde(rename = "@context", default, deserialize_with = "or_none")]
     pub context: Option<String>,
-    #[serde(rename = "@type")]
+    #[serde(rename = "@type", default, deserialize_with = "or_none")]
     pub type_field: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
-pub enum RecipeInstruction {
+pub(crate) enum RecipeInstruction {
     A(RecipeInstructionA),
     B(BOrWrapper),
 }
 
+/// The shapes `recipeInstructions` comes in.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum InstructionWrapper {
+    /// `HowToStep`s
     A(Vec<RecipeInstructionA>),
+    /// `HowToSection`s, or `ItemList`s of steps
     B(Vec<BOrWrapper>),
+    /// all of them in one string
     C(String),
+    /// lists of steps
     D(Vec<Vec<RecipeInstructionA>>),
 }
 
@@ -88,6 +175,7 @@ pub enum BOrWrapper {
     Wrapper(ItemListElement),
 }
 
+/// An `image`: a url, an `ImageObject`, or a list of either.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ImageOrList {
@@ -97,18 +185,51 @@ pub enum ImageOrList {
     Image(Image),
 }
 
+/// A whole ld+json script, by its shape. Going by `@type` with [find_recipe] first
+/// finds more recipes.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Root {
     Graph(RootGraph),
-    Recipe(RootRecipe),
-    List(Vec<RootRecipe>),
+    Recipe(Box<RootRecipe>),
+    /// e.g. `[{"@type": "Recipe", ...}, {"@type": "WebSite", ...}]`
+    List(Vec<Graph>),
+}
+
+/// The node whose `@type` is a recipe, in a list or `@graph` if need be.
+/// The type can be a list, and is matched loosely: `Recipe`, `recipe`, `schema:Recipe`
+/// and `http://schema.org/Recipe` all count.
+pub fn find_recipe(v: &Value) -> Option<&Value> {
+    find_recipes(v).into_iter().next()
+}
+
+/// Every node [find_recipe] would consider, in order.
+pub fn find_recipes(v: &Value) -> Vec<&Value> {
+    match v {
+        Value::Array(a) => a.iter().flat_map(find_recipes).collect(),
+        Value::Object(o) => match o.get("@type") {
+            Some(t) if is_recipe_type(t) => vec![v],
+            _ => o.get("@graph").map(find_recipes).unwrap_or_default(),
+        },
+        _ => vec![],
+    }
+}
+
+fn is_recipe_type(t: &Value) -> bool {
+    match t {
+        Value::String(s) => {
+            let name = s.rsplit(['/', ':', '#']).next().unwrap_or_default();
+            matches!(name.to_lowercase().as_str(), "recipe" | "recipes")
+        }
+        Value::Array(a) => a.iter().any(is_recipe_type),
+        _ => false,
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RootGraph {
-    #[serde(rename = "@context")]
+    #[serde(rename = "@context", default, deserialize_with = "lenient_string")]
     pub context: String,
     #[serde(rename = "@graph")]
     pub graph: Vec<Graph>,
@@ -117,7 +238,7 @@ pub struct RootGraph {
 // #[serde(tag = "@type")]
 #[serde(untagged)]
 pub enum Graph {
-    Recipe(RootRecipe),
+    Recipe(Box<RootRecipe>),
     Article(Value),
     WebPage(Value),
     ImageObject(Image),
@@ -129,7 +250,7 @@ pub enum Graph {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Graph2 {
+pub(crate) struct Graph2 {
     #[serde(rename = "@type")]
     pub type_field: String,
     #[serde(rename = "@id")]
@@ -181,14 +302,14 @@ pub struct Graph2 {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct IsPartOf {
+pub(crate) struct IsPartOf {
     #[serde(rename = "@id")]
     pub id: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Author {
+pub(crate) struct Author {
     #[serde(rename = "@type")]
     pub type_field: Option<String>,
     pub name: String,
@@ -198,14 +319,14 @@ pub struct Author {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct PrimaryImageOfPage {
+pub(crate) struct PrimaryImageOfPage {
     #[serde(rename = "@id")]
     pub id: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Breadcrumb {
+pub(crate) struct Breadcrumb {
     #[serde(rename = "@id")]
     pub id: String,
 }
@@ -213,18 +334,25 @@ pub struct Breadcrumb {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ItemListElement {
-    #[serde(rename = "@type")]
+    #[serde(rename = "@type", default, deserialize_with = "or_none")]
     pub type_field: Option<String>,
+    #[serde(default, deserialize_with = "lenient_number")]
     pub position: Option<i64>,
+    #[serde(default, deserialize_with = "or_none")]
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "or_none")]
     pub item: Option<String>,
+    #[serde(default, deserialize_with = "or_none")]
     pub text: Option<String>,
+    #[serde(default, deserialize_with = "or_none")]
     pub url: Option<String>,
+    #[serde(default, deserialize_with = "or_none")]
+    pub image: Option<ImageOrList>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Logo {
+pub(crate) struct Logo {
     #[serde(rename = "@type")]
     pub type_field: String,
     pub in_language: String,
@@ -239,7 +367,7 @@ pub struct Logo {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct RecipeInstructionC {
+pub(crate) struct RecipeInstructionC {
     #[serde(rename = "@type")]
     pub type_field: String,
     pub text: String,
@@ -249,7 +377,7 @@ pub struct RecipeInstructionC {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AggregateRating {
+pub(crate) struct AggregateRating {
     #[serde(rename = "@type")]
     pub type_field: String,
     pub rating_value: String,
@@ -258,7 +386,7 @@ pub struct AggregateRating {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Nutrition {
+pub(crate) struct Nutrition {
     #[serde(rename = "@type")]
     pub type_field: String,
     pub calories: String,
@@ -275,9 +403,92 @@ pub struct Nutrition {
     pub serving_size: String,
 }
 
+/// `None` for a value that doesn't fit `T`, e.g. an `@context` that's an object,
+/// rather than failing what it's part of.
+fn or_none<'de, D: Deserializer<'de>, T: DeserializeOwned>(d: D) -> Result<Option<T>, D::Error> {
+    Ok(serde_json::from_value(Value::deserialize(d)?).ok())
+}
+
+/// A string, or a number written without quotes; empty for anything else.
+fn lenient_string<'de, D: Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    Ok(match Value::deserialize(d)? {
+        Value::String(s) => s,
+        Value::Number(n) => n.to_string(),
+        _ => String::new(),
+    })
+}
+
+/// A number, or a string of one, e.g. `"width": "1200"`.
+fn lenient_number<'de, D: Deserializer<'de>>(d: D) -> Result<Option<i64>, D::Error> {
+    Ok(match Value::deserialize(d)? {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+/// A list, or a single item of one. Items that don't fit `T` are left out.
+fn one_or_many<'de, D: Deserializer<'de>, T: DeserializeOwned>(d: D) -> Result<Vec<T>, D::Error> {
+    let items = match Value::deserialize(d)? {
+        Value::Array(items) => items,
+        Value::Null => vec![],
+        item => vec![item],
+    };
+    Ok(items
+        .into_iter()
+        .filter_map(|i| serde_json::from_value(i).ok())
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{RootGraph, RootRecipe};
+    use super::{find_recipe, ImageOrList, InstructionWrapper, RootGraph, RootRecipe};
+    use serde_json::json;
+
+    #[test]
+    fn lenient() {
+        let r: RootRecipe = serde_json::from_value(json!({
+            "@context": ["https://schema.org", {"@language": "en"}],
+            "name": 42,
+            "image": {"@type": "ImageObject", "url": "https://example.com/a.jpg", "width": "1200"},
+            "recipeIngredient": "1 slice bread",
+            "recipeInstructions": [{"text": "Toast it."}],
+            "somethingNew": {"@type": "Thing"}
+        }))
+        .unwrap();
+        assert_eq!(r.context, None);
+        assert_eq!(r.name, "42");
+        match r.image {
+            Some(ImageOrList::Image(i)) => assert_eq!(i.width, Some(1200)),
+            i => panic!("{i:?}"),
+        }
+        assert_eq!(r.recipe_ingredient.len(), 1);
+        match r.recipe_instructions {
+            InstructionWrapper::A(steps) => assert_eq!(steps[0].text, "Toast it."),
+            i => panic!("{i:?}"),
+        }
+        let r: RootRecipe = serde_json::from_value(json!({
+            "image": {"@id": "#primaryimage"},
+            "recipeIngredient": ["1 slice bread", null],
+            "recipeInstructions": "Toast it."
+        }))
+        .unwrap();
+        assert_eq!((r.image, r.recipe_ingredient.len()), (None, 1));
+        // without the ingredients and instructions it isn't a recipe
+        assert!(serde_json::from_value::<RootRecipe>(json!({"name": "Home"})).is_err());
+    }
+
+    #[test]
+    fn find_recipe_by_type() {
+        let recipe = json!({"@type": ["NewsArticle", "http://schema.org/Recipe"]});
+        let graph = json!({"@graph": [{"@type": "WebPage"}, recipe]});
+        assert_eq!(find_recipe(&graph), Some(&recipe));
+        assert_eq!(
+            find_recipe(&json!([{"@type": "Recipes"}])),
+            Some(&json!({"@type": "Recipes"}))
+        );
+        assert_eq!(find_recipe(&json!({"@type": "RecipeCollection"})), None);
+    }
 
     #[test]
     fn it_works_file() {
@@ -1,15 +1,46 @@
+#[cfg(feature = "warc")]
+pub use archive::WarcPages;
+pub use fingerprint::fnv1a;
 use ingredient::{
     ingredient::Ingredient,
+    recipe::Recipe,
+    recipe_yield::Yield,
     rich_text::{Rich, RichParser},
     IngredientParser,
 };
-use scraper::{Html, Selector};
+pub use outcome::{ScrapeOutcome, ScrapeWarning};
+#[cfg(feature = "paprika")]
+pub use paprika::{write_paprikarecipes, PaprikaRecipe};
+pub use scraper::{Html, Selector};
+pub use sites::{Scraper, SelectorRules, SiteScraper};
+use std::time::Duration;
+pub use walls::detect_wall;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-mod ld_schema;
+mod archive;
+#[cfg(feature = "blocking")]
+mod blocking;
+mod clean;
+mod cooklang;
+pub mod duration;
+mod fingerprint;
+mod language;
+mod ld_json;
+pub mod ld_schema;
+mod managers;
+mod markup;
+mod outcome;
+#[cfg(feature = "paprika")]
+mod paprika;
+mod plugins;
+mod recipemd;
+mod scale;
+mod scan;
+mod sites;
+mod walls;
 use thiserror::Error;
-use tracing::{error, info};
+use tracing::error;
 
 #[derive(Error, Debug)]
 pub enum ScrapeError {
@@ -23,14 +54,199 @@ pub enum ScrapeError {
     Deserialize(#[from] serde_json::Error),
     #[error("could not parse `{0}`")]
     Parse(String),
+    #[error("robots.txt disallows fetching `{0}`")]
+    RobotsDisallowed(String),
+    #[error("could not read `{0}`")]
+    Io(String),
+    #[error("`{url}` responded with {status}")]
+    Status { url: String, status: u16 },
+    /// a bot challenge or consent wall instead of the page, see [detect_wall]
+    #[error("`{url}` is blocked: {reason}")]
+    Blocked { url: String, reason: String },
+    /// only subscribers can see the recipe, see [detect_wall]
+    #[error("`{url}` is paywalled: {reason}")]
+    Paywalled { url: String, reason: String },
 }
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+
+impl ScrapeError {
+    /// the url that failed, for the errors that know it
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            ScrapeError::LDJSONMissingRecipe(url, _)
+            | ScrapeError::RobotsDisallowed(url)
+            | ScrapeError::Status { url, .. }
+            | ScrapeError::Blocked { url, .. }
+            | ScrapeError::Paywalled { url, .. } => Some(url),
+            _ => None,
+        }
+    }
+    /// the http status the site responded with, e.g. `404` or `403`
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ScrapeError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub struct ScrapedRecipe {
     pub ingredients: Vec<String>,
     pub instructions: Vec<String>,
+    /// `instructions` along with their names, images and links
+    #[serde(default)]
+    pub steps: Vec<ScrapedStep>,
     pub name: String,
     pub url: String,
+    /// where the page says it lives (`<link rel="canonical">`), or where `url` redirected to,
+    /// so that its `m.`, AMP or tracking parameter variants can be told apart
+    #[serde(default)]
+    pub canonical_url: Option<String>,
     pub image: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// the author's notes and tips, e.g. substitutions or how to keep it, from the recipe's
+    /// `comment` or its recipe card's notes
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// what it's written in, e.g. `fr` or `en-GB`, from the recipe's `inLanguage`
+    /// or the page's `<html lang>`, or else guessed from its ingredients and instructions
+    #[serde(default)]
+    pub language: Option<String>,
+    /// `ingredients` under the headings of their sections, e.g. `For the glaze`,
+    /// empty when the recipe doesn't have any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingredient_groups: Vec<(Option<String>, Vec<String>)>,
+    /// `instructions` under the headings of their sections, e.g. `For the dough`,
+    /// empty when the recipe doesn't have any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instruction_groups: Vec<(Option<String>, Vec<String>)>,
+    #[serde(rename = "yield", default)]
+    pub yield_: Option<RecipeYield>,
+    #[serde(default)]
+    pub prep_time: Option<Duration>,
+    #[serde(default)]
+    pub cook_time: Option<Duration>,
+    #[serde(default)]
+    pub total_time: Option<Duration>,
+    /// the name of the person or organization
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub rating: Option<Rating>,
+    #[serde(default)]
+    pub cuisine: Vec<String>,
+    #[serde(default)]
+    pub category: Vec<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// tools needed, e.g. `stand mixer`
+    #[serde(default)]
+    pub equipment: Vec<String>,
+    #[serde(default)]
+    pub video: Option<RecipeVideo>,
+    /// the recipe's node of the page's ld+json as it was, for properties that aren't read
+    /// into the others, `None` when it wasn't found in ld+json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Value>,
+}
+
+/// An instruction step, with what the site has besides its text.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct ScrapedStep {
+    pub text: String,
+    /// a short title, e.g. `Make the dough`, when it isn't just the text again
+    pub name: Option<String>,
+    pub image: Option<String>,
+    /// link to the step on the page
+    pub url: Option<String>,
+}
+
+impl ScrapedStep {
+    fn new(text: String) -> ScrapedStep {
+        ScrapedStep {
+            text,
+            ..Default::default()
+        }
+    }
+    fn from_ld(step: ld_schema::RecipeInstructionA) -> ScrapedStep {
+        ScrapedStep {
+            name: step.name.filter(|n| *n != step.text),
+            image: step.image.and_then(image_url),
+            url: step.url,
+            text: step.text,
+        }
+    }
+    // a step without `text` might still have been written in its `name`
+    fn from_list_item(step: ld_schema::ItemListElement) -> Option<ScrapedStep> {
+        let text = step.text.or_else(|| step.name.clone())?;
+        Some(ScrapedStep {
+            name: step.name.filter(|n| *n != text),
+            image: step.image.and_then(image_url),
+            url: step.url,
+            text,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Rating {
+    pub value: f64,
+    pub count: Option<u64>,
+}
+
+/// A how-to video of the recipe.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct RecipeVideo {
+    pub name: Option<String>,
+    /// the video file itself
+    pub content_url: Option<String>,
+    /// a player for it, for embedding in an `iframe`
+    pub embed_url: Option<String>,
+    pub thumbnail: Option<String>,
+}
+
+/// How much a recipe makes, e.g. `Makes about 24 cookies`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RecipeYield {
+    /// as the site wrote it
+    pub raw: String,
+    /// `None` when it couldn't be made sense of, e.g. `varies`. It's what
+    /// [ScrapedRecipe::to_recipe] keeps, and what [ScrapedRecipe::scale_to_servings] goes by.
+    pub parsed: Option<Yield>,
+}
+
+impl From<Yield> for RecipeYield {
+    fn from(y: Yield) -> Self {
+        RecipeYield {
+            raw: y.raw.clone(),
+            parsed: Some(y),
+        }
+    }
+}
+
+impl RecipeYield {
+    // `recipeYield` can be a string, a number, or a list of those such as `["4", "4 servings"]`,
+    // in which case the most descriptive one is used
+    fn from_ld(v: &Value) -> Option<RecipeYield> {
+        let raw = match v {
+            Value::String(s) => s.trim().to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::Array(a) => {
+                a.iter()
+                    .filter_map(RecipeYield::from_ld)
+                    .max_by_key(|y| y.raw.len())?
+                    .raw
+            }
+            _ => return None,
+        };
+        if raw.is_empty() {
+            return None;
+        }
+        Some(RecipeYield {
+            parsed: ingredient::parse_yield(&raw).ok(),
+            raw,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
@@ -40,7 +256,7 @@ pub struct ParsedRecipe {
 }
 impl ScrapedRecipe {
     pub fn parse(&self) -> ParsedRecipe {
-        let ip = IngredientParser::new(false);
+        let ip = self.parser(false);
         let ingredients = self
             .ingredients
             .iter()
@@ -49,7 +265,7 @@ impl ScrapedRecipe {
         let names = ingredients.iter().map(|i| i.name.clone()).collect();
         let rtp = RichParser {
             ingredient_names: names,
-            ip: IngredientParser::new(true),
+            ip: self.parser(true),
         };
         let parsed_instructions = self
             .instructions
@@ -62,92 +278,433 @@ impl ScrapedRecipe {
             instructions: parsed_instructions,
         }
     }
+
+    /// Each of `ingredients` parsed by the default [IngredientParser], in order,
+    /// see [ScrapedRecipe::parsed_ingredients_with] for lenient or strict parsing.
+    pub fn parsed_ingredients(&self) -> Vec<Result<Ingredient, ScrapeError>> {
+        self.parsed_ingredients_with(&self.ingredient_parser())
+    }
+
+    /// Each of `ingredients` parsed by `parser` according to its [ParseMode](ingredient::ParseMode),
+    /// e.g. with [ParseMode::Lenient](ingredient::ParseMode::Lenient) none of them fail.
+    pub fn parsed_ingredients_with(
+        &self,
+        parser: &IngredientParser,
+    ) -> Vec<Result<Ingredient, ScrapeError>> {
+        self.ingredients
+            .iter()
+            .map(|i| {
+                parser
+                    .parse(i)
+                    .map_err(|e| ScrapeError::Parse(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// It as the `ingredient` crate's [Recipe], with its ingredients parsed
+    /// (in its language, see [ScrapedRecipe::ingredient_parser]).
+    pub fn to_recipe(&self) -> Recipe {
+        let ip = self.ingredient_parser();
+        let parse = |lines: &[String]| -> Vec<Ingredient> {
+            lines.iter().map(|i| ip.clone().from_str(i)).collect()
+        };
+        let ingredient_groups = match self.ingredient_groups.is_empty() {
+            true => vec![(None, parse(&self.ingredients))],
+            false => self
+                .ingredient_groups
+                .iter()
+                .map(|(name, lines)| (name.clone(), parse(lines)))
+                .collect(),
+        };
+        Recipe {
+            name: self.name.clone(),
+            ingredient_groups,
+            steps: self.instructions.clone(),
+            yield_: self.yield_.as_ref().and_then(|y| y.parsed.clone()),
+            prep_time: self.prep_time,
+            cook_time: self.cook_time,
+            total_time: self.total_time,
+            url: Some(
+                self.canonical_url
+                    .clone()
+                    .unwrap_or_else(|| self.url.clone()),
+            ),
+            image: self.image.clone(),
+        }
+    }
 }
 // inspiration
 // https://github.com/pombadev/sunny/blob/main/src/lib/spider.rs
 // https://github.com/megametres/recettes-api/blob/dev/src/html_parser/mod.rs
 
+/// Scrapes with the default [Scraper], see [Scraper::register] for adding sites to it.
 pub fn scrape(body: &str, url: &str) -> Result<ScrapedRecipe, ScrapeError> {
-    info!("scraping {} from {}", body.len(), url);
-    let dom = Html::parse_document(body);
-    let res = match extract_ld(dom.clone()) {
-        Ok(ld_schemas) => {
-            let items = ld_schemas.len();
-            match ld_schemas
-                .into_iter()
-                .map(|ld| scrape_from_json(ld.as_str(), url))
-                // .collect::<Vec<Result<ScrapedRecipe, ScrapeError>>>()
-                .find_map(Result::ok)
-            {
-                Some(r) => Ok(r),
-                None => Err(ScrapeError::LDJSONMissingRecipe(url.to_string(), items)),
-            }
-        }
-        Err(e) => match e {
-            ScrapeError::NoLDJSON(_) => scrape_from_html(dom),
-            _ => Err(e),
-        },
+    Scraper::default().scrape(body, url)
+}
+
+/// Every recipe on the page, for roundups and `@graph`s with several recipes,
+/// where [scrape] would only return the first.
+/// Pages without any structured recipes are scraped as with [scrape].
+pub fn scrape_all(body: &str, url: &str) -> Result<Vec<ScrapedRecipe>, ScrapeError> {
+    Scraper::default().scrape_all(body, url)
+}
+fn scrape_all_from_json(json: &str, url: &str) -> Vec<ScrapedRecipe> {
+    let raw = match serde_json::from_str::<Value>(json) {
+        Ok(raw) => raw,
+        Err(_) => return vec![],
     };
-    match res {
-        Ok(mut r) => {
-            r.ingredients = r.ingredients.into_iter().map(clean_string).collect();
-            r.instructions = r.instructions.into_iter().map(clean_string).collect();
-            Ok(r)
+    let recipes: Vec<ScrapedRecipe> = ld_schema::find_recipes(&raw)
+        .into_iter()
+        .filter_map(|node| {
+            let r: ld_schema::RootRecipe = serde_json::from_value(node.clone()).ok()?;
+            Some(ld_schema::RootRecipe {
+                raw: Some(node.clone()),
+                ..r
+            })
+        })
+        .map(|r| normalize_root_recipe(r, url))
+        .collect();
+    match recipes.is_empty() {
+        // recipes without an `@type`
+        true => recipe_from_json(json, url).into_iter().collect(),
+        false => recipes,
+    }
+}
+fn clean_recipe(mut r: ScrapedRecipe) -> ScrapedRecipe {
+    r.ingredients = r.ingredients.into_iter().map(clean_string).collect();
+    if r.ingredient_groups.is_empty() {
+        group_ingredients(&mut r);
+    }
+    for (_, lines) in r.ingredient_groups.iter_mut() {
+        *lines = lines.drain(..).map(clean_string).collect();
+    }
+    r.clean_instructions();
+    r
+}
+// sites often put their headings in with the ingredients, e.g. `For the dough:`,
+// which are taken out of them into `ingredient_groups`
+fn group_ingredients(r: &mut ScrapedRecipe) {
+    if !r
+        .ingredients
+        .iter()
+        .any(|i| ingredient_heading(i).is_some())
+    {
+        return;
+    }
+    let mut groups: Vec<(Option<String>, Vec<String>)> = vec![];
+    for line in r.ingredients.drain(..) {
+        match ingredient_heading(&line) {
+            Some(heading) => groups.push((Some(heading), vec![])),
+            None => match groups.last_mut() {
+                Some((_, lines)) => lines.push(line),
+                None => groups.push((None, vec![line])),
+            },
         }
-        Err(e) => Err(e),
     }
+    r.ingredients = groups.iter().flat_map(|(_, l)| l.clone()).collect();
+    r.ingredient_groups = groups;
+}
+// `For the dough:`, or `For serving`, but not `1 cup flour:` or `Salt, for seasoning`
+fn ingredient_heading(line: &str) -> Option<String> {
+    let line = line.trim().trim_matches('*').trim();
+    let is_heading = !line.is_empty()
+        && line.len() <= 60
+        && !line.chars().any(|c| c.is_ascii_digit())
+        && (line.ends_with(':') || line.to_lowercase().starts_with("for "));
+    is_heading.then(|| line.trim_end_matches(':').trim().to_string())
 }
 fn clean_string(i: String) -> String {
     i.replace("&nbsp;", " ").replace('\n', " ")
 }
+/// Each of the page's ld+json scripts that's valid json, in order, for reading into the
+/// [ld_schema] types or your own. Pages' recipes are usually in one of them,
+/// see [ld_schema::find_recipe].
+pub fn extract_ld_json(body: &str) -> Vec<Value> {
+    scan::ld_json_scripts(body)
+        .into_iter()
+        .filter_map(|json| serde_json::from_str(json).ok())
+        .collect()
+}
+/// Where the page at `url` links to its AMP (`<link rel="amphtml">`) and print versions,
+/// e.g. a WPRM or Tasty Recipes card's print button, AMP first.
+/// They're usually lighter to fetch and scrape, with the same recipe and less else.
+/// ```
+/// let page = r#"<link rel="amphtml" href="/toast/amp/">
+/// <a class="wprm-recipe-print" href="https://example.com/wprm_print/42">Print</a>"#;
+/// assert_eq!(
+///     recipe_scraper::lighter_variants(page, "https://example.com/toast/"),
+///     vec!["https://example.com/toast/amp/", "https://example.com/wprm_print/42"]
+/// );
+/// ```
+pub fn lighter_variants(body: &str, url: &str) -> Vec<String> {
+    let dom = Html::parse_document(body);
+    let selectors = [
+        r#"link[rel="amphtml"]"#,
+        r#"a.wprm-recipe-print, a.tasty-recipes-print-button, a[href*="/wprm_print/"], a[href*="/print/"]"#,
+    ]
+    .map(|s| Selector::parse(s).unwrap());
+    let mut variants: Vec<String> = vec![];
+    for href in selectors
+        .iter()
+        .flat_map(|s| dom.select(s))
+        .filter_map(|e| e.value().attr("href"))
+        .map(str::trim)
+        // print buttons that open a dialog instead
+        .filter(|href| {
+            !href.is_empty() && !href.starts_with('#') && !href.starts_with("javascript:")
+        })
+    {
+        let variant = resolve_url(url, href);
+        if variant != url && !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+    variants
+}
+/// Scrapes a recipe's ld+json, guessing its language from its text when it doesn't say.
 pub fn scrape_from_json(json: &str, url: &str) -> Result<ScrapedRecipe, ScrapeError> {
+    let mut r = recipe_from_json(json, url)?;
+    r.language = r.language.take().or_else(|| language::guess_language(&r));
+    Ok(r)
+}
+// without guessing its language, for pages that may say what it is
+fn recipe_from_json(json: &str, url: &str) -> Result<ScrapedRecipe, ScrapeError> {
     normalize_ld_json(parse_ld_json(json.to_owned())?, url)
 }
 
 #[tracing::instrument]
 fn normalize_root_recipe(ld_schema: ld_schema::RootRecipe, url: &str) -> ScrapedRecipe {
-    ScrapedRecipe {
-        ingredients: ld_schema.recipe_ingredient,
-        instructions: match ld_schema.recipe_instructions {
-            ld_schema::InstructionWrapper::A(a) => a.into_iter().map(|i| i.text).collect(),
-            ld_schema::InstructionWrapper::B(b) => b
-                .into_iter()
-                .flat_map(|i| match i {
-                    ld_schema::BOrWrapper::B(b) => b
-                        .item_list_element
-                        .iter()
-                        .map(|i| i.text.clone().unwrap())
-                        .collect(),
-                    ld_schema::BOrWrapper::Wrapper(w) => {
-                        vec![w.text.unwrap()]
-                    }
-                })
-                .collect(),
+    let mut step_groups = vec![];
+    let steps: Vec<ScrapedStep> = match ld_schema.recipe_instructions {
+        ld_schema::InstructionWrapper::A(a) => a.into_iter().map(ScrapedStep::from_ld).collect(),
+        ld_schema::InstructionWrapper::B(b) => {
+            flatten_sections(b, None, &mut step_groups);
+            step_groups
+                .iter()
+                .flat_map(|(_, steps)| steps.clone())
+                .collect()
+        }
 
-            ld_schema::InstructionWrapper::C(c) => {
-                let selector = Selector::parse("p").unwrap();
+        ld_schema::InstructionWrapper::C(c) => {
+            let selector = Selector::parse("p").unwrap();
 
-                Html::parse_fragment(c.as_ref())
-                    .select(&selector)
-                    .map(|i| i.text().collect::<Vec<_>>().join(""))
-                    .collect::<Vec<_>>()
-            }
-            ld_schema::InstructionWrapper::D(d) => {
-                d[0].clone().into_iter().map(|i| i.text).collect()
+            Html::parse_fragment(c.as_ref())
+                .select(&selector)
+                .map(|i| ScrapedStep::new(i.text().collect::<Vec<_>>().join("")))
+                .collect::<Vec<_>>()
+        }
+        ld_schema::InstructionWrapper::D(d) => d
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+            .into_iter()
+            .map(ScrapedStep::from_ld)
+            .collect(),
+    };
+    let mut ingredient_groups: Vec<(Option<String>, Vec<String>)> = vec![];
+    for line in ld_schema.recipe_ingredient {
+        match line {
+            ld_schema::IngredientLine::Line(l) => match ingredient_groups.last_mut() {
+                Some((None, lines)) => lines.push(l),
+                _ => ingredient_groups.push((None, vec![l])),
+            },
+            ld_schema::IngredientLine::Section(s) => {
+                let name = s
+                    .name
+                    .map(|n| n.trim().to_string())
+                    .filter(|n| !n.is_empty());
+                ingredient_groups.push((name, s.item_list_element));
             }
-        },
+        }
+    }
+    let ingredients = ingredient_groups
+        .iter()
+        .flat_map(|(_, lines)| lines.clone())
+        .collect();
+    if ingredient_groups.iter().all(|(name, _)| name.is_none()) {
+        ingredient_groups = vec![];
+    }
+    let instruction_groups = match step_groups.iter().all(|(name, _)| name.is_none()) {
+        true => vec![],
+        false => step_groups
+            .into_iter()
+            .map(|(name, steps)| (name, steps.into_iter().map(|s| s.text).collect()))
+            .collect(),
+    };
 
+    ScrapedRecipe {
+        ingredients,
+        ingredient_groups,
+        instructions: steps.iter().map(|s| s.text.clone()).collect(),
+        steps,
         name: ld_schema.name,
         url: url.to_string(),
-        image: match ld_schema.image {
-            Some(image) => match image {
-                ld_schema::ImageOrList::Url(i) => Some(i),
-                ld_schema::ImageOrList::List(l) => Some(l[0].url.clone()),
-                ld_schema::ImageOrList::UrlList(i) => Some(i[0].clone()),
-                ld_schema::ImageOrList::Image(i) => Some(i.url),
-            },
-            None => None,
-        },
+        canonical_url: None,
+        image: ld_schema.image.and_then(image_url),
+        description: ld_schema
+            .description
+            .as_ref()
+            .and_then(Value::as_str)
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty()),
+        notes: ld_schema.raw.as_ref().and_then(ld_notes),
+        language: ld_language(&ld_schema.in_language),
+        instruction_groups,
+        yield_: ld_schema
+            .recipe_yield
+            .as_ref()
+            .and_then(RecipeYield::from_ld),
+        prep_time: ld_duration(&ld_schema.prep_time),
+        cook_time: ld_duration(&ld_schema.cook_time),
+        total_time: ld_duration(&ld_schema.total_time),
+        author: ld_author(&ld_schema.author),
+        rating: ld_schema.aggregate_rating.as_ref().and_then(ld_rating),
+        video: ld_schema.video.as_ref().and_then(ld_video),
+        cuisine: ld_strings(&ld_schema.recipe_cuisine),
+        category: ld_strings(&ld_schema.recipe_category),
+        keywords: ld_strings(&ld_schema.keywords),
+        equipment: ld_names(&ld_schema.tool),
+        raw: ld_schema.raw,
+    }
+}
+// the recipe's `comment` when it's written by its author, as text rather than the Comments
+// of readers
+fn ld_notes(recipe: &Value) -> Option<String> {
+    let notes = match recipe.get("comment")? {
+        Value::String(s) => vec![s.as_str()],
+        Value::Array(a) => a.iter().filter_map(Value::as_str).collect(),
+        _ => return None,
+    };
+    Some(
+        notes
+            .iter()
+            .map(|n| n.trim())
+            .filter(|n| !n.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+    .filter(|n| !n.is_empty())
+}
+// a language code, or a Language with one as its `alternateName`
+fn ld_language(v: &Option<Value>) -> Option<String> {
+    let code = match v {
+        Some(Value::String(s)) => s.as_str(),
+        Some(Value::Object(o)) => o.get("alternateName")?.as_str()?,
+        _ => return None,
+    };
+    Some(code.trim().to_string()).filter(|c| !c.is_empty())
+}
+// a list of strings, where each string may be a comma separated list itself
+fn ld_strings(v: &Option<Value>) -> Vec<String> {
+    let values = match v {
+        Some(Value::Array(a)) => a.iter().filter_map(Value::as_str).collect(),
+        Some(Value::String(s)) => vec![s.as_str()],
+        _ => vec![],
+    };
+    values
+        .into_iter()
+        .flat_map(|s| s.split(','))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+// several authors are joined, e.g. `Jane Doe, John Doe`
+fn ld_author(v: &Option<Value>) -> Option<String> {
+    Some(ld_names(v).join(", ")).filter(|n| !n.is_empty())
+}
+// things that are either a name, or an object with a `name` such as a Person or HowToTool,
+// or a list of those
+fn ld_names(v: &Option<Value>) -> Vec<String> {
+    let name = |v: &Value| match v {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Object(o) => o
+            .get("name")
+            .and_then(Value::as_str)
+            .map(|s| s.trim().to_string()),
+        _ => None,
+    };
+    match v {
+        Some(Value::Array(a)) => a.iter().filter_map(name).collect(),
+        Some(v) => name(v).into_iter().collect(),
+        None => vec![],
+    }
+    .into_iter()
+    .filter(|n| !n.is_empty())
+    .collect()
+}
+fn ld_rating(v: &Value) -> Option<Rating> {
+    let number = |name: &str| match v.get(name)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    Some(Rating {
+        value: number("ratingValue")?,
+        count: number("ratingCount")
+            .or_else(|| number("reviewCount"))
+            .map(|c| c as u64),
+    })
+}
+// the first VideoObject with a url
+fn ld_video(v: &Value) -> Option<RecipeVideo> {
+    if let Value::Array(a) = v {
+        return a.iter().find_map(ld_video);
+    }
+    let string = |name: &str| {
+        v.get(name)
+            .and_then(Value::as_str)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let video = RecipeVideo {
+        name: string("name"),
+        content_url: string("contentUrl"),
+        embed_url: string("embedUrl"),
+        thumbnail: v
+            .get("thumbnailUrl")
+            .cloned()
+            .and_then(|t| serde_json::from_value(t).ok())
+            .and_then(image_url),
+    };
+    (video.content_url.is_some() || video.embed_url.is_some()).then_some(video)
+}
+// malformed durations are left out rather than failing the whole recipe
+fn ld_duration(v: &Option<Value>) -> Option<Duration> {
+    match v {
+        Some(Value::String(s)) => duration::parse_duration(s),
+        _ => None,
+    }
+}
+fn image_url(image: ld_schema::ImageOrList) -> Option<String> {
+    match image {
+        ld_schema::ImageOrList::Url(i) => Some(i),
+        ld_schema::ImageOrList::List(l) => l.into_iter().next().map(|i| i.url),
+        ld_schema::ImageOrList::UrlList(i) => i.into_iter().next(),
+        ld_schema::ImageOrList::Image(i) => Some(i.url),
+    }
+}
+// `HowToSection`s can hold steps as well as other sections, steps go under the innermost name
+fn flatten_sections(
+    items: Vec<ld_schema::BOrWrapper>,
+    section: Option<String>,
+    groups: &mut Vec<(Option<String>, Vec<ScrapedStep>)>,
+) {
+    for item in items {
+        match item {
+            ld_schema::BOrWrapper::B(b) => {
+                let name = Some(b.name).filter(|n| !n.is_empty()).or(section.clone());
+                flatten_sections(b.item_list_element, name, groups);
+            }
+            ld_schema::BOrWrapper::Wrapper(w) => {
+                // a step without `text` might still have been written in its `name`
+                if let Some(step) = ScrapedStep::from_list_item(w) {
+                    match groups.last_mut() {
+                        Some((name, steps)) if *name == section => steps.push(step),
+                        _ => groups.push((section.clone(), vec![step])),
+                    }
+                }
+            }
+        }
     }
 }
 #[tracing::instrument]
@@ -155,64 +712,100 @@ fn normalize_ld_json(
     ld_schema_a: ld_schema::Root,
     url: &str,
 ) -> Result<ScrapedRecipe, ScrapeError> {
-    match ld_schema_a {
-        ld_schema::Root::List(mut l) => Ok(normalize_root_recipe(l.pop().unwrap(), url)),
-        ld_schema::Root::Recipe(ld_schema) => Ok(normalize_root_recipe(ld_schema, url)),
-        ld_schema::Root::Graph(g) => {
-            let items = g.graph.len();
-            let recipe = g.graph.iter().find_map(|d| match d {
-                ld_schema::Graph::Recipe(a) => Some(a.to_owned()),
-                _ => None,
-            });
-            match recipe {
-                Some(r) => Ok(normalize_root_recipe(r, url)),
-                None => Err(ScrapeError::LDJSONMissingRecipe(
-                    "failed to find recipe in ld json graph".to_string(),
-                    items,
-                )),
-            }
-        }
+    let items = match ld_schema_a {
+        ld_schema::Root::Recipe(ld_schema) => return Ok(normalize_root_recipe(*ld_schema, url)),
+        ld_schema::Root::List(l) => l,
+        ld_schema::Root::Graph(g) => g.graph,
+    };
+    let recipe = items.iter().find_map(|d| match d {
+        ld_schema::Graph::Recipe(a) => Some(a.as_ref().to_owned()),
+        _ => None,
+    });
+    match recipe {
+        Some(r) => Ok(normalize_root_recipe(r, url)),
+        None => Err(ScrapeError::LDJSONMissingRecipe(
+            url.to_string(),
+            items.len(),
+        )),
     }
 }
-fn scrape_from_html(dom: Html) -> Result<ScrapedRecipe, ScrapeError> {
-    // smitten kitchen
-    let ingredient_selector = Selector::parse("li.jetpack-recipe-ingredient").unwrap();
-    let ingredients = dom
-        .select(&ingredient_selector)
-        .map(|i| i.text().collect::<Vec<_>>().join(""))
-        .collect::<Vec<String>>();
-
-    let ul_selector = Selector::parse(r#"div.jetpack-recipe-directions"#).unwrap();
-
-    let instruction_list_item_elem = match dom.select(&ul_selector).next() {
-        Some(x) => x,
-        None => return Err(ScrapeError::Parse("no ld json or parsed html".to_string())),
+// what the page says about itself in its OpenGraph tags (or `<title>`),
+// for whatever the recipe didn't have
+fn fill_from_meta(dom: &Html, mut r: ScrapedRecipe) -> ScrapedRecipe {
+    if r.name.trim().is_empty() {
+        r.name = meta_content(dom, "og:title")
+            .or_else(|| {
+                let title_selector = Selector::parse("title").unwrap();
+                dom.select(&title_selector)
+                    .next()
+                    .map(|t| t.text().collect::<String>().trim().to_string())
+            })
+            .unwrap_or_default();
+    }
+    r.image = r.image.or_else(|| og_image(dom));
+    r.description = r
+        .description
+        .or_else(|| meta_content(dom, "og:description"));
+    let html_selector = Selector::parse("html").unwrap();
+    r.language = r
+        .language
+        .take()
+        .or_else(|| {
+            dom.select(&html_selector)
+                .find_map(|h| h.value().attr("lang"))
+                .map(str::trim)
+                .filter(|lang| !lang.is_empty())
+                .map(str::to_string)
+        })
+        .or_else(|| language::guess_language(&r));
+    let canonical_selector = Selector::parse(r#"link[rel="canonical"]"#).unwrap();
+    r.canonical_url = r.canonical_url.or_else(|| {
+        dom.select(&canonical_selector)
+            .find_map(|l| l.value().attr("href"))
+            .map(str::trim)
+            .filter(|href| !href.is_empty())
+            .map(|href| resolve_url(&r.url, href))
+            .or_else(|| meta_content(dom, "og:url"))
+    });
+    r
+}
+// `href` relative to the page at `base`
+fn resolve_url(base: &str, href: &str) -> String {
+    let (scheme, rest) = match base.split_once("://") {
+        Some(split) => split,
+        None => return href.to_string(),
     };
-
-    let instructions = instruction_list_item_elem
-        .text()
-        .collect::<Vec<_>>()
-        .join("")
-        .split('\n')
-        .map(|s| s.into())
-        .collect::<Vec<String>>();
-
-    let image_selector = Selector::parse(r#"meta[property="og:image"]"#).unwrap();
-    let image = dom
-        .select(&image_selector)
+    let origin_len = scheme.len() + 3 + rest.find('/').unwrap_or(rest.len());
+    if href.contains("://") {
+        href.to_string()
+    } else if href.starts_with("//") {
+        format!("{scheme}:{href}")
+    } else if href.starts_with('/') {
+        format!("{}{href}", &base[..origin_len])
+    } else {
+        let dir = match base[origin_len..].rfind('/') {
+            Some(i) => &base[..origin_len + i + 1],
+            None => &base[..origin_len],
+        };
+        match dir.ends_with('/') {
+            true => format!("{dir}{href}"),
+            false => format!("{dir}/{href}"),
+        }
+    }
+}
+// the page's preview image, for recipes that don't have their own
+fn og_image(dom: &Html) -> Option<String> {
+    meta_content(dom, "og:image")
+}
+fn meta_content(dom: &Html, property: &str) -> Option<String> {
+    let selector = Selector::parse(&format!(r#"meta[property="{property}"]"#)).unwrap();
+    dom.select(&selector)
         .next()
-        .map(|i| i.value().attr("content").unwrap().to_string());
-
-    Ok(dbg!(ScrapedRecipe {
-        ingredients,
-        instructions,
-        name: "".to_string(),
-        url: "".to_string(),
-        image,
-    }))
-    // Err(ScrapeError::Parse("foo".to_string()))
-}
-fn extract_ld(dom: Html) -> Result<Vec<String>, ScrapeError> {
+        .and_then(|i| i.value().attr("content"))
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+}
+fn extract_ld(dom: &Html) -> Result<Vec<String>, ScrapeError> {
     let selector = match Selector::parse("script[type='application/ld+json']") {
         Ok(s) => s,
         Err(e) => return Err(ScrapeError::Parse(format!("{e:?}"))),
@@ -220,7 +813,6 @@ fn extract_ld(dom: Html) -> Result<Vec<String>, ScrapeError> {
 
     let json_chunks: Vec<String> = dom
         .select(&selector)
-        .into_iter()
         .map(|element| element.inner_html())
         .collect();
     match json_chunks.len() {
@@ -233,8 +825,18 @@ fn extract_ld(dom: Html) -> Result<Vec<String>, ScrapeError> {
 fn parse_ld_json(json: String) -> Result<ld_schema::Root, ScrapeError> {
     let json = json.as_str();
     let raw = serde_json::from_str::<Value>(json)?;
+    // going by `@type` first finds recipes that don't fit the shape of [ld_schema::Root],
+    // e.g. in a `@graph` without a `@context`
+    if let Some(recipe) = ld_schema::find_recipe(&raw) {
+        if let Ok(r) = serde_json::from_value::<ld_schema::RootRecipe>(recipe.clone()) {
+            return Ok(ld_schema::Root::Recipe(Box::new(ld_schema::RootRecipe {
+                raw: Some(recipe.clone()),
+                ..r
+            })));
+        }
+    }
     // tracing::info!("raw json: {:#?}", raw);
-    let v: ld_schema::Root = match serde_json::from_str(json) {
+    let mut v: ld_schema::Root = match serde_json::from_str(json) {
         Ok(v) => v,
         Err(e) => {
             error!(
@@ -244,7 +846,18 @@ fn parse_ld_json(json: String) -> Result<ld_schema::Root, ScrapeError> {
             return Err(ScrapeError::Deserialize(e));
         }
     };
-
+    match &mut v {
+        ld_schema::Root::Recipe(r) => r.raw = Some(raw),
+        ld_schema::Root::List(items)
+        | ld_schema::Root::Graph(ld_schema::RootGraph { graph: items, .. }) => {
+            let nodes = raw.get("@graph").unwrap_or(&raw).as_array();
+            for (item, node) in items.iter_mut().zip(nodes.into_iter().flatten()) {
+                if let ld_schema::Graph::Recipe(r) = item {
+                    r.raw = Some(node.clone());
+                }
+            }
+        }
+    }
     Ok(v)
 }
 #[cfg(test)]
@@ -264,13 +877,88 @@ mod tests {
                 .to_string()
             )
             .unwrap(),
-            crate::ld_schema::Root::Recipe(crate::ld_schema::RootRecipe {
+            crate::ld_schema::Root::Recipe(Box::new(crate::ld_schema::RootRecipe {
                 context: None,
                 name: "".to_string(),
                 image: None,
+                description: None,
+                in_language: None,
+                recipe_yield: None,
+                prep_time: None,
+                cook_time: None,
+                total_time: None,
+                author: None,
+                tool: None,
+                recipe_cuisine: None,
+                recipe_category: None,
+                keywords: None,
+                aggregate_rating: None,
+                video: None,
                 recipe_ingredient: vec![],
                 recipe_instructions: InstructionWrapper::A(vec![]),
-            })
+                raw: Some(serde_json::json!({
+                    "name": "",
+                    "recipeIngredient": [],
+                    "recipeInstructions": []
+                })),
+            }))
+        );
+    }
+    #[test]
+    fn test_ld_notes() {
+        use serde_json::json;
+        assert_eq!(
+            crate::ld_notes(&json!({"comment": ["Use ripe pears. ", "Keeps for a day."]})),
+            Some("Use ripe pears.\nKeeps for a day.".to_string())
+        );
+        // readers' Comments aren't the author's
+        assert_eq!(
+            crate::ld_notes(&json!({"comment": {"@type": "Comment", "text": "Yum!"}})),
+            None
+        );
+        assert_eq!(crate::ld_notes(&json!({"comment": " "})), None);
+    }
+    #[test]
+    fn test_resolve_url() {
+        let base = "https://example.com/2018/04/toast/?utm_source=x";
+        assert_eq!(
+            crate::resolve_url(base, "https://www.example.com/toast/"),
+            "https://www.example.com/toast/"
+        );
+        assert_eq!(
+            crate::resolve_url(base, "//example.com/toast/"),
+            "https://example.com/toast/"
+        );
+        assert_eq!(
+            crate::resolve_url(base, "/toast/"),
+            "https://example.com/toast/"
+        );
+        assert_eq!(
+            crate::resolve_url(base, "amp"),
+            "https://example.com/2018/04/toast/amp"
         );
+        assert_eq!(
+            crate::resolve_url("https://example.com", "toast"),
+            "https://example.com/toast"
+        );
+    }
+    #[test]
+    fn test_to_recipe() {
+        let scraped = crate::ScrapedRecipe {
+            name: "Toast".to_string(),
+            url: "https://example.com/toast?utm_source=x".to_string(),
+            canonical_url: Some("https://example.com/toast".to_string()),
+            ingredients: vec!["2 slices bread".to_string(), "1 tbsp butter".to_string()],
+            instructions: vec!["Toast the bread.".to_string()],
+            yield_: crate::RecipeYield::from_ld(&serde_json::json!("Serves 2")),
+            ..Default::default()
+        };
+        let recipe = scraped.to_recipe();
+        assert_eq!(recipe.ingredients().count(), 2);
+        assert_eq!(recipe.ingredient_groups[0].0, None);
+        assert_eq!(recipe.ingredient_groups[0].1[1].name, "butter");
+        assert_eq!(recipe.steps, vec!["Toast the bread."]);
+        assert_eq!(recipe.yield_.unwrap().value, 2.0);
+        assert_eq!(recipe.url.as_deref(), Some("https://example.com/toast"));
     }
 }
@@ -0,0 +1,264 @@
+//! Writing a [ScrapedRecipe] as the JSON that self-hosted recipe managers
+//! ([Mealie](https://mealie.io), [Tandoor](https://tandoor.dev)) create recipes from.
+use std::time::Duration;
+
+use ingredient::ingredient::Ingredient;
+use serde_json::{json, Value};
+
+use crate::ScrapedRecipe;
+
+type Groups = Vec<(Option<String>, Vec<String>)>;
+
+impl ScrapedRecipe {
+    /// A Mealie recipe, for updating one created through its api (`PUT /api/recipes/{slug}`).
+    /// Ingredients are parsed into quantity, unit and food, keeping the line as it was
+    /// as their `originalText`. The first ingredient and step of each section
+    /// has its heading as `title`.
+    pub fn to_mealie_json(&self) -> Value {
+        let ip = self.ingredient_parser();
+        let ingredients: Vec<Value> = groups(&self.ingredient_groups, &self.ingredients)
+            .into_iter()
+            .flat_map(|(title, lines)| {
+                let ip = ip.clone();
+                lines.into_iter().enumerate().map(move |(n, line)| {
+                    let i = ip.clone().from_str(&line);
+                    let (quantity, unit) = amount(&i);
+                    json!({
+                        "title": title.clone().filter(|_| n == 0),
+                        "quantity": quantity.unwrap_or_default(),
+                        "unit": unit.map(|name| json!({"name": name})),
+                        "food": match i.name.is_empty() {
+                            true => Value::Null,
+                            false => json!({"name": i.name}),
+                        },
+                        "note": i.modifier.unwrap_or_default(),
+                        "display": line,
+                        "originalText": line,
+                        "disableAmount": false,
+                    })
+                })
+            })
+            .collect();
+        let instructions: Vec<Value> = groups(&self.instruction_groups, &self.instructions)
+            .into_iter()
+            .flat_map(|(title, steps)| {
+                steps.into_iter().enumerate().map(move |(n, text)| {
+                    let title = title.clone().filter(|_| n == 0).unwrap_or_default();
+                    json!({"title": title, "text": text})
+                })
+            })
+            .collect();
+        let names = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| json!({"name": name}))
+                .collect::<Vec<_>>()
+        };
+        json!({
+            "name": self.name,
+            "description": self.description.clone().unwrap_or_default(),
+            "orgURL": self.canonical_url.as_ref().unwrap_or(&self.url),
+            "image": self.image,
+            "recipeYield": self.yield_.as_ref().map(|y| y.raw.clone()),
+            "prepTime": self.prep_time.map(time),
+            "performTime": self.cook_time.map(time),
+            "totalTime": self.total_time.map(time),
+            "recipeCategory": names(&self.category),
+            "tags": names(&self.keywords),
+            "tools": names(&self.equipment),
+            "recipeIngredient": ingredients,
+            "recipeInstructions": instructions,
+        })
+    }
+
+    /// A Tandoor recipe, for creating it through its api (`POST /api/recipe/`).
+    /// All the ingredients are in the first step, with headings as `is_header` ingredients,
+    /// as Tandoor does with recipes it imports itself.
+    pub fn to_tandoor_json(&self) -> Value {
+        let ip = self.ingredient_parser();
+        let mut ingredients = vec![];
+        for (heading, lines) in groups(&self.ingredient_groups, &self.ingredients) {
+            if let Some(heading) = heading {
+                ingredients.push(json!({
+                    "food": null,
+                    "unit": null,
+                    "amount": 0,
+                    "note": heading,
+                    "is_header": true,
+                    "no_amount": true,
+                }));
+            }
+            for line in lines {
+                let i = ip.clone().from_str(&line);
+                let (amount, unit) = amount(&i);
+                ingredients.push(json!({
+                    "food": {"name": match i.name.is_empty() {
+                        true => line.clone(),
+                        false => i.name,
+                    }},
+                    "unit": unit.map(|name| json!({"name": name})),
+                    "amount": amount.unwrap_or_default(),
+                    "note": i.modifier.unwrap_or_default(),
+                    "original_text": line,
+                    "is_header": false,
+                    "no_amount": amount.is_none(),
+                }));
+            }
+        }
+        let mut steps: Vec<Value> = groups(&self.instruction_groups, &self.instructions)
+            .into_iter()
+            .flat_map(|(name, steps)| {
+                steps.into_iter().enumerate().map(move |(n, text)| {
+                    let name = name.clone().filter(|_| n == 0).unwrap_or_default();
+                    json!({"name": name, "instruction": text, "ingredients": []})
+                })
+            })
+            .collect();
+        match steps.first_mut() {
+            Some(first) => first["ingredients"] = json!(ingredients),
+            None if !ingredients.is_empty() => {
+                steps.push(json!({"name": "", "instruction": "", "ingredients": ingredients}))
+            }
+            None => {}
+        }
+        let yield_ = self.yield_.as_ref().and_then(|y| y.parsed.as_ref());
+        // the longest Tandoor allows
+        let description: Option<String> = self
+            .description
+            .as_ref()
+            .map(|d| d.chars().take(512).collect());
+        let keywords: Vec<Value> = self
+            .keywords
+            .iter()
+            .map(|name| json!({"name": name}))
+            .collect();
+        json!({
+            "name": self.name,
+            "description": description,
+            "source_url": self.canonical_url.as_ref().unwrap_or(&self.url),
+            "servings": yield_.map_or(1, |y| (y.value.round() as u32).max(1)),
+            "servings_text": yield_.map(|y| y.unit.clone()).unwrap_or_default(),
+            "working_time": self.prep_time.map_or(0, minutes),
+            "waiting_time": self.cook_time.map_or(0, minutes),
+            "keywords": keywords,
+            "internal": true,
+            "steps": steps,
+        })
+    }
+}
+
+// a single unnamed group when it doesn't have any
+fn groups(groups: &Groups, lines: &[String]) -> Groups {
+    match groups.is_empty() {
+        true => vec![(None, lines.to_vec())],
+        false => groups.clone(),
+    }
+}
+
+// the first amount, with no unit for `2 eggs`
+fn amount(i: &Ingredient) -> (Option<f64>, Option<String>) {
+    match i.amounts.first() {
+        Some(m) => {
+            let (value, _, unit) = m.values();
+            let unit = Some(unit).filter(|u| !(m.inferred_unit() || u.is_empty()));
+            (Some(value), unit)
+        }
+        None => (None, None),
+    }
+}
+
+fn minutes(d: Duration) -> u64 {
+    (d.as_secs_f64() / 60.0).round() as u64
+}
+
+fn time(d: Duration) -> String {
+    let m = minutes(d);
+    match (m / 60, m % 60) {
+        (0, m) => plural(m, "minute"),
+        (h, 0) => plural(h, "hour"),
+        (h, m) => format!("{} {}", plural(h, "hour"), plural(m, "minute")),
+    }
+}
+
+fn plural(n: u64, unit: &str) -> String {
+    match n {
+        1 => format!("1 {unit}"),
+        n => format!("{n} {unit}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RecipeYield;
+
+    fn toast() -> ScrapedRecipe {
+        ScrapedRecipe {
+            name: "Toast".to_string(),
+            url: "https://example.com/toast".to_string(),
+            ingredients: vec![
+                "2 slices bread".to_string(),
+                "1 tbsp butter, soft".to_string(),
+            ],
+            ingredient_groups: vec![
+                (None, vec!["2 slices bread".to_string()]),
+                (
+                    Some("Topping".to_string()),
+                    vec!["1 tbsp butter, soft".to_string()],
+                ),
+            ],
+            instructions: vec!["Toast the bread.".to_string(), "Butter it.".to_string()],
+            yield_: Some(RecipeYield {
+                raw: "Serves 2".to_string(),
+                parsed: ingredient::parse_yield("Serves 2").ok(),
+            }),
+            prep_time: Some(Duration::from_secs(90 * 60)),
+            keywords: vec!["breakfast".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_mealie_json() {
+        let mealie = toast().to_mealie_json();
+        assert_eq!(mealie["orgURL"], "https://example.com/toast");
+        assert_eq!(mealie["prepTime"], "1 hour 30 minutes");
+        assert_eq!(mealie["tags"], json!([{"name": "breakfast"}]));
+        assert_eq!(
+            mealie["recipeIngredient"][1],
+            json!({
+                "title": "Topping",
+                "quantity": 1.0,
+                "unit": {"name": "tbsp"},
+                "food": {"name": "butter"},
+                "note": "soft",
+                "display": "1 tbsp butter, soft",
+                "originalText": "1 tbsp butter, soft",
+                "disableAmount": false,
+            })
+        );
+        assert_eq!(mealie["recipeIngredient"][0]["title"], Value::Null);
+        assert_eq!(
+            mealie["recipeInstructions"],
+            json!([{"title": "", "text": "Toast the bread."}, {"title": "", "text": "Butter it."}])
+        );
+    }
+
+    #[test]
+    fn test_to_tandoor_json() {
+        let tandoor = toast().to_tandoor_json();
+        assert_eq!(tandoor["servings"], 2);
+        assert_eq!(tandoor["servings_text"], "servings");
+        assert_eq!(tandoor["working_time"], 90);
+        let steps = tandoor["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1]["ingredients"], json!([]));
+        let ingredients = steps[0]["ingredients"].as_array().unwrap();
+        assert_eq!(ingredients.len(), 3);
+        assert_eq!(ingredients[0]["food"]["name"], "bread");
+        assert_eq!(ingredients[0]["amount"], 2.0);
+        assert_eq!(ingredients[1]["is_header"], true);
+        assert_eq!(ingredients[1]["note"], "Topping");
+        assert_eq!(ingredients[2]["unit"]["name"], "tbsp");
+    }
+}
@@ -0,0 +1,237 @@
+//! Recipes marked up in html attributes instead of ld+json, which older sites use:
+//! schema.org microdata (`<div itemscope itemtype="https://schema.org/Recipe">`),
+//! RDFa (`<div vocab="https://schema.org/" typeof="Recipe">`) or the
+//! microformats2 classes (`<div class="h-recipe">`).
+//! They are read into the ld+json types so they go through the same normalization.
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+
+use crate::{
+    ld_schema::{ImageOrList, IngredientLine, InstructionWrapper, RecipeInstructionA, RootRecipe},
+    normalize_root_recipe, og_image, ScrapedRecipe,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Markup {
+    Microdata,
+    Rdfa,
+    HRecipe,
+}
+
+impl Markup {
+    fn recipe_selector(&self) -> &'static str {
+        match self {
+            Markup::Microdata => r#"[itemscope][itemtype$="schema.org/Recipe"]"#,
+            Markup::Rdfa => r#"[typeof~="Recipe"], [typeof~="schema:Recipe"]"#,
+            Markup::HRecipe => ".h-recipe",
+        }
+    }
+    // what a schema.org property is selected by, e.g. `[itemprop~="name"]`
+    fn prop_selector(&self, name: &str) -> Option<String> {
+        Some(match self {
+            Markup::Microdata => format!(r#"[itemprop~="{name}"]"#),
+            Markup::Rdfa => format!(r#"[property~="{name}"], [property~="schema:{name}"]"#),
+            Markup::HRecipe => match name {
+                "name" => ".p-name",
+                "recipeIngredient" => ".p-ingredient",
+                "recipeInstructions" => ".e-instructions",
+                "image" => ".u-photo",
+                "recipeYield" => ".p-yield",
+                "totalTime" => ".dt-duration",
+                "author" => ".p-author",
+                "recipeCategory" => ".p-category",
+                "description" => ".p-summary",
+                _ => return None,
+            }
+            .to_string(),
+        })
+    }
+    // whether the element starts a nested item, whose properties aren't the recipe's
+    fn starts_item(&self, e: ElementRef) -> bool {
+        let e = e.value();
+        match self {
+            Markup::Microdata => e.attr("itemscope").is_some(),
+            Markup::Rdfa => e.attr("typeof").is_some(),
+            Markup::HRecipe => e.classes().any(|c| c.starts_with("h-")),
+        }
+    }
+    // where a link (image) is held, instead of the element text
+    fn link_attrs(&self) -> &'static [&'static str] {
+        match self {
+            Markup::Microdata => &["src", "content", "href"],
+            Markup::Rdfa => &["src", "resource", "content", "href"],
+            Markup::HRecipe => &["src", "href"],
+        }
+    }
+
+    pub(crate) fn scrape(&self, dom: &Html, url: &str) -> Option<ScrapedRecipe> {
+        self.scrape_all(dom, url).into_iter().next()
+    }
+
+    pub(crate) fn scrape_all(&self, dom: &Html, url: &str) -> Vec<ScrapedRecipe> {
+        let recipe_selector = Selector::parse(self.recipe_selector()).unwrap();
+        dom.select(&recipe_selector)
+            .filter_map(|recipe| self.extract(dom, recipe))
+            .map(|r| normalize_root_recipe(r, url))
+            .collect()
+    }
+
+    fn extract(&self, dom: &Html, recipe: ElementRef) -> Option<RootRecipe> {
+        // `ingredients` is the older name of `recipeIngredient`
+        let recipe_ingredient: Vec<IngredientLine> = self
+            .props(recipe, &["recipeIngredient", "ingredients"])
+            .into_iter()
+            .map(prop_value)
+            .filter(|i| !i.is_empty())
+            .map(IngredientLine::Line)
+            .collect();
+
+        let li_selector = Selector::parse("li").unwrap();
+        let instructions: Vec<RecipeInstructionA> = self
+            .props(recipe, &["recipeInstructions"])
+            .into_iter()
+            .flat_map(|i| match i.select(&li_selector).next() {
+                Some(_) => i.select(&li_selector).map(element_text).collect::<Vec<_>>(),
+                None => element_text(i)
+                    .split('\n')
+                    .map(|s| s.trim().to_string())
+                    .collect(),
+            })
+            .filter(|i| !i.is_empty())
+            .map(|text| RecipeInstructionA {
+                text,
+                ..Default::default()
+            })
+            .collect();
+        // partial markup (smitten kitchen only marks up ingredients) is left to the html scraper
+        if recipe_ingredient.is_empty() || instructions.is_empty() {
+            return None;
+        }
+
+        let image = self
+            .props(recipe, &["image"])
+            .into_iter()
+            .next()
+            .and_then(|i| {
+                self.link_attrs()
+                    .iter()
+                    .find_map(|a| i.value().attr(a))
+                    .map(str::to_string)
+            })
+            .or_else(|| og_image(dom))
+            .map(ImageOrList::Url);
+
+        Some(RootRecipe {
+            context: None,
+            name: self
+                .props(recipe, &["name"])
+                .into_iter()
+                .next()
+                .map(prop_value)
+                .unwrap_or_default(),
+            image,
+            description: self.prop_string(recipe, "description"),
+            in_language: self.prop_string(recipe, "inLanguage"),
+            recipe_yield: self.prop_string(recipe, "recipeYield"),
+            prep_time: self.prop_string(recipe, "prepTime"),
+            cook_time: self.prop_string(recipe, "cookTime"),
+            total_time: self.prop_string(recipe, "totalTime"),
+            author: self.named(recipe, "author"),
+            tool: self.named(recipe, "tool"),
+            recipe_cuisine: self.prop_list(recipe, "recipeCuisine"),
+            recipe_category: self.prop_list(recipe, "recipeCategory"),
+            keywords: self.prop_list(recipe, "keywords"),
+            aggregate_rating: self.item(
+                recipe,
+                "aggregateRating",
+                &["ratingValue", "ratingCount", "reviewCount"],
+            ),
+            video: self.item(
+                recipe,
+                "video",
+                &["name", "contentUrl", "embedUrl", "thumbnailUrl"],
+            ),
+            recipe_ingredient,
+            recipe_instructions: InstructionWrapper::A(instructions),
+            raw: None,
+        })
+    }
+
+    fn prop_string(&self, scope: ElementRef, name: &str) -> Option<Value> {
+        self.props(scope, &[name])
+            .into_iter()
+            .next()
+            .map(|e| Value::String(prop_value(e)))
+    }
+
+    fn prop_list(&self, scope: ElementRef, name: &str) -> Option<Value> {
+        let values: Vec<Value> = self
+            .props(scope, &[name])
+            .into_iter()
+            .map(|e| Value::String(prop_value(e)))
+            .collect();
+        Some(Value::Array(values)).filter(|v| v != &Value::Array(vec![]))
+    }
+
+    // each either a name, or an item (a Person, a HowToTool) with its own `name`
+    fn named(&self, recipe: ElementRef, name: &str) -> Option<Value> {
+        let names: Vec<Value> = self
+            .props(recipe, &[name])
+            .into_iter()
+            .filter_map(|e| match self.starts_item(e) {
+                true => self.prop_string(e, "name"),
+                false => Some(Value::String(prop_value(e))),
+            })
+            .collect();
+        Some(Value::Array(names)).filter(|v| v != &Value::Array(vec![]))
+    }
+
+    // an item nested in the recipe, such as its `aggregateRating`, read into the shape of the ld+json
+    fn item(&self, recipe: ElementRef, name: &str, fields: &[&str]) -> Option<Value> {
+        let item = self.props(recipe, &[name]).into_iter().next()?;
+        let mut values = serde_json::Map::new();
+        for name in fields {
+            if let Some(v) = self.prop_string(item, name) {
+                values.insert(name.to_string(), v);
+            }
+        }
+        Some(Value::Object(values))
+    }
+
+    // elements with one of the property names, in document order, belonging to `scope` itself
+    // rather than to an item nested in it such as the recipe's `author`
+    fn props<'a>(&self, scope: ElementRef<'a>, names: &[&str]) -> Vec<ElementRef<'a>> {
+        let selectors: Vec<String> = names.iter().filter_map(|n| self.prop_selector(n)).collect();
+        // not something this markup has
+        if selectors.is_empty() {
+            return vec![];
+        }
+        let selector = Selector::parse(&selectors.join(", ")).unwrap();
+        scope
+            .select(&selector)
+            .filter(|e| {
+                e.ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|a| self.starts_item(*a))
+                    .map(|a| a.id())
+                    == Some(scope.id())
+            })
+            .collect()
+    }
+}
+
+// `datetime` for durations, e.g. `<time itemprop="cookTime" datetime="PT1H">an hour</time>`
+fn prop_value(e: ElementRef) -> String {
+    match e
+        .value()
+        .attr("content")
+        .or_else(|| e.value().attr("datetime"))
+    {
+        Some(c) => c.trim().to_string(),
+        None => element_text(e),
+    }
+}
+
+fn element_text(e: ElementRef) -> String {
+    e.text().collect::<Vec<_>>().join("").trim().to_string()
+}
@@ -0,0 +1,95 @@
+//! Scraping that keeps what it could find, along with what it couldn't.
+use std::fmt;
+
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+
+use crate::{fill_from_meta, sites::Found, ScrapeError, ScrapedRecipe, Scraper};
+
+/// A recipe that may be missing parts, see [Scraper::scrape_outcome].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrapeOutcome {
+    pub recipe: ScrapedRecipe,
+    /// empty when nothing seemed to be missing
+    pub warnings: Vec<ScrapeWarning>,
+}
+
+impl ScrapeOutcome {
+    /// whether a recipe was found, rather than only what the page says about itself
+    pub fn is_complete(&self) -> bool {
+        !self
+            .warnings
+            .iter()
+            .any(|w| matches!(w, ScrapeWarning::NoRecipe(_)))
+    }
+}
+
+/// Something a [ScrapeOutcome] is missing, or was found in a way that's less reliable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScrapeWarning {
+    /// no recipe was found, so there's only the page's title, image and description,
+    /// with why as it would have been a [ScrapeError]
+    NoRecipe(String),
+    NoName,
+    NoImage,
+    NoIngredients,
+    NoInstructions,
+    /// there was no structured recipe, so the ingredients and instructions were read from the
+    /// page's html by a [crate::SiteScraper]
+    HtmlFallback,
+}
+
+impl fmt::Display for ScrapeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrapeWarning::NoRecipe(e) => write!(f, "no recipe found: {e}"),
+            ScrapeWarning::NoName => write!(f, "no name found"),
+            ScrapeWarning::NoImage => write!(f, "no image found"),
+            ScrapeWarning::NoIngredients => write!(f, "no ingredients found"),
+            ScrapeWarning::NoInstructions => write!(f, "no instructions found"),
+            ScrapeWarning::HtmlFallback => {
+                write!(f, "ingredients and instructions parsed from html fallback")
+            }
+        }
+    }
+}
+
+impl Scraper {
+    /// Like [Scraper::scrape], but a page without a recipe still has its title, image and description
+    /// returned, as long as it has a title and isn't a wall (see [crate::detect_wall]),
+    /// and what's missing is noted.
+    pub fn scrape_outcome(&self, body: &str, url: &str) -> Result<ScrapeOutcome, ScrapeError> {
+        let dom = Html::parse_document(body);
+        let (recipe, mut warnings) = match self.scrape_dom(body, &dom, url) {
+            Ok((r, Found::Site)) => (r, vec![ScrapeWarning::HtmlFallback]),
+            Ok((r, _)) => (r, vec![]),
+            Err(e @ (ScrapeError::Blocked { .. } | ScrapeError::Paywalled { .. })) => {
+                return Err(e)
+            }
+            Err(e) => {
+                let r = fill_from_meta(
+                    &dom,
+                    ScrapedRecipe {
+                        url: url.to_string(),
+                        ..Default::default()
+                    },
+                );
+                if r.name.is_empty() {
+                    return Err(e);
+                }
+                (r, vec![ScrapeWarning::NoRecipe(e.to_string())])
+            }
+        };
+        let missing = [
+            (recipe.name.trim().is_empty(), ScrapeWarning::NoName),
+            (recipe.image.is_none(), ScrapeWarning::NoImage),
+            (recipe.ingredients.is_empty(), ScrapeWarning::NoIngredients),
+            (
+                recipe.instructions.iter().all(|i| i.trim().is_empty()),
+                ScrapeWarning::NoInstructions,
+            ),
+        ];
+        warnings.extend(missing.into_iter().filter(|(m, _)| *m).map(|(_, w)| w));
+        Ok(ScrapeOutcome { recipe, warnings })
+    }
+}
@@ -0,0 +1,209 @@
+//! Writing recipes as a [Paprika](https://www.paprikaapp.com) `.paprikarecipes` export,
+//! for importing them into the app.
+use std::{
+    collections::HashSet,
+    io::{Seek, Write},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flate2::{write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{ScrapeError, ScrapedRecipe};
+
+/// A recipe as Paprika exports them, with its text fields as Paprika shows them.
+/// Paprika gives the recipe its own `uid` and hashes when importing it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct PaprikaRecipe {
+    pub name: String,
+    /// a line each, with section headings as lines ending in `:`
+    pub ingredients: String,
+    /// a line each, with section headings as lines ending in `:`
+    pub directions: String,
+    pub description: String,
+    pub notes: String,
+    pub nutritional_info: String,
+    pub servings: String,
+    pub difficulty: String,
+    /// e.g. `15 mins`
+    pub prep_time: String,
+    pub cook_time: String,
+    pub total_time: String,
+    /// the site's domain
+    pub source: String,
+    pub source_url: String,
+    pub image_url: Option<String>,
+    /// the file name of `photo_data`
+    pub photo: Option<String>,
+    /// the base64 of a JPEG
+    pub photo_data: Option<String>,
+    pub categories: Vec<String>,
+    /// out of 5, `0` when unrated
+    pub rating: u8,
+}
+
+impl From<&ScrapedRecipe> for PaprikaRecipe {
+    fn from(recipe: &ScrapedRecipe) -> Self {
+        let source_url = recipe.canonical_url.as_ref().unwrap_or(&recipe.url).clone();
+        let directions = match recipe.instruction_groups.is_empty() {
+            true => recipe.instructions.join("\n"),
+            false => recipe
+                .instruction_groups
+                .iter()
+                .flat_map(|(name, steps)| {
+                    name.iter()
+                        .map(|n| format!("{n}:"))
+                        .chain(steps.iter().cloned())
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let time = |t: Option<std::time::Duration>| {
+            t.map(|t| format!("{} mins", (t.as_secs_f64() / 60.0).round()))
+                .unwrap_or_default()
+        };
+        PaprikaRecipe {
+            name: recipe.name.clone(),
+            ingredients: recipe.ld_ingredients().join("\n"),
+            directions,
+            description: recipe.description.clone().unwrap_or_default(),
+            notes: recipe.notes.clone().unwrap_or_default(),
+            servings: recipe
+                .yield_
+                .as_ref()
+                .map(|y| y.raw.clone())
+                .unwrap_or_default(),
+            prep_time: time(recipe.prep_time),
+            cook_time: time(recipe.cook_time),
+            total_time: time(recipe.total_time),
+            source: source_url
+                .split_once("://")
+                .and_then(|(_, rest)| rest.split('/').next())
+                .unwrap_or_default()
+                .to_string(),
+            source_url,
+            image_url: recipe.image.clone(),
+            categories: recipe.category.clone(),
+            rating: recipe
+                .rating
+                .as_ref()
+                .map_or(0, |r| r.value.round().clamp(0.0, 5.0) as u8),
+            ..Default::default()
+        }
+    }
+}
+
+impl PaprikaRecipe {
+    /// With `jpeg` as its photo, e.g. a downloaded image of the recipe.
+    /// Paprika only shows JPEGs.
+    pub fn with_photo(self, jpeg: &[u8]) -> Self {
+        PaprikaRecipe {
+            photo: Some(format!("{}.jpg", file_name(&self.name))),
+            photo_data: Some(STANDARD.encode(jpeg)),
+            ..self
+        }
+    }
+}
+
+/// Writes `recipes` to `writer` as a `.paprikarecipes` archive:
+/// a zip with a gzipped json `.paprikarecipe` for each of them.
+pub fn write_paprikarecipes<W: Write + Seek>(
+    writer: W,
+    recipes: &[PaprikaRecipe],
+) -> Result<W, ScrapeError> {
+    let mut zip = ZipWriter::new(writer);
+    // already gzipped
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut names = HashSet::new();
+    for recipe in recipes {
+        let mut gz = GzEncoder::new(vec![], Compression::default());
+        serde_json::to_writer(&mut gz, recipe)?;
+        let gz = gz.finish().map_err(io_error)?;
+        // Paprika doesn't mind duplicate names, but zip tools do
+        let mut name = file_name(&recipe.name);
+        for n in 1.. {
+            if names.insert(name.clone()) {
+                break;
+            }
+            name = format!("{} {n}", file_name(&recipe.name));
+        }
+        zip.start_file(format!("{name}.paprikarecipe"), options)
+            .map_err(io_error)?;
+        zip.write_all(&gz).map_err(io_error)?;
+    }
+    zip.finish().map_err(io_error)
+}
+
+// the recipe's name, without anything file systems don't like
+fn file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match name.trim() {
+        "" => "recipe".to_string(),
+        name => name.to_string(),
+    }
+}
+
+fn io_error(e: impl std::fmt::Display) -> ScrapeError {
+    ScrapeError::Io(format!("paprika: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+    use crate::Rating;
+
+    #[test]
+    fn test_write_paprikarecipes() {
+        let recipe = ScrapedRecipe {
+            name: "Toast: the best".to_string(),
+            url: "https://example.com/toast".to_string(),
+            ingredients: vec!["2 slices bread".to_string(), "butter".to_string()],
+            instructions: vec!["Toast the bread.".to_string(), "Butter it.".to_string()],
+            cook_time: Some(std::time::Duration::from_secs(300)),
+            rating: Some(Rating {
+                value: 4.6,
+                count: Some(10),
+            }),
+            ..Default::default()
+        };
+        let paprika = PaprikaRecipe::from(&recipe);
+        assert_eq!(paprika.ingredients, "2 slices bread\nbutter");
+        assert_eq!(paprika.directions, "Toast the bread.\nButter it.");
+        assert_eq!(paprika.cook_time, "5 mins");
+        assert_eq!(paprika.source, "example.com");
+        assert_eq!(paprika.rating, 5);
+
+        let recipes = [paprika.clone().with_photo(b"jpeg"), paprika];
+        let zip = write_paprikarecipes(Cursor::new(vec![]), &recipes).unwrap();
+        let mut zip = zip::ZipArchive::new(zip).unwrap();
+        assert_eq!(
+            zip.file_names().collect::<std::collections::BTreeSet<_>>(),
+            [
+                "Toast_ the best.paprikarecipe",
+                "Toast_ the best 1.paprikarecipe"
+            ]
+            .into_iter()
+            .collect()
+        );
+        let mut json = String::new();
+        GzDecoder::new(zip.by_name("Toast_ the best.paprikarecipe").unwrap())
+            .read_to_string(&mut json)
+            .unwrap();
+        let read: PaprikaRecipe = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, recipes[0]);
+        assert_eq!(read.photo.as_deref(), Some("Toast_ the best.jpg"));
+        assert_eq!(read.photo_data.as_deref(), Some("anBlZw=="));
+    }
+}
@@ -0,0 +1,209 @@
+//! Recipe cards made by WordPress plugins, for when their ld+json is broken or missing.
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{ScrapeError, ScrapedRecipe, ScrapedStep, SiteScraper};
+
+/// [WP Recipe Maker](https://wordpress.org/plugins/wp-recipe-maker/)
+pub(crate) struct WpRecipeMaker;
+
+impl SiteScraper for WpRecipeMaker {
+    // any WordPress site can have it
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+    fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError> {
+        scrape_wprm(dom).ok_or_else(|| ScrapeError::Parse("no wprm recipe card".to_string()))
+    }
+}
+
+/// [Tasty Recipes](https://www.wptasty.com/tasty-recipes)
+pub(crate) struct TastyRecipes;
+
+impl SiteScraper for TastyRecipes {
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+    fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError> {
+        scrape_tasty(dom).ok_or_else(|| ScrapeError::Parse("no tasty recipe card".to_string()))
+    }
+}
+
+fn scrape_wprm(dom: &Html) -> Option<ScrapedRecipe> {
+    let recipe = dom.select(&selector(".wprm-recipe")).next()?;
+
+    // the amount, unit, name and notes are each in their own span
+    let parts =
+        ["amount", "unit", "name"].map(|p| selector(&format!(".wprm-recipe-ingredient-{p}")));
+    let notes = selector(".wprm-recipe-ingredient-notes");
+    let line = |i: ElementRef| {
+        let line = parts
+            .iter()
+            .filter_map(|p| i.select(p).next().map(element_text))
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let line = match line.is_empty() {
+            true => element_text(i),
+            false => line,
+        };
+        match i.select(&notes).next().map(element_text) {
+            Some(n) if !n.is_empty() => format!("{line}, {n}"),
+            _ => line,
+        }
+    };
+    let ingredient = selector(".wprm-recipe-ingredient");
+    let group_name = selector(".wprm-recipe-group-name");
+    let mut ingredient_groups: Vec<(Option<String>, Vec<String>)> = recipe
+        .select(&selector(".wprm-recipe-ingredient-group"))
+        .map(|g| {
+            let name = g
+                .select(&group_name)
+                .next()
+                .map(element_text)
+                .filter(|n| !n.is_empty());
+            let lines = g
+                .select(&ingredient)
+                .map(line)
+                .filter(|i| !i.is_empty())
+                .collect();
+            (name, lines)
+        })
+        .collect();
+    let ingredients: Vec<String> = recipe
+        .select(&ingredient)
+        .map(line)
+        .filter(|i| !i.is_empty())
+        .collect();
+    if ingredient_groups.iter().all(|(name, _)| name.is_none()) {
+        ingredient_groups = vec![];
+    }
+
+    let instructions: Vec<String> = recipe
+        .select(&selector(".wprm-recipe-instruction-text"))
+        .map(element_text)
+        .filter(|i| !i.is_empty())
+        .collect();
+    if ingredients.is_empty() || instructions.is_empty() {
+        return None;
+    }
+
+    Some(ScrapedRecipe {
+        ingredient_groups,
+        ..recipe_card(
+            recipe,
+            ingredients,
+            instructions,
+            ".wprm-recipe-name",
+            ".wprm-recipe-image img",
+        )
+    })
+}
+
+fn scrape_tasty(dom: &Html) -> Option<ScrapedRecipe> {
+    let recipe = dom.select(&selector(".tasty-recipes")).next()?;
+
+    let ingredients: Vec<String> = recipe
+        .select(&selector(".tasty-recipes-ingredients li"))
+        .map(|i| normalize_whitespace(&text_with_data_amounts(i)))
+        .filter(|i| !i.is_empty())
+        .collect();
+    let instructions: Vec<String> = recipe
+        .select(&selector(".tasty-recipes-instructions li"))
+        .map(element_text)
+        .filter(|i| !i.is_empty())
+        .collect();
+    if ingredients.is_empty() || instructions.is_empty() {
+        return None;
+    }
+
+    Some(recipe_card(
+        recipe,
+        ingredients,
+        instructions,
+        ".tasty-recipes-title",
+        ".tasty-recipes-image img",
+    ))
+}
+
+// the amount is in `data-amount` and `data-unit`, the text may have been scaled or converted
+// for display, e.g. `<span data-amount="1" data-unit="cup">1 cup (120 g)</span>`
+fn text_with_data_amounts(e: ElementRef) -> String {
+    e.children()
+        .map(|child| match ElementRef::wrap(child) {
+            Some(el) => match el.value().attr("data-amount") {
+                Some(amount) => match el.value().attr("data-unit") {
+                    Some(unit) if !unit.is_empty() => format!("{amount} {unit}"),
+                    _ => amount.to_string(),
+                },
+                None => text_with_data_amounts(el),
+            },
+            None => child
+                .value()
+                .as_text()
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+// what every plugin's card has besides ingredients and instructions
+fn recipe_card(
+    recipe: ElementRef,
+    ingredients: Vec<String>,
+    instructions: Vec<String>,
+    name_selector: &str,
+    image_selector: &str,
+) -> ScrapedRecipe {
+    let image = recipe
+        .select(&selector(image_selector))
+        .next()
+        .and_then(|i| {
+            // lazy loaded images keep the real one in `data-lazy-src`
+            let i = i.value();
+            i.attr("data-lazy-src").or_else(|| i.attr("src"))
+        })
+        .map(str::to_string);
+
+    ScrapedRecipe {
+        ingredients,
+        steps: instructions.iter().cloned().map(ScrapedStep::new).collect(),
+        instructions,
+        name: recipe
+            .select(&selector(name_selector))
+            .next()
+            .map(element_text)
+            .unwrap_or_default(),
+        image,
+        ..Default::default()
+    }
+}
+
+/// The notes of the page's WPRM or Tasty Recipes card, a line for each paragraph.
+/// Sites that use them for their cards still put the recipe itself in ld+json.
+pub(crate) fn card_notes(dom: &Html) -> Option<String> {
+    let notes = dom
+        .select(&selector(".wprm-recipe-notes, .tasty-recipes-notes-body"))
+        .next()?;
+    let paragraphs: Vec<String> = notes
+        .select(&selector("p, li"))
+        .map(element_text)
+        .filter(|p| !p.is_empty())
+        .collect();
+    let notes = match paragraphs.is_empty() {
+        true => element_text(notes),
+        false => paragraphs.join("\n"),
+    };
+    Some(notes).filter(|n| !n.is_empty())
+}
+
+fn selector(s: &str) -> Selector {
+    Selector::parse(s).unwrap()
+}
+
+pub(crate) fn element_text(e: ElementRef) -> String {
+    normalize_whitespace(&e.text().collect::<String>())
+}
+
+fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,174 @@
+//! Writing a [ScrapedRecipe] as a [RecipeMD](https://recipemd.org) markdown document.
+use ingredient::{
+    display::DisplayOptions,
+    markdown::{grouped_ingredient_list, ingredient_list},
+};
+
+use crate::ScrapedRecipe;
+
+impl ScrapedRecipe {
+    /// A RecipeMD document of it: the name as the title, its description and a link to it,
+    /// `*keywords*` as tags, `**yield**`, then the ingredients with their amounts
+    /// (under `##` headings when it has [ScrapedRecipe::ingredient_groups])
+    /// and the instructions as a numbered list, each between `---`s.
+    pub fn to_recipemd(&self) -> String {
+        let mut md = format!("# {}\n\n", escape(&self.name));
+        if let Some(description) = self.description.as_ref().filter(|d| !d.is_empty()) {
+            md.push_str(&format!("{}\n\n", escape(description)));
+        }
+        md.push_str(&format!(
+            "<{}>\n\n",
+            self.canonical_url.as_ref().unwrap_or(&self.url)
+        ));
+        if !self.keywords.is_empty() {
+            md.push_str(&format!("*{}*\n\n", escape(&self.keywords.join(", "))));
+        }
+        if let Some(y) = self.yield_.as_ref().filter(|y| !y.raw.is_empty()) {
+            md.push_str(&format!("**{}**\n\n", escape(&y.raw)));
+        }
+
+        let ip = self.ingredient_parser();
+        let parse = |lines: &[String]| {
+            lines
+                .iter()
+                .map(|i| ip.clone().from_str(i))
+                .collect::<Vec<_>>()
+        };
+        let options = DisplayOptions::default();
+        md.push_str("---\n\n");
+        match self.ingredient_groups.is_empty() {
+            true => md.push_str(&ingredient_list(&parse(&self.ingredients), &options)),
+            false => {
+                let groups: Vec<_> = self
+                    .ingredient_groups
+                    .iter()
+                    .map(|(name, lines)| (name.clone().unwrap_or_default(), parse(lines)))
+                    .collect();
+                md.push_str(&grouped_ingredient_list(&groups, &options));
+            }
+        }
+        md.push_str("\n---\n");
+
+        let groups = match self.instruction_groups.is_empty() {
+            true => vec![(None, self.instructions.clone())],
+            false => self.instruction_groups.clone(),
+        };
+        for (name, steps) in groups {
+            if let Some(name) = name {
+                md.push_str(&format!("\n## {}\n", escape(&name)));
+            }
+            md.push('\n');
+            for (n, step) in steps.iter().enumerate() {
+                md.push_str(&format!("{}. {}\n", n + 1, escape(&one_line(step))));
+            }
+        }
+        md
+    }
+}
+
+// so text like `*optional*` doesn't turn into emphasis
+fn escape(s: &str) -> String {
+    s.replace('*', "\\*").replace('_', "\\_")
+}
+
+// a blank line would end the list item
+fn one_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RecipeYield;
+
+    #[test]
+    fn test_to_recipemd() {
+        let recipe = ScrapedRecipe {
+            name: "Toast".to_string(),
+            url: "https://example.com/toast".to_string(),
+            description: Some("Crunchy.".to_string()),
+            ingredients: vec!["2 slices bread".to_string(), "butter".to_string()],
+            instructions: vec![
+                "Toast the bread.".to_string(),
+                "Spread with butter.\n\nEat.".to_string(),
+            ],
+            yield_: Some(RecipeYield {
+                raw: "2 servings".to_string(),
+                parsed: None,
+            }),
+            keywords: vec!["breakfast".to_string(), "quick".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            recipe.to_recipemd(),
+            "# Toast
+
+Crunchy.
+
+<https://example.com/toast>
+
+*breakfast, quick*
+
+**2 servings**
+
+---
+
+- *2 slice* bread
+- butter
+
+---
+
+1. Toast the bread.
+2. Spread with butter. Eat.
+"
+        );
+    }
+
+    #[test]
+    fn test_to_recipemd_groups() {
+        let recipe = ScrapedRecipe {
+            name: "Cake".to_string(),
+            url: "https://example.com/cake".to_string(),
+            ingredients: vec!["2 eggs".to_string(), "1 cup sugar".to_string()],
+            ingredient_groups: vec![
+                (None, vec!["2 eggs".to_string()]),
+                (
+                    Some("For the glaze".to_string()),
+                    vec!["1 cup sugar".to_string()],
+                ),
+            ],
+            instructions: vec!["Beat the eggs.".to_string(), "Add the sugar.".to_string()],
+            instruction_groups: vec![
+                (None, vec!["Beat the eggs.".to_string()]),
+                (
+                    Some("For the glaze".to_string()),
+                    vec!["Add the sugar.".to_string()],
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            recipe.to_recipemd(),
+            "# Cake
+
+<https://example.com/cake>
+
+---
+
+- *2* eggs
+
+## For the glaze
+
+- *1 cup* sugar
+
+---
+
+1. Beat the eggs.
+
+## For the glaze
+
+1. Add the sugar.
+"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Scaling a recipe to make more or fewer servings than it was written for.
+use ingredient::{
+    instructions::Span,
+    normalize::{plural_name, singular_name},
+    recipe_yield::Yield,
+    unit::{Measure, MeasureKind, Unit},
+    IngredientParser,
+};
+
+use crate::{RecipeYield, ScrapedRecipe};
+
+impl ScrapedRecipe {
+    /// A copy of it to serve `servings`, scaled from its parsed [ScrapedRecipe::yield_]:
+    /// every ingredient's amounts, and the weights, volumes and counts written in its
+    /// instructions (`add 120 g of the flour`), but not times, temperatures or pan sizes.
+    /// Only the amounts are rewritten, the rest of each line is kept as it was written;
+    /// ingredients without an amount (`salt, to taste`) are left as they are.
+    /// `None` when it isn't known how many servings the recipe makes.
+    pub fn scale_to_servings(&self, servings: f64) -> Option<ScrapedRecipe> {
+        let parsed = self.yield_.as_ref()?.parsed.as_ref()?;
+        if parsed.value <= 0.0 || servings <= 0.0 {
+            return None;
+        }
+        let factor = servings / parsed.value;
+
+        let ip = self.ingredient_parser();
+        let scale_ingredients = |lines: &[String]| -> Vec<String> {
+            lines
+                .iter()
+                .map(|l| scale_ingredient(&ip, l, factor))
+                .collect()
+        };
+        let rich = self.parser(true);
+        let scale_steps = |steps: &[String]| -> Vec<String> {
+            steps
+                .iter()
+                .map(|s| scale_instruction(&rich, s, factor))
+                .collect()
+        };
+
+        let mut scaled = self.clone();
+        scaled.ingredients = scale_ingredients(&self.ingredients);
+        for (_, lines) in scaled.ingredient_groups.iter_mut() {
+            *lines = scale_ingredients(lines);
+        }
+        scaled.instructions = scale_steps(&self.instructions);
+        for step in scaled.steps.iter_mut() {
+            step.text = scale_instruction(&rich, &step.text, factor);
+        }
+        for (_, steps) in scaled.instruction_groups.iter_mut() {
+            *steps = scale_steps(steps);
+        }
+        let unit = parsed.unit.clone();
+        scaled.yield_ = Some(RecipeYield::from(Yield {
+            value: servings,
+            upper: None,
+            raw: format!("{servings} {unit}"),
+            unit,
+        }));
+        Some(scaled)
+    }
+}
+
+// only the amounts the line was parsed with are rewritten, the rest of it stays as it was
+// written: the size in `2 (15-ounce) cans`, the `100%` of a baker's percentage
+fn scale_ingredient(ip: &IngredientParser, line: &str, factor: f64) -> String {
+    let mut unscaled = ip.clone().from_str(line).amounts;
+    if unscaled.is_empty() {
+        return line.to_string();
+    }
+    let found = ip.extract_amounts(line);
+    let mut scaled = String::new();
+    let mut end = 0;
+    let mut i = 0;
+    while i < found.len() {
+        let span = found[i].0.clone();
+        let group: Vec<&Measure> = found[i..]
+            .iter()
+            .take_while(|(s, _)| *s == span)
+            .map(|(_, m)| m)
+            .collect();
+        i += group.len();
+        let mut matched = vec![];
+        for m in &group {
+            let same = |a: &Measure| {
+                a.values().0 == m.values().0
+                    && a.values().1 == m.values().1
+                    && (m.inferred_unit() || a.unit() == m.unit())
+            };
+            if let Some(j) = unscaled.iter().position(same) {
+                matched.push(unscaled.remove(j));
+            }
+        }
+        if span.start < end || matched.len() != group.len() {
+            continue;
+        }
+        scaled.push_str(&line[end..span.start]);
+        end = match matched.as_slice() {
+            [amount] => {
+                let (text, to) = scale_count(line, span.clone(), group[0], amount, factor)
+                    .unwrap_or_else(|| ((amount.clone() * factor).to_string(), span.end));
+                scaled.push_str(&text);
+                to
+            }
+            // equivalent amounts written together, `1 cup (120 g)` or `120 g / 1 cup`
+            amounts => {
+                let amounts: Vec<String> = amounts
+                    .iter()
+                    .map(|m| (m.clone() * factor).to_string())
+                    .collect();
+                match line[span.clone()].contains('(') {
+                    true => {
+                        scaled.push_str(&format!("{} ({})", amounts[0], amounts[1..].join(", ")))
+                    }
+                    false => scaled.push_str(&amounts.join(" / ")),
+                }
+                span.end
+            }
+        };
+    }
+    scaled.push_str(&line[end..]);
+    scaled
+}
+
+// a count, `2 eggs` or `1 lemon`, gets a new number, and its noun agrees with it
+// when it's the one the count was parsed with
+fn scale_count(
+    line: &str,
+    span: Span,
+    found: &Measure,
+    amount: &Measure,
+    factor: f64,
+) -> Option<(String, usize)> {
+    let Unit::Other(noun) = amount.unit() else {
+        return None;
+    };
+    let text = &line[span.clone()];
+    let number_end = match found.inferred_unit() {
+        true => span.end,
+        false => span.start + text.trim_end().rfind(' ')?,
+    };
+    let (value, upper, _) = (amount.clone() * factor).values();
+    let count = Measure::new_with_upper(Unit::Other(String::new()), value, upper).to_string();
+
+    let rest = &line[number_end..];
+    let word = rest.trim_start();
+    let word = &word[..word
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(word.len())];
+    if word.is_empty() || singular_name(&word.to_lowercase()) != noun {
+        return Some((count, number_end));
+    }
+    let noun = match value.max(upper.unwrap_or_default()) > 1.0 {
+        true => plural_name(&noun),
+        false => noun,
+    };
+    let space = &rest[..rest.len() - rest.trim_start().len()];
+    let word_end = number_end + space.len() + word.len();
+    Some((format!("{count}{space}{noun}"), word_end))
+}
+
+// amounts with a unit saying how much of something there is; a bare number is as likely
+// to be an oven temperature or a step number as a count
+fn scales(m: &Measure) -> bool {
+    !m.inferred_unit()
+        && matches!(
+            m.kind(),
+            Ok(MeasureKind::Weight | MeasureKind::Volume | MeasureKind::Count)
+        )
+}
+
+fn scale_instruction(ip: &IngredientParser, step: &str, factor: f64) -> String {
+    let mut found = ip.extract_amounts(step);
+    found.retain(|(_, m)| scales(m));
+    let mut scaled = String::new();
+    let mut end = 0;
+    let mut i = 0;
+    while i < found.len() {
+        let span = found[i].0.clone();
+        // equivalent amounts written together, `120 g / 1 cup`
+        let amounts: Vec<String> = found[i..]
+            .iter()
+            .take_while(|(s, _)| *s == span)
+            .map(|(_, m)| (m.clone() * factor).to_string())
+            .collect();
+        i += amounts.len();
+        scaled.push_str(&step[end..span.start]);
+        scaled.push_str(&amounts.join(" / "));
+        end = span.end;
+    }
+    scaled.push_str(&step[end..]);
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScrapedStep;
+
+    #[test]
+    fn test_scale_to_servings() {
+        let recipe = ScrapedRecipe {
+            ingredients: vec![
+                "1 cup flour".to_string(),
+                "2 eggs".to_string(),
+                "salt, to taste".to_string(),
+            ],
+            instructions: vec![
+                "Heat the oven to 350°F.".to_string(),
+                "Mix 120 g / 1 cup of the flour with the eggs in a 9 inch pan.".to_string(),
+                "Bake for 20 minutes.".to_string(),
+            ],
+            steps: vec![ScrapedStep {
+                text: "Add 2 tbsp water.".to_string(),
+                ..Default::default()
+            }],
+            yield_: Some(RecipeYield {
+                raw: "Serves 4".to_string(),
+                parsed: ingredient::parse_yield("Serves 4").ok(),
+            }),
+            ..Default::default()
+        };
+        let scaled = recipe.scale_to_servings(8.0).unwrap();
+        assert_eq!(
+            scaled.ingredients,
+            vec!["2 cups flour", "4 eggs", "salt, to taste"]
+        );
+        assert_eq!(
+            scaled.instructions,
+            vec![
+                "Heat the oven to 350°F.",
+                "Mix 240 g / 2 cups of the flour with the eggs in a 9 inch pan.",
+                "Bake for 20 minutes.",
+            ]
+        );
+        assert_eq!(scaled.steps[0].text, "Add 4 tbsp water.");
+        let y = scaled.yield_.unwrap();
+        assert_eq!(
+            (y.raw.as_str(), y.parsed.unwrap().value),
+            ("8 servings", 8.0)
+        );
+
+        let halved = recipe.scale_to_servings(2.0).unwrap();
+        assert_eq!(halved.ingredients[0], "0.5 cup flour");
+
+        let unknown = ScrapedRecipe {
+            yield_: None,
+            ..recipe
+        };
+        assert!(unknown.scale_to_servings(2.0).is_none());
+    }
+
+    #[test]
+    fn test_scale_ingredient() {
+        let ip = ScrapedRecipe::default().ingredient_parser();
+        let scale = |l: &str, factor: f64| scale_ingredient(&ip, l, factor);
+        assert_eq!(
+            scale("2 (15-ounce) cans black beans, drained and rinsed", 2.0),
+            "4 (15-ounce) cans black beans, drained and rinsed"
+        );
+        assert_eq!(
+            scale("2 cans (14 oz each) tomatoes", 0.5),
+            "1 can (14 oz each) tomatoes"
+        );
+        assert_eq!(scale("zest of 1 lemon", 3.0), "zest of 3 lemons");
+        assert_eq!(
+            scale("500 g bread flour (100%)", 2.0),
+            "1000 g bread flour (100%)"
+        );
+        assert_eq!(
+            scale("1 cup (120 g) flour, sifted", 2.0),
+            "2 cups (240 g) flour, sifted"
+        );
+        assert_eq!(scale("3 Eggs, beaten", 2.0), "6 Eggs, beaten");
+    }
+}
@@ -0,0 +1,74 @@
+//! Finding the ld+json of a page without parsing all of it. Some recipe pages are several
+//! megabytes of html, and building their whole [scraper::Html] takes many times that in memory,
+//! when all that's needed is a few kilobytes of json and the `<head>`.
+
+/// Pages longer than this are scanned for their ld+json before being parsed.
+pub(crate) const LARGE_PAGE: usize = 1024 * 1024;
+
+/// The contents of each `<script type="application/ld+json">`, in order.
+/// Script contents aren't html, so they're taken as they are.
+pub(crate) fn ld_json_scripts(body: &str) -> Vec<&str> {
+    let mut scripts = vec![];
+    let mut rest = 0;
+    while let Some(start) = find_tag(body, rest, "<script") {
+        let Some(open_end) = body[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = find_tag(body, open_end, "</script") else {
+            break;
+        };
+        if body[start..open_end]
+            .to_ascii_lowercase()
+            .contains("application/ld+json")
+        {
+            scripts.push(&body[open_end..end]);
+        }
+        rest = end;
+    }
+    scripts
+}
+
+/// The page up to the end of its `<head>`, which is where its meta tags are,
+/// empty when it doesn't have one.
+pub(crate) fn head(body: &str) -> &str {
+    let end = find_tag(body, 0, "</head").or_else(|| find_tag(body, 0, "<body"));
+    &body[..end.unwrap_or(0)]
+}
+
+// where the first `tag` (lowercase) is from `from` on, in any case
+fn find_tag(body: &str, from: usize, tag: &str) -> Option<usize> {
+    body[from..].match_indices('<').find_map(|(i, _)| {
+        let candidate = body.as_bytes().get(from + i..from + i + tag.len())?;
+        candidate
+            .eq_ignore_ascii_case(tag.as_bytes())
+            .then_some(from + i)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ld_json_scripts() {
+        let page = r#"<html lang="en"><HEAD><title>Toast</title>
+<script>var a = "<b>";</script>
+<script type="application/ld+json">{"@type": "Organization"}</script></head>
+<body><p>1 < 2</p><SCRIPT TYPE='Application/LD+JSON'>
+{"@type": "Recipe", "name": "a <b>bold</b> toast"}
+</SCRIPT></body></html>"#;
+        assert_eq!(
+            ld_json_scripts(page),
+            vec![
+                r#"{"@type": "Organization"}"#,
+                "\n{\"@type\": \"Recipe\", \"name\": \"a <b>bold</b> toast\"}\n",
+            ]
+        );
+        assert!(head(page).ends_with(r#"{"@type": "Organization"}</script>"#));
+        assert_eq!(head("<p>no head</p>"), "");
+        assert_eq!(
+            ld_json_scripts("<script type=\"application/ld+json\">{"),
+            Vec::<&str>::new()
+        );
+    }
+}
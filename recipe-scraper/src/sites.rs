@@ -0,0 +1,381 @@
+//! Scrapers for the html of particular sites, for pages without a structured recipe.
+//! More can be added to a [Scraper] with [Scraper::register].
+use std::fmt;
+
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::{
+    clean_recipe, extract_ld, fill_from_meta,
+    markup::Markup,
+    og_image,
+    plugins::{self, element_text},
+    recipe_from_json, scan, scrape_all_from_json, walls, ScrapeError, ScrapedRecipe, ScrapedStep,
+};
+
+/// Reads a recipe out of the html of the sites it [matches](SiteScraper::matches).
+/// ```
+/// use recipe_scraper::{Html, ScrapeError, ScrapedRecipe, Scraper, Selector, SiteScraper};
+/// struct Grandma;
+/// impl SiteScraper for Grandma {
+///     fn matches(&self, url: &str) -> bool {
+///         url.contains("grandmas-recipes.com")
+///     }
+///     fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError> {
+///         let selector = Selector::parse("pre.recipe").unwrap();
+///         let card = dom.select(&selector).next();
+///         let card = card.ok_or_else(|| ScrapeError::Parse("no recipe card".to_string()))?;
+///         let text = card.text().collect::<String>();
+///         let (ingredients, instructions) = text.split_once("\n\n").unwrap_or_default();
+///         Ok(ScrapedRecipe {
+///             ingredients: ingredients.lines().map(str::to_string).collect(),
+///             instructions: instructions.lines().map(str::to_string).collect(),
+///             ..Default::default()
+///         })
+///     }
+/// }
+/// let mut scraper = Scraper::default();
+/// scraper.register(Grandma);
+/// let html = "<pre class=recipe>1 cup tea\n\nsteep the tea</pre>";
+/// let r = scraper.scrape(html, "https://grandmas-recipes.com/tea").unwrap();
+/// assert_eq!(r.ingredients, vec!["1 cup tea"]);
+/// assert_eq!(r.url, "https://grandmas-recipes.com/tea");
+/// assert!(scraper.scrape(html, "https://example.com/tea").is_err());
+/// ```
+pub trait SiteScraper: Send + Sync {
+    /// whether the page at `url` is one this can scrape
+    fn matches(&self, url: &str) -> bool;
+    /// the `url` is filled in afterwards if it's left empty
+    fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError>;
+}
+
+/// Scrapes a page from its ld+json, its microdata or RDFa, and then from its html
+/// using the registered [SiteScraper]s. [crate::scrape] uses the default one.
+pub struct Scraper {
+    sites: Vec<Box<dyn SiteScraper>>,
+}
+
+impl Default for Scraper {
+    /// with the built in [SiteScraper]s: WordPress recipe plugins and Jetpack recipe cards
+    fn default() -> Self {
+        Scraper {
+            sites: vec![
+                Box::new(plugins::WpRecipeMaker),
+                Box::new(plugins::TastyRecipes),
+                Box::new(Jetpack),
+            ],
+        }
+    }
+}
+
+impl fmt::Debug for Scraper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scraper")
+            .field("sites", &self.sites.len())
+            .finish()
+    }
+}
+
+impl Scraper {
+    /// without any [SiteScraper]s, only structured recipes are found
+    pub fn new() -> Self {
+        Scraper { sites: vec![] }
+    }
+    /// Tries `site` before the ones already registered.
+    pub fn register(&mut self, site: impl SiteScraper + 'static) -> &mut Self {
+        self.sites.insert(0, Box::new(site));
+        self
+    }
+
+    /// See [crate::scrape].
+    pub fn scrape(&self, body: &str, url: &str) -> Result<ScrapedRecipe, ScrapeError> {
+        info!("scraping {} from {}", body.len(), url);
+        if body.len() > scan::LARGE_PAGE {
+            if let Some(r) = scan::ld_json_scripts(body)
+                .into_iter()
+                .find_map(|ld| recipe_from_json(ld, url).ok())
+            {
+                Found::LdJson.record(url);
+                return Ok(fill_from_head(body, r));
+            }
+        }
+        let dom = Html::parse_document(body);
+        self.scrape_dom(body, &dom, url).map(|(r, _)| r)
+    }
+
+    // the recipe on the page (`body`, parsed as `dom`), and where it was found
+    pub(crate) fn scrape_dom(
+        &self,
+        body: &str,
+        dom: &Html,
+        url: &str,
+    ) -> Result<(ScrapedRecipe, Found), ScrapeError> {
+        let res = match extract_ld(dom) {
+            Ok(ld_schemas) => {
+                let items = ld_schemas.len();
+                // sites often put their Organization or BreadcrumbList first, so every
+                // script is tried until one of them has the recipe
+                match ld_schemas
+                    .into_iter()
+                    .map(|ld| recipe_from_json(ld.as_str(), url))
+                    .find_map(Result::ok)
+                {
+                    Some(r) => Ok((r, Found::LdJson)),
+                    None => scrape_from_markup(dom, url)
+                        .map(|r| (r, Found::Markup))
+                        .ok_or_else(|| ScrapeError::LDJSONMissingRecipe(url.to_string(), items)),
+                }
+            }
+            Err(e) => match e {
+                ScrapeError::NoLDJSON(_) => match scrape_from_markup(dom, url) {
+                    Some(r) => Ok((r, Found::Markup)),
+                    None => self.scrape_from_html(dom, url).map(|r| (r, Found::Site)),
+                },
+                _ => Err(e),
+            },
+        };
+        // a wall says more about why than not finding one does
+        let res = res.map_err(|e| walls::detect_wall(body, url).unwrap_or(e));
+        match &res {
+            Ok((_, found)) => found.record(url),
+            Err(e) => debug!("found no recipe in {}: {}", url, e),
+        }
+        res.map(|(mut r, found)| {
+            // the card's notes are only on the page, not in its ld+json
+            r.notes = r.notes.or_else(|| plugins::card_notes(dom));
+            (fill_from_meta(dom, clean_recipe(r)), found)
+        })
+    }
+
+    /// See [crate::scrape_all].
+    pub fn scrape_all(&self, body: &str, url: &str) -> Result<Vec<ScrapedRecipe>, ScrapeError> {
+        info!("scraping all from {} from {}", body.len(), url);
+        if body.len() > scan::LARGE_PAGE {
+            let recipes: Vec<ScrapedRecipe> = scan::ld_json_scripts(body)
+                .into_iter()
+                .flat_map(|ld| scrape_all_from_json(ld, url))
+                .collect();
+            if !recipes.is_empty() {
+                return Ok(recipes
+                    .into_iter()
+                    .map(|r| fill_from_head(body, r))
+                    .collect());
+            }
+        }
+        let dom = Html::parse_document(body);
+        let mut recipes: Vec<ScrapedRecipe> = extract_ld(&dom)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|ld| scrape_all_from_json(ld, url))
+            .collect();
+        if recipes.is_empty() {
+            recipes = [Markup::Microdata, Markup::Rdfa, Markup::HRecipe]
+                .iter()
+                .map(|m| m.scrape_all(&dom, url))
+                .find(|r| !r.is_empty())
+                .unwrap_or_default();
+        }
+        if recipes.is_empty() {
+            return self.scrape_dom(body, &dom, url).map(|(r, _)| vec![r]);
+        }
+        Ok(recipes
+            .into_iter()
+            .map(|r| fill_from_meta(&dom, clean_recipe(r)))
+            .collect())
+    }
+
+    // the first site that matches and finds a recipe
+    fn scrape_from_html(&self, dom: &Html, url: &str) -> Result<ScrapedRecipe, ScrapeError> {
+        let mut res = Err(ScrapeError::Parse("no ld json or parsed html".to_string()));
+        for site in self.sites.iter().filter(|s| s.matches(url)) {
+            res = site.scrape(dom);
+            if res.is_ok() {
+                break;
+            }
+        }
+        res.map(|mut r| {
+            if r.url.is_empty() {
+                r.url = url.to_string();
+            }
+            r
+        })
+    }
+}
+
+/// Where on the page a recipe was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Found {
+    LdJson,
+    /// microdata, RDFa or h-recipe
+    Markup,
+    /// by a [SiteScraper], from the page's html
+    Site,
+}
+
+impl Found {
+    fn name(self) -> &'static str {
+        match self {
+            Found::LdJson => "ld+json",
+            Found::Markup => "markup",
+            Found::Site => "html",
+        }
+    }
+    fn record(self, url: &str) {
+        debug!(strategy = self.name(), "found recipe in {}", url);
+        #[cfg(feature = "metrics")]
+        metrics::counter!("recipe_scraper_extractions_total", "strategy" => self.name())
+            .increment(1);
+    }
+}
+
+// what's in a large page's `<head>`, for a recipe found in it without parsing the rest
+fn fill_from_head(body: &str, r: ScrapedRecipe) -> ScrapedRecipe {
+    fill_from_meta(&Html::parse_document(scan::head(body)), clean_recipe(r))
+}
+
+fn scrape_from_markup(dom: &Html, url: &str) -> Option<ScrapedRecipe> {
+    [Markup::Microdata, Markup::Rdfa, Markup::HRecipe]
+        .iter()
+        .find_map(|m| m.scrape(dom, url))
+}
+
+/// The recipe card of WordPress's Jetpack plugin, found by its markup on any site.
+/// Some, like [smitten kitchen](https://smittenkitchen.com)'s, only have their
+/// ingredients marked up as structured data.
+pub(crate) struct Jetpack;
+
+impl SiteScraper for Jetpack {
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+    fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError> {
+        let ingredient_selector = Selector::parse("li.jetpack-recipe-ingredient").unwrap();
+        let ingredients = dom
+            .select(&ingredient_selector)
+            .map(|i| i.text().collect::<Vec<_>>().join(""))
+            .collect::<Vec<String>>();
+
+        let ul_selector = Selector::parse(r#"div.jetpack-recipe-directions"#).unwrap();
+
+        let instruction_list_item_elem = match dom.select(&ul_selector).next() {
+            Some(x) => x,
+            None => {
+                return Err(ScrapeError::Parse(
+                    "no jetpack recipe directions".to_string(),
+                ))
+            }
+        };
+
+        let instructions = instruction_list_item_elem
+            .text()
+            .collect::<Vec<_>>()
+            .join("")
+            .split('\n')
+            .map(|s| s.into())
+            .collect::<Vec<String>>();
+
+        Ok(ScrapedRecipe {
+            ingredients,
+            steps: instructions.iter().cloned().map(ScrapedStep::new).collect(),
+            instructions,
+            image: og_image(dom),
+            ..Default::default()
+        })
+    }
+}
+
+/// Where a site without structured data keeps its recipe, as css selectors, so it can be
+/// supported by configuration. It can be deserialized, e.g. from a TOML file of
+/// ```toml
+/// [[sites]]
+/// domain = "example.com"
+/// ingredients = ".ingredients li"
+/// instructions = ".method li"
+/// name = "h1.title"
+/// ```
+/// and each [registered](Scraper::register) on a [Scraper].
+/// ```
+/// use recipe_scraper::{Scraper, SelectorRules};
+/// let rules: SelectorRules = serde_json::from_str(
+///     r#"{"domain": "example.com", "ingredients": ".ingredients li", "instructions": ".method li"}"#,
+/// )
+/// .unwrap();
+/// let mut scraper = Scraper::default();
+/// scraper.register(rules);
+/// let html = r#"<ul class="ingredients"><li>1 egg</li></ul><ol class="method"><li>Boil it.</li></ol>"#;
+/// let r = scraper.scrape(html, "https://www.example.com/egg").unwrap();
+/// assert_eq!(r.ingredients, vec!["1 egg"]);
+/// assert_eq!(r.instructions, vec!["Boil it."]);
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SelectorRules {
+    /// also matches its subdomains, e.g. `example.com` matches `www.example.com`
+    pub domain: String,
+    pub ingredients: String,
+    pub instructions: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// an `img`, or anything else with the image's `src`, `content` or `href`
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+impl SiteScraper for SelectorRules {
+    fn matches(&self, url: &str) -> bool {
+        let host = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let host = host
+            .split(['/', '?', '#', ':'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let domain = self.domain.to_lowercase();
+        host == domain || host.ends_with(&format!(".{domain}"))
+    }
+    fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError> {
+        let select = |s: &str| -> Result<Vec<ElementRef>, ScrapeError> {
+            let selector = Selector::parse(s)
+                .map_err(|e| ScrapeError::Parse(format!("selector `{s}`: {e:?}")))?;
+            Ok(dom.select(&selector).collect())
+        };
+        let texts = |s: &str| -> Result<Vec<String>, ScrapeError> {
+            Ok(select(s)?
+                .into_iter()
+                .map(element_text)
+                .filter(|t| !t.is_empty())
+                .collect())
+        };
+
+        let ingredients = texts(&self.ingredients)?;
+        let instructions = texts(&self.instructions)?;
+        if ingredients.is_empty() || instructions.is_empty() {
+            return Err(ScrapeError::Parse(format!(
+                "no recipe found with the selectors for {}",
+                self.domain
+            )));
+        }
+        let name = match &self.name {
+            Some(s) => texts(s)?.into_iter().next(),
+            None => None,
+        };
+        let image = match &self.image {
+            Some(s) => select(s)?.into_iter().find_map(|i| {
+                let i = i.value();
+                ["data-lazy-src", "src", "content", "href"]
+                    .iter()
+                    .find_map(|a| i.attr(a))
+                    .map(str::to_string)
+            }),
+            None => None,
+        };
+
+        Ok(ScrapedRecipe {
+            ingredients,
+            steps: instructions.iter().cloned().map(ScrapedStep::new).collect(),
+            instructions,
+            name: name.unwrap_or_default(),
+            image,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,107 @@
+//! Pages that stand in for the recipe, such as bot challenges, consent walls and paywalls,
+//! told apart from pages that just don't have one.
+use crate::ScrapeError;
+
+// what's on the page, lowercased, and why it's a wall
+const BLOCKED: &[(&str, &str)] = &[
+    ("challenges.cloudflare.com", "Cloudflare challenge"),
+    ("cf-browser-verification", "Cloudflare challenge"),
+    ("attention required! | cloudflare", "Cloudflare block"),
+    ("captcha-delivery.com", "DataDome captcha"),
+    ("px-captcha", "PerimeterX captcha"),
+    ("<title>access denied</title>", "access denied"),
+    ("consent.yahoo.com", "consent wall"),
+    ("consent.google.com", "consent wall"),
+    ("<title>before you continue", "consent wall"),
+];
+const PAYWALLED: &[(&str, &str)] = &[
+    ("log in to view", "log in to view"),
+    ("sign in to view", "log in to view"),
+    ("subscribe to view", "subscribers only"),
+    ("subscribe to continue", "subscribers only"),
+    ("subscribers only", "subscribers only"),
+];
+
+/// [ScrapeError::Blocked] or [ScrapeError::Paywalled] when the page at `url` is a wall
+/// rather than the recipe, e.g. a Cloudflare challenge, or marked as not accessible for free
+/// in its ld+json. It's only a guess, for pages a recipe wasn't found on,
+/// as recipe pages may mention these too.
+/// ```
+/// use recipe_scraper::{detect_wall, ScrapeError};
+/// let page = "<title>Just a moment...</title>
+/// <script src=\"https://challenges.cloudflare.com/turnstile/v0/api.js\"></script>";
+/// assert!(matches!(
+///     detect_wall(page, "https://example.com"),
+///     Some(ScrapeError::Blocked { .. })
+/// ));
+/// assert!(detect_wall("<p>Toast the bread.</p>", "https://example.com").is_none());
+/// ```
+pub fn detect_wall(body: &str, url: &str) -> Option<ScrapeError> {
+    let page = body.to_lowercase();
+    let found = |walls: &[(&str, &'static str)]| {
+        walls
+            .iter()
+            .find(|(marker, _)| page.contains(marker))
+            .map(|(_, reason)| reason.to_string())
+    };
+    if let Some(reason) = found(BLOCKED) {
+        return Some(ScrapeError::Blocked {
+            url: url.to_string(),
+            reason,
+        });
+    }
+    let reason = match not_free(&page) {
+        true => Some("not accessible for free".to_string()),
+        false => found(PAYWALLED),
+    }?;
+    Some(ScrapeError::Paywalled {
+        url: url.to_string(),
+        reason,
+    })
+}
+
+// `"isAccessibleForFree": false`, as schema.org has paywalled content marked
+fn not_free(page: &str) -> bool {
+    page.match_indices("isaccessibleforfree").any(|(i, m)| {
+        let value = page[i + m.len()..]
+            .trim_start_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
+        value
+            .strip_prefix(':')
+            .map(|v| v.trim_start_matches(|c: char| c == '"' || c.is_whitespace()))
+            .is_some_and(|v| v.starts_with("false"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(body: &str) -> Option<(bool, String)> {
+        match detect_wall(body, "https://example.com")? {
+            ScrapeError::Blocked { reason, .. } => Some((true, reason)),
+            ScrapeError::Paywalled { reason, .. } => Some((false, reason)),
+            e => panic!("{e}"),
+        }
+    }
+
+    #[test]
+    fn test_detect_wall() {
+        assert_eq!(
+            reason("<title>Attention Required! | Cloudflare</title>"),
+            Some((true, "Cloudflare block".to_string()))
+        );
+        assert_eq!(
+            reason(r#"<form action="https://consent.yahoo.com/v2/collectConsent">"#),
+            Some((true, "consent wall".to_string()))
+        );
+        assert_eq!(
+            reason(r#"{"@type": "NewsArticle", "isAccessibleForFree" : "False"}"#),
+            Some((false, "not accessible for free".to_string()))
+        );
+        assert_eq!(
+            reason("<div class=gate>Log in to view this recipe</div>"),
+            Some((false, "log in to view".to_string()))
+        );
+        assert_eq!(reason(r#"{"isAccessibleForFree": true}"#), None);
+    }
+}
@@ -1,6 +1,11 @@
+use ingredient::{IngredientParser, ParseMode};
 use pretty_assertions::assert_eq;
-use recipe_scraper::{scrape, scrape_from_json, ParsedRecipe, ScrapeError, ScrapedRecipe};
-use std::collections::HashMap;
+use recipe_scraper::{
+    scrape, scrape_all, scrape_from_json, Html, ParsedRecipe, Rating, RecipeVideo, RecipeYield,
+    ScrapeError, ScrapeWarning, ScrapedRecipe, ScrapedStep, Scraper, Selector, SelectorRules,
+    SiteScraper,
+};
+use std::{collections::HashMap, time::Duration};
 
 macro_rules! include_testdata {
     ($x:expr) => {
@@ -85,6 +90,7 @@ fn scrape_from_cache_html() {
     assert_eq!(res.ingredients.len(), 17);
     assert_eq!(res.instructions.len(), 16);
     assert_eq!(res.image, Some("https://i1.wp.com/smittenkitchen.com/wp-content/uploads//2018/04/crispy-tofu-pad-thai.jpg?fit=1200%2C800&ssl=1".to_string()));
+    assert_eq!(res.name, "Crispy Tofu Pad Thai");
 }
 #[test]
 fn json() {
@@ -132,3 +138,796 @@ fn handle_no_ldjson() {
         ScrapeError::Parse(_)
     ));
 }
+#[test]
+fn scrape_microdata() {
+    let res = scrape(include_testdata!("microdata.html"), "https://microdata.com").unwrap();
+    assert_eq!(res.name, "Buttermilk Pancakes");
+    assert_eq!(
+        res.ingredients,
+        vec![
+            "2 cups all-purpose flour",
+            "2 tbsp sugar",
+            "2 cups buttermilk",
+            "2 large eggs"
+        ]
+    );
+    assert_eq!(res.instructions.len(), 3);
+    assert_eq!(res.instructions[2], "Cook on a hot griddle.");
+    assert_eq!(
+        res.image,
+        Some("https://example.com/pancakes.jpg".to_string())
+    );
+}
+#[test]
+fn scrape_structured_markup() {
+    let rdfa = scrape(include_testdata!("rdfa.html"), "https://microdata.com").unwrap();
+    let microdata = scrape(include_testdata!("microdata.html"), "https://microdata.com").unwrap();
+    assert_eq!(rdfa, microdata);
+    let ld = scrape_from_json(
+        r#"{
+  "name": "Buttermilk Pancakes",
+  "author": {"@type": "Person", "name": "Jane Doe"},
+  "recipeCuisine": "American",
+  "keywords": "breakfast, brunch",
+  "aggregateRating": {"@type": "AggregateRating", "ratingValue": "4.5", "ratingCount": 12},
+  "image": "https://example.com/pancakes.jpg",
+  "recipeIngredient": ["2 cups all-purpose flour", "2 tbsp sugar", "2 cups buttermilk", "2 large eggs"],
+  "recipeInstructions": [
+    {"@type": "HowToStep", "text": "Whisk the flour and sugar."},
+    {"@type": "HowToStep", "text": "Stir in the buttermilk and eggs."},
+    {"@type": "HowToStep", "text": "Cook on a hot griddle."}
+  ]
+}"#,
+        "https://microdata.com",
+    )
+    .unwrap();
+    assert_eq!(ld.raw.as_ref().unwrap()["name"], "Buttermilk Pancakes");
+    // markup isn't ld+json
+    let ld = ScrapedRecipe { raw: None, ..ld };
+    assert_eq!(rdfa, ld);
+    assert_eq!(ld.author, Some("Jane Doe".to_string()));
+    assert_eq!(ld.keywords, vec!["breakfast", "brunch"]);
+    assert_eq!(
+        ld.rating,
+        Some(Rating {
+            value: 4.5,
+            count: Some(12)
+        })
+    );
+    let h_recipe = scrape(include_testdata!("h-recipe.html"), "https://microdata.com").unwrap();
+    // microformats don't have ratings or keywords
+    assert_eq!(
+        h_recipe,
+        ScrapedRecipe {
+            rating: None,
+            cuisine: vec![],
+            keywords: vec![],
+            ..ld
+        }
+    );
+}
+#[test]
+fn scrape_multiple_ld_json() {
+    let res = scrape(
+        include_testdata!("multiple_ld_json.html"),
+        "https://example.com",
+    )
+    .unwrap();
+    assert_eq!(res.name, "Buttermilk Pancakes");
+    assert_eq!(res.ingredients.len(), 4);
+
+    // no recipe in the ld+json, but there is in the microdata
+    let html = include_testdata!("microdata.html").replace(
+        "<head>",
+        r#"<head><script type="application/ld+json">{"@type": "Organization"}</script>"#,
+    );
+    let res = scrape(&html, "https://example.com").unwrap();
+    assert_eq!(res.name, "Buttermilk Pancakes");
+}
+#[test]
+fn json_top_level_array() {
+    let r = scrape_from_json(
+        r#"[
+  {"@type": "WebSite", "name": "Example Kitchen", "url": "https://example.com"},
+  {"@type": "Recipe", "name": "Toast", "recipeIngredient": ["1 slice bread"], "recipeInstructions": [{"@type": "HowToStep", "text": "Toast the bread."}]},
+  {"@type": "Organization", "name": "Example Kitchen"}
+]"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(r.name, "Toast");
+    assert_eq!(r.instructions, vec!["Toast the bread."]);
+
+    let e =
+        scrape_from_json(r#"[{"@type": "WebSite", "name": "Example Kitchen"}]"#, "a").unwrap_err();
+    assert!(matches!(e, ScrapeError::LDJSONMissingRecipe(_, 1)));
+    assert_eq!(e.url(), Some("a"));
+}
+#[test]
+fn json_how_to_sections() {
+    let r = scrape_from_json(
+        r#"{
+  "name": "Pie",
+  "recipeIngredient": ["1 cup flour"],
+  "recipeInstructions": [
+    {"@type": "HowToStep", "text": "Preheat the oven."},
+    {"@type": "HowToSection", "name": "For the dough", "itemListElement": [
+      {"@type": "HowToStep", "text": "Mix."},
+      {"@type": "HowToStep", "name": "Chill."}
+    ]},
+    {"@type": "HowToSection", "name": "For the filling", "itemListElement": [
+      {"@type": "HowToSection", "name": "Apples", "itemListElement": [
+        {"@type": "HowToStep", "text": "Slice the apples."}
+      ]},
+      {"@type": "HowToStep", "url": "https://example.com#step-4"}
+    ]}
+  ]
+}"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(
+        r.instructions,
+        vec!["Preheat the oven.", "Mix.", "Chill.", "Slice the apples."]
+    );
+    assert_eq!(
+        r.instruction_groups,
+        vec![
+            (None, vec!["Preheat the oven.".to_string()]),
+            (
+                Some("For the dough".to_string()),
+                vec!["Mix.".to_string(), "Chill.".to_string()]
+            ),
+            (
+                Some("Apples".to_string()),
+                vec!["Slice the apples.".to_string()]
+            ),
+        ]
+    );
+}
+#[test]
+fn json_type_variants() {
+    let recipe = r#""name": "Toast", "recipeIngredient": ["1 slice bread"], "recipeInstructions": "<p>Toast the bread.</p>""#;
+    for json in [
+        format!(r#"{{"@type": ["Recipe", "NewsArticle"], {recipe}}}"#),
+        format!(r#"{{"@type": "recipe", {recipe}}}"#),
+        format!(
+            r#"{{"@graph": [{{"@type": "WebPage"}}, {{"@type": ["NewsArticle", "schema:Recipe"], {recipe}}}]}}"#
+        ),
+    ] {
+        let r = scrape_from_json(&json, "a").unwrap();
+        assert_eq!(r.instructions, vec!["Toast the bread."], "{json}");
+    }
+}
+#[test]
+fn json_recipe_yield() {
+    let recipe_yield = |y: &str| {
+        scrape_from_json(
+            &format!(
+                r#"{{"name": "Toast", "recipeIngredient": [], "recipeInstructions": [], "recipeYield": {y}}}"#
+            ),
+            "a",
+        )
+        .unwrap()
+        .yield_
+    };
+    let y = recipe_yield(r#"["4", "4 to 6 servings"]"#).unwrap();
+    assert_eq!(y.raw, "4 to 6 servings");
+    let parsed = y.parsed.unwrap();
+    assert_eq!((parsed.value, parsed.upper), (4.0, Some(6.0)));
+    assert_eq!(recipe_yield("12").unwrap().parsed.unwrap().unit, "servings");
+    assert_eq!(
+        recipe_yield(r#""varies""#),
+        Some(RecipeYield {
+            raw: "varies".to_string(),
+            parsed: None
+        })
+    );
+    assert_eq!(recipe_yield("null"), None);
+
+    let res = scrape_url("https://smittenkitchen.com/2018/04/crispy-tofu-pad-thai/").unwrap();
+    assert!(res.yield_.is_some());
+}
+#[test]
+fn json_times() {
+    let r = scrape_from_json(
+        r#"{"name": "Toast", "recipeIngredient": [], "recipeInstructions": [],
+        "prepTime": "PT5M", "cookTime": "about 3 minutes", "totalTime": 8}"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(r.prep_time, Some(Duration::from_secs(5 * 60)));
+    assert_eq!(r.cook_time, None);
+    assert_eq!(r.total_time, None);
+
+    let r = scrape_from_json(
+        r#"{"name": "Toast", "recipeIngredient": [], "recipeInstructions": [],
+        "cookTime": "P99999999999999999999D"}"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(r.cook_time, None);
+}
+#[test]
+fn json_metadata() {
+    let r = scrape_from_json(
+        r#"{"name": "Toast", "recipeIngredient": [], "recipeInstructions": [],
+        "author": [{"@type": "Person", "name": "Jane Doe"}, "John Doe"],
+        "recipeCategory": ["Breakfast", "Snack"],
+        "aggregateRating": {"ratingValue": 4, "reviewCount": "3"},
+        "tool": [{"@type": "HowToTool", "name": "toaster"}, "butter knife", {"@type": "HowToTool"}]}"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(r.author, Some("Jane Doe, John Doe".to_string()));
+    assert_eq!(r.category, vec!["Breakfast", "Snack"]);
+    assert_eq!(r.cuisine, Vec::<String>::new());
+    assert_eq!(r.equipment, vec!["toaster", "butter knife"]);
+    assert_eq!(
+        r.rating,
+        Some(Rating {
+            value: 4.0,
+            count: Some(3)
+        })
+    );
+}
+#[test]
+fn json_steps() {
+    let r = scrape_from_json(
+        r#"{"name": "Toast", "recipeIngredient": [], "recipeInstructions": [
+          {"@type": "HowToStep", "name": "Toast", "text": "Toast the bread.",
+           "url": "https://example.com/toast#step-1", "image": {"@type": "ImageObject", "url": "https://example.com/toast.jpg"}},
+          {"@type": "HowToStep", "text": "Butter it.", "name": "Butter it."}
+        ]}"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(
+        r.steps,
+        vec![
+            ScrapedStep {
+                text: "Toast the bread.".to_string(),
+                name: Some("Toast".to_string()),
+                image: Some("https://example.com/toast.jpg".to_string()),
+                url: Some("https://example.com/toast#step-1".to_string()),
+            },
+            ScrapedStep {
+                text: "Butter it.".to_string(),
+                ..Default::default()
+            }
+        ]
+    );
+    assert_eq!(r.instructions, vec!["Toast the bread.", "Butter it."]);
+}
+#[test]
+fn scrape_all_recipes() {
+    let recipe = |name: &str| {
+        format!(
+            r#"{{"@type": "Recipe", "name": "{name}", "recipeIngredient": ["1 slice bread"], "recipeInstructions": "<p>Toast.</p>"}}"#
+        )
+    };
+    let html = format!(
+        r#"<html><head><script type="application/ld+json">{{"@context": "https://schema.org", "@graph": [{}, {{"@type": "WebPage"}}, {}]}}</script></head></html>"#,
+        recipe("Toast"),
+        recipe("French toast")
+    );
+    let names: Vec<String> = scrape_all(&html, "a")
+        .unwrap()
+        .into_iter()
+        .map(|r| r.name)
+        .collect();
+    assert_eq!(names, vec!["Toast", "French toast"]);
+    assert_eq!(scrape(&html, "a").unwrap().name, "Toast");
+
+    let microdata = include_testdata!("microdata.html");
+    let html = microdata.replace("</body>", &format!("{microdata}</body>"));
+    assert_eq!(scrape_all(&html, "a").unwrap().len(), 2);
+
+    let res = scrape_all(
+        get_testdata()["https://smittenkitchen.com/2018/04/crispy-tofu-pad-thai/"].as_str(),
+        "a",
+    )
+    .unwrap();
+    assert_eq!(res.len(), 1);
+    assert!(scrape_all(include_testdata!("missing.html"), "a").is_err());
+}
+#[test]
+fn scrape_meta_fallback() {
+    let html = format!(
+        r#"<html><head>
+        <title>Toast | Example Kitchen</title>
+        <meta property="og:image" content="https://example.com/toast.jpg" />
+        <meta property="og:description" content="The best toast." />
+        <script type="application/ld+json">{}</script>
+        </head></html>"#,
+        r#"{"@type": "Recipe", "name": "", "recipeIngredient": [], "recipeInstructions": []}"#
+    );
+    let res = scrape(&html, "a").unwrap();
+    assert_eq!(res.name, "Toast | Example Kitchen");
+    assert_eq!(res.image, Some("https://example.com/toast.jpg".to_string()));
+    assert_eq!(res.description, Some("The best toast.".to_string()));
+    // the recipe's own description comes first
+    let html = html.replace(
+        r#""name": "","#,
+        r#""name": "", "description": " Crunchy. ","#,
+    );
+    assert_eq!(
+        scrape(&html, "a").unwrap().description,
+        Some("Crunchy.".to_string())
+    );
+}
+#[test]
+fn scrape_wprm() {
+    let res = scrape(include_testdata!("wprm.html"), "https://wprm.com").unwrap();
+    assert_eq!(res.name, "Garlic Butter Noodles");
+    assert_eq!(
+        res.ingredients,
+        vec![
+            "8 oz spaghetti",
+            "4 cloves garlic, minced",
+            "¼ cup unsalted butter",
+            "salt, to taste"
+        ]
+    );
+    assert_eq!(
+        res.instructions[1],
+        "Melt the butter and gently fry the garlic."
+    );
+    assert_eq!(
+        res.image,
+        Some("https://example.com/noodles.jpg".to_string())
+    );
+    assert_eq!(
+        res.notes,
+        Some(
+            "Use salted butter and leave out the salt.\nKeeps for 2 days in the fridge."
+                .to_string()
+        )
+    );
+}
+#[test]
+fn scrape_tasty() {
+    let res = scrape(include_testdata!("tasty.html"), "https://tasty.com").unwrap();
+    assert_eq!(res.name, "Olive Oil Cake");
+    assert_eq!(
+        res.ingredients,
+        vec![
+            "1.5 cup all-purpose flour",
+            "3 large eggs",
+            "0.75 cup extra virgin olive oil",
+            "Pinch of salt"
+        ]
+    );
+    assert_eq!(res.instructions.len(), 2);
+    assert_eq!(res.image, Some("https://example.com/cake.jpg".to_string()));
+    assert_eq!(
+        res.notes,
+        Some("Use a fruity olive oil.\nKeeps for 3 days wrapped at room temperature.".to_string())
+    );
+}
+#[test]
+fn scrape_jetpack() {
+    // the card is found by its markup, on any site
+    let html = r#"<html><body><div class="jetpack-recipe">
+        <ul><li class="jetpack-recipe-ingredient">2 eggs</li></ul>
+        <div class="jetpack-recipe-directions">Whisk the eggs.</div>
+        </div></body></html>"#;
+    let res = scrape(html, "https://example.com/eggs").unwrap();
+    assert_eq!(res.ingredients, vec!["2 eggs"]);
+    assert_eq!(res.instructions, vec!["Whisk the eggs."]);
+    assert!(Scraper::new()
+        .scrape(html, "https://example.com/eggs")
+        .is_err());
+}
+#[test]
+fn scrape_registered_site() {
+    struct Notes;
+    impl SiteScraper for Notes {
+        fn matches(&self, url: &str) -> bool {
+            url.starts_with("https://notes.example.com/")
+        }
+        fn scrape(&self, dom: &Html) -> Result<ScrapedRecipe, ScrapeError> {
+            let select = |s| {
+                let selector = Selector::parse(s).unwrap();
+                dom.select(&selector)
+                    .map(|e| e.text().collect::<String>())
+                    .collect::<Vec<_>>()
+            };
+            Ok(ScrapedRecipe {
+                name: select("h1").join(""),
+                ingredients: select(".ingredients p"),
+                instructions: select(".method p"),
+                ..Default::default()
+            })
+        }
+    }
+    let html = r#"<html><body><h1>Toast</h1>
+        <div class="ingredients"><p>1 slice bread</p></div>
+        <div class="method"><p>Toast the bread.</p></div>
+        </body></html>"#;
+
+    let mut scraper = Scraper::default();
+    scraper.register(Notes);
+    let res = scraper
+        .scrape(html, "https://notes.example.com/toast")
+        .unwrap();
+    assert_eq!(res.name, "Toast");
+    assert_eq!(res.ingredients, vec!["1 slice bread"]);
+    assert_eq!(res.instructions, vec!["Toast the bread."]);
+    assert_eq!(res.url, "https://notes.example.com/toast");
+
+    assert!(scraper.scrape(html, "https://example.com/toast").is_err());
+    assert!(scrape(html, "https://notes.example.com/toast").is_err());
+    // structured recipes still come first
+    let res = scraper
+        .scrape(
+            include_testdata!("microdata.html"),
+            "https://notes.example.com/",
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        scrape(
+            include_testdata!("microdata.html"),
+            "https://notes.example.com/"
+        )
+        .unwrap()
+    );
+}
+#[test]
+fn scrape_selector_rules() {
+    let rules: Vec<SelectorRules> = serde_json::from_str(
+        r#"[
+            {"domain": "toast.example.com", "ingredients": ".i p", "instructions": ".m p"},
+            {"domain": "notes.example.com", "ingredients": ".ingredients p",
+             "instructions": ".method p", "name": "h1", "image": ".hero img"}
+        ]"#,
+    )
+    .unwrap();
+    let mut scraper = Scraper::default();
+    for r in rules {
+        scraper.register(r);
+    }
+    let html = r#"<html><body><h1>Toast</h1>
+        <div class="hero"><img src="https://notes.example.com/toast.jpg"></div>
+        <div class="ingredients"><p>1  slice
+            bread</p><p></p></div>
+        <div class="method"><p>Toast the bread.</p></div>
+        </body></html>"#;
+
+    let res = scraper
+        .scrape(html, "https://NOTES.example.com:8080/toast?print=1")
+        .unwrap();
+    assert_eq!(res.name, "Toast");
+    assert_eq!(res.ingredients, vec!["1 slice bread"]);
+    assert_eq!(res.instructions, vec!["Toast the bread."]);
+    assert_eq!(
+        res.image,
+        Some("https://notes.example.com/toast.jpg".to_string())
+    );
+
+    assert!(scraper.scrape(html, "https://toast.example.com/").is_err());
+    assert!(scraper.scrape(html, "https://example.com/toast").is_err());
+    assert!(scraper
+        .scrape(html, "https://notes.example.com.evil.com/")
+        .is_err());
+}
+#[test]
+fn scrape_canonical_url() {
+    let html = |head: &str| {
+        format!(
+            r#"<html><head>{head}<script type="application/ld+json">{}</script></head></html>"#,
+            r#"{"@type": "Recipe", "name": "Toast", "recipeIngredient": ["1 slice bread"], "recipeInstructions": []}"#
+        )
+    };
+    let url = "https://m.example.com/toast/amp?utm_source=feed";
+    let res = scrape(&html(r#"<link rel="canonical" href="/toast/">"#), url).unwrap();
+    assert_eq!(res.url, url);
+    assert_eq!(
+        res.canonical_url,
+        Some("https://m.example.com/toast/".to_string())
+    );
+    let res = scrape(
+        &html(r#"<meta property="og:url" content="https://example.com/toast/">"#),
+        url,
+    )
+    .unwrap();
+    assert_eq!(
+        res.canonical_url,
+        Some("https://example.com/toast/".to_string())
+    );
+    assert_eq!(scrape(&html(""), url).unwrap().canonical_url, None);
+}
+
+#[test]
+fn scrape_file() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/microdata.html");
+    let res = Scraper::default().scrape_file(path).unwrap();
+    assert!(res.url.starts_with("file://"));
+    assert!(res.url.ends_with("/test_data/microdata.html"));
+    assert!(!res.ingredients.is_empty());
+    assert!(matches!(
+        Scraper::default().scrape_file("test_data/nope.html"),
+        Err(ScrapeError::Io(_))
+    ));
+}
+
+#[test]
+fn scrape_outcome() {
+    let scraper = Scraper::default();
+    let outcome = scraper
+        .scrape_outcome(
+            include_testdata!("microdata.html"),
+            "https://example.com/microdata",
+        )
+        .unwrap();
+    assert!(outcome.is_complete());
+    assert!(!outcome.warnings.contains(&ScrapeWarning::NoIngredients));
+
+    let page = r#"<html><head><title>Grandma's toast</title>
+        <meta property="og:image" content="https://example.com/toast.jpg"></head>
+        <body><p>coming soon</p></body></html>"#;
+    let outcome = scraper
+        .scrape_outcome(page, "https://example.com/toast")
+        .unwrap();
+    assert!(!outcome.is_complete());
+    assert_eq!(outcome.recipe.name, "Grandma's toast");
+    assert_eq!(
+        outcome.recipe.image,
+        Some("https://example.com/toast.jpg".to_string())
+    );
+    assert_eq!(
+        outcome.warnings[1..],
+        [ScrapeWarning::NoIngredients, ScrapeWarning::NoInstructions]
+    );
+    assert!(scraper
+        .scrape_outcome("<p>coming soon</p>", "https://example.com/toast")
+        .is_err());
+
+    let e = ScrapeError::Status {
+        url: "https://example.com/toast".to_string(),
+        status: 404,
+    };
+    assert_eq!(
+        (e.url(), e.status()),
+        (Some("https://example.com/toast"), Some(404))
+    );
+    assert_eq!(
+        e.to_string(),
+        "`https://example.com/toast` responded with 404"
+    );
+}
+
+#[test]
+fn scrape_ingredient_groups() {
+    let ld = |ingredients: &str| {
+        format!(
+            r#"<script type="application/ld+json">{{"@type": "Recipe", "name": "Buns", "recipeIngredient": {ingredients}, "recipeInstructions": []}}</script>"#
+        )
+    };
+    let res = scrape(
+        &ld(r#"["For the dough:", "500 g flour", "1 egg", "For the glaze:", "2 tbsp honey"]"#),
+        "https://example.com/buns",
+    )
+    .unwrap();
+    assert_eq!(
+        res.ingredients,
+        vec!["500 g flour", "1 egg", "2 tbsp honey"]
+    );
+    assert_eq!(
+        res.ingredient_groups,
+        vec![
+            (
+                Some("For the dough".to_string()),
+                vec!["500 g flour".to_string(), "1 egg".to_string()]
+            ),
+            (
+                Some("For the glaze".to_string()),
+                vec!["2 tbsp honey".to_string()]
+            ),
+        ]
+    );
+
+    let res = scrape(
+        &ld(r#"["1 pinch salt", {"@type": "HowToSection", "name": "Glaze", "itemListElement": ["2 tbsp honey"]}]"#),
+        "https://example.com/buns",
+    )
+    .unwrap();
+    assert_eq!(res.ingredients, vec!["1 pinch salt", "2 tbsp honey"]);
+    assert_eq!(
+        res.ingredient_groups,
+        vec![
+            (None, vec!["1 pinch salt".to_string()]),
+            (Some("Glaze".to_string()), vec!["2 tbsp honey".to_string()]),
+        ]
+    );
+
+    let res = scrape(
+        &ld(r#"["1 cup flour", "Salt, for seasoning"]"#),
+        "https://example.com/buns",
+    )
+    .unwrap();
+    assert_eq!(res.ingredients.len(), 2);
+    assert!(res.ingredient_groups.is_empty());
+
+    let wprm = r#"<div class="wprm-recipe"><h2 class="wprm-recipe-name">Buns</h2>
+        <div class="wprm-recipe-ingredient-group"><h4 class="wprm-recipe-group-name">Dough</h4>
+        <ul><li class="wprm-recipe-ingredient">500 g flour</li></ul></div>
+        <div class="wprm-recipe-ingredient-group"><h4 class="wprm-recipe-group-name">Glaze</h4>
+        <ul><li class="wprm-recipe-ingredient">2 tbsp honey</li></ul></div>
+        <div class="wprm-recipe-instruction-text">Bake.</div></div>"#;
+    let res = scrape(wprm, "https://example.com/buns").unwrap();
+    assert_eq!(res.ingredients, vec!["500 g flour", "2 tbsp honey"]);
+    assert_eq!(
+        res.ingredient_groups,
+        vec![
+            (Some("Dough".to_string()), vec!["500 g flour".to_string()]),
+            (Some("Glaze".to_string()), vec!["2 tbsp honey".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn parsed_ingredients() {
+    let recipe = ScrapedRecipe {
+        ingredients: vec!["1 cup flour".to_string(), "flour 00 type".to_string()],
+        ..Default::default()
+    };
+    let parsed = recipe.parsed_ingredients();
+    assert_eq!(parsed[0].as_ref().unwrap().to_string(), "1 cup flour");
+
+    let strict = IngredientParser::builder().mode(ParseMode::Strict).build();
+    let parsed = recipe.parsed_ingredients_with(&strict);
+    assert!(parsed[0].is_ok());
+    assert!(matches!(parsed[1], Err(ScrapeError::Parse(_))));
+
+    let lenient = IngredientParser::builder().mode(ParseMode::Lenient).build();
+    let parsed = recipe.parsed_ingredients_with(&lenient);
+    assert_eq!(parsed[1].as_ref().unwrap().name, "flour 00 type");
+}
+
+#[test]
+fn ld_json_round_trip() {
+    for (url, html) in get_testdata() {
+        let r = match scrape(&html, &url) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let ld = r.to_ld_json();
+        assert_eq!(ld["@type"], "Recipe");
+        // as pages do, so that it reads the same as the script's html
+        let ld = ld
+            .to_string()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026");
+        let html = format!(r#"<script type="application/ld+json">{ld}</script>"#);
+        let again = scrape(&html, &url).unwrap();
+        assert_eq!(
+            again,
+            ScrapedRecipe {
+                canonical_url: None,
+                // what it was read from this time
+                raw: again.raw.clone(),
+                ..r
+            },
+            "{url}"
+        );
+    }
+}
+
+#[test]
+fn scrape_language() {
+    let ld = r#"{"@type": "Recipe", "name": "Kuchen", "recipeIngredient": ["2 EL Zucker"], "recipeInstructions": [{"@type": "HowToStep", "text": "Zucker einrühren."}]}"#;
+    let page = |lang: &str, ld: &str| {
+        format!(r#"<html lang="{lang}"><script type="application/ld+json">{ld}</script></html>"#)
+    };
+    let r = scrape(&page("de-DE", ld), "https://example.de").unwrap();
+    assert_eq!(r.language, Some("de-DE".to_string()));
+    assert_eq!(
+        r.parsed_ingredients()[0].as_ref().unwrap().to_string(),
+        "2 tbsp Zucker"
+    );
+
+    // the recipe's own language goes first
+    let with_language = ld.replace(r#""name""#, r#""inLanguage": "fr", "name""#);
+    let r = scrape(&page("en", &with_language), "https://example.de").unwrap();
+    assert_eq!(r.language, Some("fr".to_string()));
+
+    let r = scrape_from_json(
+        r#"{"@type": "Recipe", "name": "Toast", "recipeIngredient": ["1 slice bread"], "recipeInstructions": [{"@type": "HowToStep", "text": "Toast the bread until golden, then add the butter."}]}"#,
+        "https://example.com",
+    )
+    .unwrap();
+    assert_eq!(r.language, Some("en".to_string()));
+}
+
+#[test]
+fn scrape_video() {
+    let r = scrape_from_json(
+        r#"{"@type": "Recipe", "name": "Toast", "recipeIngredient": [], "recipeInstructions": [],
+        "video": [
+            {"@type": "VideoObject", "name": "Teaser"},
+            {"@type": "VideoObject", "name": "How to make toast",
+             "contentUrl": "https://example.com/toast.mp4",
+             "embedUrl": "https://player.example.com/embed/toast",
+             "thumbnailUrl": ["https://example.com/toast-16x9.jpg", "https://example.com/toast-4x3.jpg"]}
+        ]}"#,
+        "a",
+    )
+    .unwrap();
+    assert_eq!(
+        r.video,
+        Some(RecipeVideo {
+            name: Some("How to make toast".to_string()),
+            content_url: Some("https://example.com/toast.mp4".to_string()),
+            embed_url: Some("https://player.example.com/embed/toast".to_string()),
+            thumbnail: Some("https://example.com/toast-16x9.jpg".to_string()),
+        })
+    );
+
+    let html = r#"<div itemscope itemtype="https://schema.org/Recipe">
+        <h1 itemprop="name">Toast</h1>
+        <li itemprop="recipeIngredient">1 slice bread</li>
+        <li itemprop="recipeInstructions">Toast the bread.</li>
+        <div itemprop="video" itemscope itemtype="https://schema.org/VideoObject">
+            <meta itemprop="name" content="Toast author" />
+            <meta itemprop="embedUrl" content="https://player.example.com/embed/toast" />
+        </div>
+    </div>"#;
+    let r = scrape(html, "a").unwrap();
+    assert_eq!(
+        r.video.and_then(|v| v.embed_url),
+        Some("https://player.example.com/embed/toast".to_string())
+    );
+}
+
+#[test]
+fn scrape_large_page() {
+    let ld = r#"{"@type": "Recipe", "name": "Toast", "recipeIngredient": ["1 slice bread"], "recipeInstructions": [{"@type": "HowToStep", "text": "Toast it."}]}"#;
+    let comments = "<div class=\"comment\">Lovely!</div>\n".repeat(50_000);
+    let page = format!(
+        r#"<html lang="en"><head><meta property="og:image" content="https://example.com/toast.jpg"></head>
+<body>{comments}<script type="application/ld+json">{ld}</script></body></html>"#
+    );
+    assert!(page.len() > 1024 * 1024);
+    let r = scrape(&page, "https://example.com/toast").unwrap();
+    assert_eq!(r.name, "Toast");
+    assert_eq!(r.instructions, vec!["Toast it."]);
+    assert_eq!(r.image, Some("https://example.com/toast.jpg".to_string()));
+    assert_eq!(r.language, Some("en".to_string()));
+    assert_eq!(
+        scrape_all(&page, "https://example.com/toast").unwrap(),
+        vec![r]
+    );
+}
+#[test]
+fn scrape_walls() {
+    let challenge = r#"<html><head><title>Just a moment...</title></head>
+<body><div id="cf-browser-verification">Checking your browser</div></body></html>"#;
+    let e = scrape(challenge, "https://example.com/toast").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "`https://example.com/toast` is blocked: Cloudflare challenge"
+    );
+    assert_eq!(e.url(), Some("https://example.com/toast"));
+    assert!(Scraper::default()
+        .scrape_outcome(challenge, "https://example.com/toast")
+        .is_err());
+
+    let paywall = r#"<html><head><title>Toast</title>
+<script type="application/ld+json">{"@type": "WebPage", "isAccessibleForFree": false}</script>
+</head><body><p>Subscribe to view this recipe.</p></body></html>"#;
+    assert!(matches!(
+        scrape(paywall, "https://example.com/toast"),
+        Err(ScrapeError::Paywalled { .. })
+    ));
+    // it's only a wall when there's no recipe
+    let recipe = r#"<script type="application/ld+json">{"@type": "Recipe", "name": "Toast",
+"recipeIngredient": ["1 slice bread"], "recipeInstructions": "Toast it.", "isAccessibleForFree": false}
+</script>"#;
+    assert_eq!(
+        scrape(recipe, "https://example.com/toast").unwrap().name,
+        "Toast"
+    );
+}
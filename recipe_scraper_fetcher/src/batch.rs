@@ -0,0 +1,103 @@
+//! Scraping many urls at once, e.g. for importing a list of bookmarks.
+use std::{collections::BTreeMap, fmt};
+
+use futures_util::stream::{self, StreamExt};
+use recipe_scraper::{ScrapeError, ScrapedRecipe};
+
+use crate::{telemetry::error_kind, Fetcher};
+
+/// What [Fetcher::scrape_urls] found at each url, in the order they were given.
+#[derive(Debug)]
+pub struct BatchResults(pub Vec<(String, Result<ScrapedRecipe, ScrapeError>)>);
+
+impl BatchResults {
+    pub fn recipes(&self) -> impl Iterator<Item = &ScrapedRecipe> {
+        self.0.iter().filter_map(|(_, r)| r.as_ref().ok())
+    }
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &ScrapeError)> {
+        self.0
+            .iter()
+            .filter_map(|(url, r)| r.as_ref().err().map(|e| (url.as_str(), e)))
+    }
+    /// how many urls failed with each kind of error, for a summary of what went wrong
+    pub fn error_counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for (_, e) in self.failures() {
+            *counts.entry(error_kind(e)).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// e.g. `9 scraped, 3 failed (Http: 2, NoLDJSON: 1)`
+impl fmt::Display for BatchResults {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let failed = self.failures().count();
+        write!(f, "{} scraped, {} failed", self.0.len() - failed, failed)?;
+        if failed > 0 {
+            let counts: Vec<String> = self
+                .error_counts()
+                .iter()
+                .map(|(kind, n)| format!("{kind}: {n}"))
+                .collect();
+            write!(f, " ({})", counts.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Fetcher {
+    /// Scrapes each of `urls`, with up to `concurrency` at a time.
+    /// They share the fetcher's cache, rate limit and retries, and one failing doesn't stop the rest.
+    pub async fn scrape_urls<I, S>(&self, urls: I, concurrency: usize) -> BatchResults
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let results = stream::iter(urls.into_iter().map(Into::into))
+            .map(|url: String| async move {
+                let res = self.scrape_url(&url).await;
+                (url, res)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        BatchResults(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scrape_urls() {
+        let recipe = r#"<script type="application/ld+json">
+            {"@type": "Recipe", "name": "Toast", "recipeIngredient": ["1 slice bread"], "recipeInstructions": []}
+            </script>"#;
+        let fetcher = Fetcher::new().with_transport(HashMap::from([
+            ("https://example.com/toast".to_string(), recipe.to_string()),
+            (
+                "https://example.com/about".to_string(),
+                "<p>hi</p>".to_string(),
+            ),
+        ]));
+        let results = fetcher
+            .scrape_urls(
+                [
+                    "https://example.com/toast",
+                    "https://example.com/about",
+                    "https://example.com/toast",
+                ],
+                2,
+            )
+            .await;
+        assert_eq!(results.0.len(), 3);
+        assert_eq!(results.0[1].0, "https://example.com/about");
+        assert_eq!(results.recipes().count(), 2);
+        assert_eq!(results.failures().count(), 1);
+        assert_eq!(results.to_string(), "2 scraped, 1 failed (Parse: 1)");
+    }
+}
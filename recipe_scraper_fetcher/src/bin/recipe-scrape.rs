@@ -0,0 +1,152 @@
+//! Scrapes recipes from the command line, e.g.
+//! `recipe-scrape --cache ~/.cache/recipes --format markdown https://example.com/toast`.
+use std::{
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, ValueEnum};
+use recipe_scraper::ScrapedRecipe;
+use recipe_scraper_fetcher::{cache::DirCache, Fetcher};
+use serde_json::Value;
+
+#[derive(Parser)]
+#[clap(author, version, about = "Scrapes recipes from urls", long_about = None)]
+struct Cli {
+    /// pages to scrape
+    urls: Vec<String>,
+    /// a file of urls to scrape, one per line, `-` for stdin
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// how many pages are fetched at once
+    #[arg(short, long, default_value_t = 4)]
+    concurrency: usize,
+    /// keeps fetched pages in this directory, and scrapes them from it next time
+    #[arg(long)]
+    cache: Option<PathBuf>,
+    /// asks sites whether cached pages have changed
+    #[arg(long, requires = "cache")]
+    revalidate: bool,
+    /// saves every response to this directory, to scrape them again with --replay
+    #[arg(long)]
+    record: Option<PathBuf>,
+    /// scrapes pages only from the responses saved with --record, without fetching any
+    #[arg(long, conflicts_with = "record")]
+    replay: Option<PathBuf>,
+    /// scrapes pages' AMP or print versions instead when they link to one
+    #[arg(long)]
+    lighter: bool,
+    #[arg(short, long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    /// adds the ingredients and instructions parsed by the `ingredient` crate, as `parsed`,
+    /// to json and yaml
+    #[arg(short, long)]
+    parse: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Yaml,
+    /// RecipeMD
+    Markdown,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut urls = cli.urls.clone();
+    if let Some(input) = &cli.input {
+        match read_urls(input) {
+            Ok(more) => urls.extend(more),
+            Err(e) => {
+                eprintln!("could not read {}: {e}", input.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if urls.is_empty() {
+        eprintln!("no urls to scrape, give some or a file of them with --input");
+        return ExitCode::FAILURE;
+    }
+
+    let mut fetcher = Fetcher::new()
+        .revalidate(cli.revalidate)
+        .prefer_lighter_pages(cli.lighter);
+    if let Some(dir) = &cli.cache {
+        fetcher = fetcher.with_cache(DirCache::new(dir));
+    }
+    if let Some(dir) = &cli.record {
+        fetcher = fetcher.record(dir);
+    }
+    if let Some(dir) = &cli.replay {
+        fetcher = fetcher.replay(dir);
+    }
+    let many = urls.len() > 1;
+    let results = fetcher.scrape_urls(urls, cli.concurrency).await;
+    for (url, e) in results.failures() {
+        eprintln!("{url}: {e}");
+    }
+    let recipes: Vec<&ScrapedRecipe> = results.recipes().collect();
+    match write(&recipes, many, &cli) {
+        Ok(out) => print!("{out}"),
+        Err(e) => {
+            eprintln!("could not write the recipes: {e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    eprintln!("{results}");
+    let failed = results.failures().next().is_some();
+    match failed {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+// blank lines and `#` comments are skipped
+fn read_urls(input: &Path) -> io::Result<Vec<String>> {
+    let reader: Box<dyn BufRead> = match input.to_str() {
+        Some("-") => Box::new(BufReader::new(io::stdin())),
+        _ => Box::new(BufReader::new(std::fs::File::open(input)?)),
+    };
+    let mut urls = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !(line.is_empty() || line.starts_with('#')) {
+            urls.push(line.to_string());
+        }
+    }
+    Ok(urls)
+}
+
+fn write(recipes: &[&ScrapedRecipe], many: bool, cli: &Cli) -> Result<String, String> {
+    if let Format::Markdown = cli.format {
+        let docs: Vec<String> = recipes.iter().map(|r| r.to_recipemd()).collect();
+        return Ok(docs.join("\n"));
+    }
+    let values = recipes
+        .iter()
+        .map(|r| {
+            let mut value = serde_json::to_value(r)?;
+            if cli.parse {
+                value["parsed"] = serde_json::to_value(r.parse())?;
+            }
+            Ok(value)
+        })
+        .collect::<Result<Vec<Value>, serde_json::Error>>()
+        .map_err(|e| e.to_string())?;
+    // a list when there were several urls, even if only one of them could be scraped
+    let value = match (many, <[Value; 1]>::try_from(values)) {
+        (false, Ok([value])) => value,
+        (true, Ok(value)) => Value::Array(value.into()),
+        (_, Err(values)) => Value::Array(values),
+    };
+    match cli.format {
+        Format::Yaml => serde_yaml::to_string(&value).map_err(|e| e.to_string()),
+        _ => serde_json::to_string_pretty(&value)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+    }
+}
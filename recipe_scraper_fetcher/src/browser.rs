@@ -0,0 +1,94 @@
+//! Rendering pages in a headless Chromium, for sites that only add their ld+json with javascript.
+use std::fmt;
+
+use chromiumoxide::{Browser, BrowserConfig};
+use futures_util::StreamExt;
+use recipe_scraper::ScrapeError;
+use tokio::sync::OnceCell;
+
+/// Renders the pages of some sites, see [crate::Fetcher::render_with_browser].
+pub(crate) struct Renderer {
+    domains: Vec<String>,
+    user_agent: String,
+    // launched on the first page that needs it
+    browser: OnceCell<Browser>,
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Renderer")
+            .field("domains", &self.domains)
+            .field("launched", &self.browser.initialized())
+            .finish()
+    }
+}
+
+impl Renderer {
+    pub(crate) fn new(domains: Vec<String>, user_agent: &str) -> Self {
+        Renderer {
+            domains: domains.into_iter().map(|d| d.to_lowercase()).collect(),
+            user_agent: user_agent.to_string(),
+            browser: OnceCell::new(),
+        }
+    }
+
+    /// whether `url` is on one of the sites, or their subdomains
+    pub(crate) fn renders(&self, url: &str) -> bool {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase))
+            .unwrap_or_default();
+        self.domains
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{domain}")))
+    }
+
+    /// the html of `url` once its scripts have run
+    #[tracing::instrument(name = "render")]
+    pub(crate) async fn render(&self, url: &str) -> Result<String, ScrapeError> {
+        let browser = self.browser.get_or_try_init(|| self.launch()).await?;
+        let page = browser.new_page(url).await.map_err(http)?;
+        let html = match page.wait_for_navigation().await {
+            Ok(page) => page.content().await.map_err(http),
+            Err(e) => Err(http(e)),
+        };
+        if let Err(e) = page.close().await {
+            tracing::warn!("failed to close {}: {}", url, e);
+        }
+        html
+    }
+
+    async fn launch(&self) -> Result<Browser, ScrapeError> {
+        let config = BrowserConfig::builder()
+            .arg(format!("--user-agent={}", self.user_agent))
+            .build()
+            .map_err(ScrapeError::Http)?;
+        let (browser, mut handler) = Browser::launch(config).await.map_err(http)?;
+        // the browser only makes progress while its events are handled
+        tokio::spawn(async move {
+            while let Some(event) = handler.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(browser)
+    }
+}
+
+fn http(e: chromiumoxide::error::CdpError) -> ScrapeError {
+    ScrapeError::Http(format!("browser: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders() {
+        let renderer = Renderer::new(vec!["Example.com".to_string()], "recipe");
+        assert!(renderer.renders("https://example.com/toast"));
+        assert!(renderer.renders("https://www.example.com/toast"));
+        assert!(!renderer.renders("https://notexample.com/toast"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Configuring the http client of a [Fetcher].
+use std::{sync::Arc, time::Duration};
+
+use recipe_scraper::{ScrapeError, Scraper};
+use reqwest::{
+    cookie::Jar,
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_LANGUAGE},
+    redirect, Proxy, Url,
+};
+use reqwest_middleware::ClientWithMiddleware;
+
+use crate::{http_utils, retry::RetryPolicy, Fetcher};
+
+/// What the default [Fetcher] identifies itself as.
+pub const DEFAULT_USER_AGENT: &str = "recipe";
+
+/// Builds a [Fetcher] with a configured http client, see [Fetcher::builder].
+/// ```
+/// use std::time::Duration;
+/// use recipe_scraper_fetcher::Fetcher;
+/// let fetcher = Fetcher::builder()
+///     .user_agent("my-importer/1.0")
+///     .timeout(Duration::from_secs(10))
+///     .accept_language("fr-CA, fr;q=0.9")
+///     .redirect_limit(5)
+///     .proxy("http://proxy.example.com:3128")
+///     .cookie(&"https://cooking.example.com".parse().unwrap(), "session=abc")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FetcherBuilder {
+    pub(crate) user_agent: String,
+    timeout: Option<Duration>,
+    headers: HeaderMap,
+    redirect_limit: Option<usize>,
+    accept_language: Option<String>,
+    proxy: Option<String>,
+    // shared by the clients of every proxy, so a session carries over
+    cookies: Arc<Jar>,
+    // (domain, header)
+    domain_headers: Vec<(String, HeaderName, HeaderValue)>,
+}
+
+impl Default for FetcherBuilder {
+    fn default() -> Self {
+        FetcherBuilder {
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: None,
+            headers: HeaderMap::new(),
+            redirect_limit: None,
+            accept_language: None,
+            proxy: None,
+            cookies: Default::default(),
+            domain_headers: vec![],
+        }
+    }
+}
+
+impl FetcherBuilder {
+    /// also what `robots.txt` rules are looked up by
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+    /// for the whole request, including reading the page, none by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// sent with every request
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+    /// how many redirects are followed, 10 by default
+    pub fn redirect_limit(mut self, limit: usize) -> Self {
+        self.redirect_limit = Some(limit);
+        self
+    }
+    /// e.g. `fr-CA, fr;q=0.9`, for sites that serve recipes in several languages
+    pub fn accept_language(mut self, languages: impl Into<String>) -> Self {
+        self.accept_language = Some(languages.into());
+        self
+    }
+
+    /// Sends every request through `proxy`, e.g. `http://proxy.example.com:3128`,
+    /// or `socks5://127.0.0.1:1080` with the `socks` feature.
+    /// See [Fetcher::scrape_url_via] for using another one for some requests.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Sends `cookie` (as in a `Set-Cookie`, e.g. `session=abc; Domain=example.com`) to `url`'s site,
+    /// for scraping subscription sites with a session from a logged in browser.
+    /// Cookies the sites set are kept as well.
+    pub fn cookie(self, url: &Url, cookie: &str) -> Self {
+        self.cookies.add_cookie_str(cookie, url);
+        self
+    }
+    /// Sends a header only to `domain` and its subdomains, e.g. an `Authorization` for a membership site.
+    pub fn domain_header(
+        mut self,
+        domain: impl Into<String>,
+        name: HeaderName,
+        value: HeaderValue,
+    ) -> Self {
+        self.domain_headers
+            .push((domain.into().to_lowercase(), name, value));
+        self
+    }
+
+    pub fn build(self) -> Result<Fetcher, ScrapeError> {
+        Ok(Fetcher {
+            client: self.client(self.proxy.as_deref())?,
+            config: self,
+            proxied: Default::default(),
+            cache: None,
+            revalidate: false,
+            cache_policy: Default::default(),
+            stale: Default::default(),
+            retry: RetryPolicy::default(),
+            rate_limit: None,
+            robots: None,
+            #[cfg(feature = "browser")]
+            renderer: None,
+            scraper: Scraper::default(),
+            lighter: false,
+            transport: None,
+            recordings: None,
+        })
+    }
+
+    pub(crate) fn client(&self, proxy: Option<&str>) -> Result<ClientWithMiddleware, ScrapeError> {
+        let mut headers = self.headers.clone();
+        if let Some(languages) = &self.accept_language {
+            let value = HeaderValue::from_str(languages)
+                .map_err(|e| ScrapeError::Http(format!("accept-language `{languages}`: {e}")))?;
+            headers.insert(ACCEPT_LANGUAGE, value);
+        }
+        let mut client = reqwest::Client::builder()
+            .user_agent(self.user_agent.as_str())
+            .default_headers(headers)
+            .cookie_provider(self.cookies.clone());
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(limit) = self.redirect_limit {
+            client = client.redirect(redirect::Policy::limited(limit));
+        }
+        if let Some(proxy) = proxy {
+            let proxy = Proxy::all(proxy)
+                .map_err(|e| ScrapeError::Http(format!("proxy `{proxy}`: {e}")))?;
+            client = client.proxy(proxy);
+        }
+        let client = client
+            .build()
+            .map_err(|e| ScrapeError::Http(e.to_string()))?;
+        Ok(http_utils::http_client(client))
+    }
+
+    /// the [FetcherBuilder::domain_header]s for `url`
+    pub(crate) fn headers_for(&self, url: &str) -> HeaderMap {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase))
+            .unwrap_or_default();
+        self.domain_headers
+            .iter()
+            .filter(|(domain, _, _)| host == *domain || host.ends_with(&format!(".{domain}")))
+            .map(|(_, name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::AUTHORIZATION;
+
+    #[test]
+    fn test_headers_for() {
+        let builder = FetcherBuilder::default().domain_header(
+            "Example.com",
+            AUTHORIZATION,
+            HeaderValue::from_static("Bearer abc"),
+        );
+        let headers = builder.headers_for("https://members.example.com/recipes/toast");
+        assert_eq!(headers.get(AUTHORIZATION).unwrap(), "Bearer abc");
+        assert!(builder
+            .headers_for("https://example.com/")
+            .contains_key(AUTHORIZATION));
+        assert!(builder.headers_for("https://notexample.com/").is_empty());
+        assert!(builder
+            .headers_for("https://example.com.evil.com/")
+            .is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+//! Where fetched pages are kept, so they aren't fetched again.
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::RwLock,
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use recipe_scraper::fnv1a;
+use tracing::error;
+
+use crate::transport::Response;
+
+/// Pages by url.
+#[async_trait]
+pub trait Cache: Send + Sync + fmt::Debug {
+    async fn get(&self, url: &str) -> Option<CachedPage>;
+    async fn put(&self, url: &str, page: &CachedPage);
+}
+
+/// A fetched page, along with the validators its response had,
+/// for asking the site whether it has changed since.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CachedPage {
+    pub body: String,
+    /// the `ETag` header
+    pub etag: Option<String>,
+    /// the `Last-Modified` header
+    pub last_modified: Option<String>,
+    /// where it was redirected to, if anywhere
+    pub url: Option<String>,
+    /// `None` for pages put in the cache some other way, which don't go stale
+    pub fetched_at: Option<SystemTime>,
+    /// how long the site said it can be cached for, in its `Cache-Control` or `Expires` header
+    pub max_age: Option<Duration>,
+}
+
+impl CachedPage {
+    /// whether a conditional request can be made for it
+    pub fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+impl From<String> for CachedPage {
+    fn from(body: String) -> Self {
+        CachedPage {
+            body,
+            ..Default::default()
+        }
+    }
+}
+
+/// How long cached pages are scraped before they're fetched again, see [crate::Fetcher::cache_policy].
+/// By default they're kept for good.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CachePolicy {
+    /// fetches pages again once their [CachedPage::max_age] has passed
+    pub respect_headers: bool,
+    /// the longest a page is kept, whatever its headers say, including pages without them
+    pub max_ttl: Option<Duration>,
+    /// scrapes stale pages rather than waiting for them to be fetched again,
+    /// and fetches them on the next [crate::Fetcher::refresh_stale] instead
+    pub stale_while_revalidate: bool,
+}
+
+impl CachePolicy {
+    /// whether `page` can still be scraped at `now` without fetching it again
+    pub fn is_fresh(&self, page: &CachedPage, now: SystemTime) -> bool {
+        let Some(fetched_at) = page.fetched_at else {
+            return true;
+        };
+        let max_age = page.max_age.filter(|_| self.respect_headers);
+        let ttl = match (max_age, self.max_ttl) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => match a.or(b) {
+                Some(ttl) => ttl,
+                None => return true,
+            },
+        };
+        // a clock that went backwards keeps it fresh
+        now.duration_since(fetched_at).map_or(true, |age| age < ttl)
+    }
+}
+
+/// How long a response can be cached for, by its `Cache-Control` or else its `Expires` header.
+/// `no-cache` and `no-store` are taken to mean not at all, as is an `Expires` that isn't a date.
+pub(crate) fn max_age(res: &Response) -> Option<Duration> {
+    if let Some(cache_control) = res.header("cache-control") {
+        let directives: Vec<String> = cache_control
+            .split(',')
+            .map(|d| d.trim().to_ascii_lowercase())
+            .collect();
+        if directives
+            .iter()
+            .any(|d| d == "no-cache" || d == "no-store")
+        {
+            return Some(Duration::ZERO);
+        }
+        if let Some(secs) = directives.iter().find_map(|d| {
+            d.strip_prefix("max-age=")?
+                .trim_matches('"')
+                .parse::<u64>()
+                .ok()
+        }) {
+            return Some(Duration::from_secs(secs));
+        }
+    }
+    expires_in(res)
+}
+
+#[cfg(feature = "fetch")]
+fn expires_in(res: &Response) -> Option<Duration> {
+    let expires = httpdate::parse_http_date(res.header("expires")?).ok();
+    // relative to the site's clock rather than ours
+    let date = res
+        .header("date")
+        .and_then(|d| httpdate::parse_http_date(d).ok())
+        .unwrap_or_else(SystemTime::now);
+    Some(
+        expires
+            .and_then(|e| e.duration_since(date).ok())
+            .unwrap_or_default(),
+    )
+}
+
+// http dates are only parsed with the `fetch` feature
+#[cfg(not(feature = "fetch"))]
+fn expires_in(_res: &Response) -> Option<Duration> {
+    None
+}
+
+/// Kept for as long as the [crate::Fetcher] is.
+#[derive(Debug, Default)]
+pub struct MemoryCache(RwLock<HashMap<String, CachedPage>>);
+
+impl From<HashMap<String, String>> for MemoryCache {
+    fn from(m: HashMap<String, String>) -> Self {
+        MemoryCache(RwLock::new(
+            m.into_iter()
+                .map(|(url, body)| (url, body.into()))
+                .collect(),
+        ))
+    }
+}
+
+#[async_trait]
+impl Cache for MemoryCache {
+    async fn get(&self, url: &str) -> Option<CachedPage> {
+        self.0.read().unwrap().get(url).cloned()
+    }
+    async fn put(&self, url: &str, page: &CachedPage) {
+        self.0
+            .write()
+            .unwrap()
+            .insert(url.to_string(), page.clone());
+    }
+}
+
+/// A bounded in-memory cache, evicting the least used pages, see [moka::future::Cache].
+#[cfg(feature = "moka")]
+#[derive(Clone)]
+pub struct MokaCache(moka::future::Cache<String, CachedPage>);
+
+#[cfg(feature = "moka")]
+impl MokaCache {
+    /// holding up to `max_capacity` pages
+    pub fn new(max_capacity: u64) -> Self {
+        MokaCache(moka::future::Cache::new(max_capacity))
+    }
+}
+
+#[cfg(feature = "moka")]
+impl From<moka::future::Cache<String, CachedPage>> for MokaCache {
+    fn from(c: moka::future::Cache<String, CachedPage>) -> Self {
+        MokaCache(c)
+    }
+}
+
+#[cfg(feature = "moka")]
+impl fmt::Debug for MokaCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MokaCache")
+            .field(&self.0.entry_count())
+            .finish()
+    }
+}
+
+#[cfg(feature = "moka")]
+#[async_trait]
+impl Cache for MokaCache {
+    async fn get(&self, url: &str) -> Option<CachedPage> {
+        self.0.get(url).await
+    }
+    async fn put(&self, url: &str, page: &CachedPage) {
+        self.0.insert(url.to_string(), page.clone()).await
+    }
+}
+
+/// A file per page in a directory, so they are kept across runs.
+/// It's read and written with [tokio::fs], so it needs a tokio runtime, and isn't there on wasm.
+/// The validators are kept next to it, in a file of `etag: ...` and `last-modified: ...` lines,
+/// along with the `url: ...` it redirected to, and when it was fetched and for how long it can be
+/// kept, as `fetched-at: ...` and `max-age: ...` seconds.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct DirCache {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DirCache {
+    /// The directory is created on the first [Cache::put].
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        DirCache {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+    // urls aren't valid file names, so they're hashed
+    fn path(&self, url: &str, extension: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.{extension}", fnv1a(url.as_bytes())))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Cache for DirCache {
+    async fn get(&self, url: &str) -> Option<CachedPage> {
+        let body = tokio::fs::read_to_string(self.path(url, "html"))
+            .await
+            .ok()?;
+        let validators = tokio::fs::read_to_string(self.path(url, "validators"))
+            .await
+            .unwrap_or_default();
+        let validator = |name: &str| {
+            validators
+                .lines()
+                .find_map(|l| l.strip_prefix(name)?.strip_prefix(": "))
+                .map(str::to_string)
+        };
+        Some(CachedPage {
+            body,
+            etag: validator("etag"),
+            last_modified: validator("last-modified"),
+            url: validator("url"),
+            fetched_at: validator("fetched-at")
+                .and_then(|s| s.parse().ok())
+                .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+            max_age: validator("max-age")
+                .and_then(|s| s.parse().ok())
+                .map(Duration::from_secs),
+        })
+    }
+    async fn put(&self, url: &str, page: &CachedPage) {
+        let secs = |d: Duration| d.as_secs().to_string();
+        let fetched_at = page
+            .fetched_at
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(secs);
+        let validators: String = [
+            ("etag", &page.etag),
+            ("last-modified", &page.last_modified),
+            ("url", &page.url),
+            ("fetched-at", &fetched_at),
+            ("max-age", &page.max_age.map(secs)),
+        ]
+        .iter()
+        .filter_map(|(name, v)| v.as_ref().map(|v| format!("{name}: {v}\n")))
+        .collect();
+        let written = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(self.path(url, "html"), &page.body).await?;
+            tokio::fs::write(self.path(url, "validators"), validators).await
+        };
+        if let Err(e) = written.await {
+            error!("failed to cache {} in {:?}: {}", url, self.dir, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dir_cache() {
+        let dir = std::env::temp_dir().join(format!("dir_cache_{}", std::process::id()));
+        let cache = DirCache::new(&dir);
+        assert_eq!(cache.get("https://example.com/a").await, None);
+        let a = CachedPage {
+            body: "<html>a</html>".to_string(),
+            etag: Some(r#"W/"123""#.to_string()),
+            last_modified: None,
+            url: Some("https://www.example.com/a".to_string()),
+            fetched_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            max_age: Some(Duration::from_secs(600)),
+        };
+        cache.put("https://example.com/a", &a).await;
+        cache
+            .put(
+                "https://example.com/b",
+                &"<html>b</html>".to_string().into(),
+            )
+            .await;
+        assert_eq!(
+            DirCache::new(&dir).get("https://example.com/a").await,
+            Some(a)
+        );
+        assert!(!cache
+            .get("https://example.com/b")
+            .await
+            .unwrap()
+            .has_validators());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cache_policy() {
+        let now = SystemTime::now();
+        let page = CachedPage {
+            fetched_at: Some(now - Duration::from_secs(3600)),
+            max_age: Some(Duration::from_secs(600)),
+            ..Default::default()
+        };
+        assert!(CachePolicy::default().is_fresh(&page, now));
+        let respecting = CachePolicy {
+            respect_headers: true,
+            ..Default::default()
+        };
+        assert!(!respecting.is_fresh(&page, now));
+        assert!(respecting.is_fresh(&page, now - Duration::from_secs(3500)));
+        let day = CachePolicy {
+            max_ttl: Some(Duration::from_secs(86400)),
+            ..Default::default()
+        };
+        assert!(day.is_fresh(&page, now));
+        assert!(!day.is_fresh(&page, now + Duration::from_secs(86400)));
+        // not fetched by us
+        assert!(respecting.is_fresh(&"<html></html>".to_string().into(), now));
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn test_max_age() {
+        let mut res = Response::ok("");
+        assert_eq!(max_age(&res), None);
+        res.headers = vec![
+            (
+                "Date".to_string(),
+                "Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+            ),
+            (
+                "Expires".to_string(),
+                "Wed, 21 Oct 2015 08:28:00 GMT".to_string(),
+            ),
+        ];
+        assert_eq!(max_age(&res), Some(Duration::from_secs(3600)));
+        res.headers[1].1 = "0".to_string();
+        assert_eq!(max_age(&res), Some(Duration::ZERO));
+        res.headers.push((
+            "cache-control".to_string(),
+            "public, max-age=300, stale-while-revalidate=60".to_string(),
+        ));
+        assert_eq!(max_age(&res), Some(Duration::from_secs(300)));
+        res.headers[2].1 = "max-age=300, no-cache".to_string();
+        assert_eq!(max_age(&res), Some(Duration::ZERO));
+    }
+}
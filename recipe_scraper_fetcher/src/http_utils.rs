@@ -26,8 +26,8 @@ impl ReqwestOtelSpanBackend for TimeTrace {
     }
 }
 
-pub fn http_client() -> ClientWithMiddleware {
-    ClientBuilder::new(reqwest::Client::new())
+pub fn http_client(client: reqwest::Client) -> ClientWithMiddleware {
+    ClientBuilder::new(client)
         .with(reqwest_tracing::TracingMiddleware::<TimeTrace>::new())
         .build()
 }
@@ -0,0 +1,136 @@
+//! Downloading recipes' images, for keeping them along with the recipe.
+use recipe_scraper::{ScrapeError, ScrapedRecipe};
+use reqwest::{header::CONTENT_TYPE, Url};
+
+use crate::{transport::Request, Fetcher};
+
+/// A downloaded [ScrapedRecipe::image].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipeImage {
+    /// where it was downloaded from
+    pub url: String,
+    pub bytes: Vec<u8>,
+    /// the `Content-Type` header, e.g. `image/jpeg`
+    pub content_type: Option<String>,
+}
+
+impl Fetcher {
+    /// Downloads `recipe`'s image, `None` if it doesn't have one.
+    /// It's requested like pages are, with the same client, rate limit and retries,
+    /// but isn't cached, as the cache only keeps pages.
+    #[tracing::instrument(skip(recipe), fields(url = %recipe.url))]
+    pub async fn fetch_image(
+        &self,
+        recipe: &ScrapedRecipe,
+    ) -> Result<Option<RecipeImage>, ScrapeError> {
+        let url = match image_url(recipe) {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+        let request = Request {
+            url: url.clone(),
+            headers: vec![],
+        };
+        let r = self.send(&request, None).await?;
+        if !r.status().is_success() {
+            return Err(ScrapeError::Status {
+                url,
+                status: r.status().as_u16(),
+            });
+        }
+        let content_type = r
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let bytes = r
+            .bytes()
+            .await
+            .map_err(|e| ScrapeError::Http(e.to_string()))?;
+        Ok(Some(RecipeImage {
+            url,
+            bytes: bytes.to_vec(),
+            content_type,
+        }))
+    }
+}
+
+// sites sometimes give it relative to the page
+fn image_url(recipe: &ScrapedRecipe) -> Option<String> {
+    let image = recipe.image.as_deref().filter(|i| !i.is_empty())?;
+    let base = recipe.canonical_url.as_deref().unwrap_or(&recipe.url);
+    match Url::parse(base).and_then(|base| base.join(image)) {
+        Ok(url) => Some(url.to_string()),
+        Err(_) => Some(image.to_string()),
+    }
+}
+
+#[cfg(feature = "thumbnail")]
+impl RecipeImage {
+    /// A JPEG of it scaled down to fit in `max_size` by `max_size` pixels, keeping its aspect ratio.
+    pub fn thumbnail(&self, max_size: u32) -> Result<RecipeImage, ScrapeError> {
+        let parse_error = |e: image::ImageError| ScrapeError::Parse(format!("{}: {e}", self.url));
+        let image = image::load_from_memory(&self.bytes).map_err(parse_error)?;
+        let thumbnail = image.thumbnail(max_size, max_size).into_rgb8();
+        let mut bytes = std::io::Cursor::new(vec![]);
+        thumbnail
+            .write_to(&mut bytes, image::ImageFormat::Jpeg)
+            .map_err(parse_error)?;
+        Ok(RecipeImage {
+            url: self.url.clone(),
+            bytes: bytes.into_inner(),
+            content_type: Some("image/jpeg".to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fetch_image() {
+        let mut recipe = ScrapedRecipe {
+            url: "https://example.com/recipes/toast".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(Fetcher::new().fetch_image(&recipe).await.unwrap(), None);
+
+        recipe.image = Some("/images/toast.jpg".to_string());
+        assert_eq!(
+            image_url(&recipe),
+            Some("https://example.com/images/toast.jpg".to_string())
+        );
+        recipe.image = Some("https://doesnotresolve.com/toast.jpg".to_string());
+        assert!(matches!(
+            Fetcher::new().fetch_image(&recipe).await,
+            Err(ScrapeError::Http(_))
+        ));
+    }
+
+    #[cfg(feature = "thumbnail")]
+    #[test]
+    fn test_thumbnail() {
+        let mut png = std::io::Cursor::new(vec![]);
+        image::RgbImage::new(400, 200)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let image = RecipeImage {
+            url: "https://example.com/toast.png".to_string(),
+            bytes: png.into_inner(),
+            content_type: Some("image/png".to_string()),
+        };
+        let thumbnail = image.thumbnail(100).unwrap();
+        assert_eq!(thumbnail.content_type.as_deref(), Some("image/jpeg"));
+        let decoded = image::load_from_memory(&thumbnail.bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (100, 50));
+        assert!(matches!(
+            RecipeImage {
+                bytes: b"not an image".to_vec(),
+                ..image
+            }
+            .thumbnail(100),
+            Err(ScrapeError::Parse(_))
+        ));
+    }
+}
@@ -1,72 +1,654 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::SystemTime,
+};
 
-use recipe_scraper::ScrapeError;
-use tracing::error;
+use recipe_scraper::{ScrapeError, Scraper};
+#[cfg(feature = "fetch")]
+use tracing::warn;
+use tracing::{debug, error};
 
+pub mod batch;
+#[cfg(feature = "browser")]
+mod browser;
+#[cfg(feature = "fetch")]
+mod builder;
+pub mod cache;
+#[cfg(feature = "fetch")]
 mod http_utils;
+#[cfg(feature = "fetch")]
+pub mod image;
+#[cfg(feature = "fetch")]
+mod rate_limit;
+pub mod record;
+#[cfg(feature = "fetch")]
+pub mod retry;
+#[cfg(feature = "fetch")]
+mod robots;
+pub mod sitemap;
+mod telemetry;
+pub mod transport;
 
+#[cfg(feature = "fetch")]
+pub use builder::{FetcherBuilder, DEFAULT_USER_AGENT};
+use cache::{Cache, CachePolicy, CachedPage, MemoryCache};
+use transport::{Request, Transport};
+
+/// How [Fetcher::scrape_url_with] uses the cache for a single page.
+/// ```
+/// use recipe_scraper_fetcher::ScrapeOptions;
+/// // the site updated the recipe
+/// let options = ScrapeOptions::new().force_refresh(true);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ScrapeOptions {
+    /// fetches the page even when it's cached, still keeping it there
+    pub force_refresh: bool,
+    /// only scrapes the page from the cache, failing when it isn't there instead of fetching it,
+    /// unless `force_refresh` is set too
+    pub cache_only: bool,
+}
+
+impl ScrapeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+    pub fn cache_only(mut self, cache_only: bool) -> Self {
+        self.cache_only = cache_only;
+        self
+    }
+}
+
+/// Fetches pages and scrapes them, see [recipe_scraper::Scraper].
+/// Without the `fetch` feature there's no http client, and only pages in the cache
+/// or from a [Fetcher::with_transport] can be scraped, for wasm or for bodies that were already fetched.
 #[derive(Debug)]
 pub struct Fetcher {
+    #[cfg(feature = "fetch")]
     client: reqwest_middleware::ClientWithMiddleware,
-    cache: Option<HashMap<String, String>>,
+    #[cfg(feature = "fetch")]
+    config: FetcherBuilder,
+    // clients for the proxies of [Fetcher::scrape_url_via]
+    #[cfg(feature = "fetch")]
+    proxied: std::sync::Mutex<HashMap<String, reqwest_middleware::ClientWithMiddleware>>,
+    cache: Option<Box<dyn Cache>>,
+    revalidate: bool,
+    cache_policy: CachePolicy,
+    // scraped stale, for [Fetcher::refresh_stale]
+    stale: Mutex<HashSet<String>>,
+    #[cfg(feature = "fetch")]
+    retry: retry::RetryPolicy,
+    #[cfg(feature = "fetch")]
+    rate_limit: Option<rate_limit::RateLimiter>,
+    // by origin, when they're respected
+    #[cfg(feature = "fetch")]
+    robots: Option<std::sync::Mutex<HashMap<String, std::sync::Arc<robots::Robots>>>>,
+    #[cfg(feature = "browser")]
+    renderer: Option<browser::Renderer>,
+    scraper: Scraper,
+    // whether pages' AMP and print versions are scraped first
+    lighter: bool,
+    // instead of the http client
+    transport: Option<Box<dyn Transport>>,
+    // where responses are saved, for [Fetcher::record]
+    recordings: Option<record::Recordings>,
 }
 impl Fetcher {
+    #[cfg(feature = "fetch")]
+    pub fn new() -> Self {
+        Self::builder()
+            .build()
+            .expect("failed to build the http client")
+    }
+    #[cfg(not(feature = "fetch"))]
     pub fn new() -> Self {
         Fetcher {
-            client: http_utils::http_client(),
             cache: None,
+            revalidate: false,
+            cache_policy: CachePolicy::default(),
+            stale: Default::default(),
+            scraper: Scraper::default(),
+            lighter: false,
+            transport: None,
+            recordings: None,
         }
     }
+    /// For setting the user agent, timeouts or headers.
+    #[cfg(feature = "fetch")]
+    pub fn builder() -> FetcherBuilder {
+        FetcherBuilder::default()
+    }
+    /// Pages in `m` are scraped instead of being fetched, see [Fetcher::with_cache].
+    /// To test how pages are fetched as well, give it a [Fetcher::with_transport] of them instead.
     pub fn new_with_cache(m: HashMap<String, String>) -> Self {
-        Fetcher {
-            client: http_utils::http_client(),
-            cache: Some(m),
+        Self::new().with_cache(MemoryCache::from(m))
+    }
+    /// Looks pages up in `cache` before fetching them, and keeps the ones it fetches there,
+    /// e.g. a [cache::DirCache] to keep them across runs.
+    pub fn with_cache(mut self, cache: impl Cache + 'static) -> Self {
+        self.cache = Some(Box::new(cache));
+        self
+    }
+    /// Fetches pages with `transport` instead of its http client, e.g. canned pages in tests,
+    /// or another client or middleware. The rate limit, retries, robots.txt and headers
+    /// it was built with are left to the transport, which also works without the `fetch` feature.
+    /// Images are still downloaded with its http client.
+    /// ```
+    /// use std::collections::HashMap;
+    /// use recipe_scraper_fetcher::Fetcher;
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let pages = HashMap::from([(
+    ///     "https://example.com/toast".to_string(),
+    ///     "<title>Toast</title>".to_string(),
+    /// )]);
+    /// let fetcher = Fetcher::new().with_transport(pages);
+    /// let outcome = fetcher.scrape_url_outcome("https://example.com/toast").await.unwrap();
+    /// assert_eq!(outcome.recipe.name, "Toast");
+    /// assert!(fetcher.scrape_url("https://example.com/tea").await.is_err());
+    /// # });
+    /// ```
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+    /// Saves every response it fetches to `dir`, with its headers and when it was fetched,
+    /// to be scraped again offline with [Fetcher::replay], see [record::Recordings].
+    /// `304 Not Modified`s aren't, as they have no body.
+    pub fn record(mut self, dir: impl AsRef<std::path::Path>) -> Self {
+        self.recordings = Some(record::Recordings::new(dir));
+        self
+    }
+    /// Fetches pages only from the responses [Fetcher::record] saved in `dir`,
+    /// failing for the ones that weren't. It replaces any [Fetcher::with_transport].
+    pub fn replay(self, dir: impl AsRef<std::path::Path>) -> Self {
+        self.with_transport(record::Recordings::new(dir))
+    }
+    /// Asks the site whether cached pages have changed, with their `ETag` or `Last-Modified`,
+    /// instead of always using them. Unchanged pages aren't downloaded again.
+    pub fn revalidate(mut self, revalidate: bool) -> Self {
+        self.revalidate = revalidate;
+        self
+    }
+    /// When cached pages go stale and are fetched again, e.g. once the `Cache-Control: max-age`
+    /// of their response has passed. By default they're kept for good.
+    /// ```
+    /// use std::time::Duration;
+    /// use recipe_scraper_fetcher::{cache::{CachePolicy, DirCache}, Fetcher};
+    /// let fetcher = Fetcher::new()
+    ///     .with_cache(DirCache::new("recipes"))
+    ///     .cache_policy(CachePolicy {
+    ///         respect_headers: true,
+    ///         max_ttl: Some(Duration::from_secs(7 * 24 * 60 * 60)),
+    ///         stale_while_revalidate: true,
+    ///     });
+    /// ```
+    pub fn cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+    /// Fetches the pages that were scraped stale, with [CachePolicy::stale_while_revalidate],
+    /// again, so that they're fresh the next time. Returns how many were,
+    /// the ones that couldn't be keep being scraped stale until the next call.
+    pub async fn refresh_stale(&self) -> usize {
+        let urls: Vec<String> = self.stale.lock().unwrap().drain().collect();
+        let mut refreshed = 0;
+        for url in urls {
+            let cached = match &self.cache {
+                Some(cache) => cache.get(&url).await,
+                None => None,
+            };
+            match self.fetch_page(&url, cached, None).await {
+                Ok(page) => {
+                    if let Some(cache) = &self.cache {
+                        cache.put(&url, &page).await;
+                    }
+                    refreshed += 1;
+                }
+                Err(e) => tracing::warn!("failed to refresh {}: {}", url, e),
+            }
         }
+        refreshed
+    }
+    /// How requests that were throttled or timed out are retried, [retry::RetryPolicy::default] if unset.
+    #[cfg(feature = "fetch")]
+    pub fn retry(mut self, policy: retry::RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+    /// Makes at most `per_second` requests to each host, waiting for a turn when there have been more.
+    /// Zero, a negative number or NaN turns the limit off.
+    #[cfg(feature = "fetch")]
+    pub fn rate_limit(mut self, per_second: f64) -> Self {
+        self.rate_limit = rate_limit::RateLimiter::new(per_second);
+        self
+    }
+    /// Checks each site's `robots.txt` before fetching from it, failing with
+    /// [ScrapeError::RobotsDisallowed] for the pages it doesn't allow. They're fetched once per site;
+    /// while a site's responds with a server error nothing is fetched from it, and it's tried again
+    /// next time, and when it can't be reached at all the fetch fails with [ScrapeError::Http].
+    #[cfg(feature = "fetch")]
+    pub fn respect_robots(mut self, respect: bool) -> Self {
+        self.robots = respect.then(Default::default);
+        self
+    }
+    /// Renders pages from `domains` (and their subdomains) in a headless Chromium when their html
+    /// has no recipe in it, for sites that add their ld+json with javascript.
+    /// The browser is launched the first time it's needed, and needs Chrome or Chromium to be installed.
+    #[cfg(feature = "browser")]
+    pub fn render_with_browser<S: Into<String>>(
+        mut self,
+        domains: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.renderer = Some(browser::Renderer::new(
+            domains.into_iter().map(Into::into).collect(),
+            &self.config.user_agent,
+        ));
+        self
+    }
+    /// Scrapes with `scraper` instead of the default one, e.g. to add a [recipe_scraper::SiteScraper].
+    pub fn with_scraper(mut self, scraper: Scraper) -> Self {
+        self.scraper = scraper;
+        self
+    }
+    /// Scrapes the AMP or print version of pages that link to one instead, falling back to
+    /// the page itself when they don't have the recipe, see [recipe_scraper::lighter_variants].
+    /// They often have cleaner ld+json, but are another request.
+    pub fn prefer_lighter_pages(mut self, prefer: bool) -> Self {
+        self.lighter = prefer;
+        self
     }
     #[tracing::instrument(name = "scrape_url")]
     pub async fn scrape_url(
         &self,
         url: &str,
     ) -> Result<recipe_scraper::ScrapedRecipe, ScrapeError> {
-        let body = self.fetch_html(url).await?;
-        recipe_scraper::scrape(body.as_ref(), url)
+        self.scrape_url_with(url, &ScrapeOptions::default()).await
+    }
+
+    /// Like [Fetcher::scrape_url], but refreshing the cached page or only scraping it from the cache,
+    /// see [ScrapeOptions].
+    /// ```
+    /// use std::collections::HashMap;
+    /// use recipe_scraper_fetcher::{Fetcher, ScrapeOptions};
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let url = "https://example.com/toast";
+    /// let fetcher = Fetcher::new_with_cache(HashMap::new());
+    /// let cached = ScrapeOptions::new().cache_only(true);
+    /// assert!(fetcher.scrape_url_with(url, &cached).await.is_err());
+    /// # });
+    /// ```
+    #[tracing::instrument(name = "scrape_url_with")]
+    pub async fn scrape_url_with(
+        &self,
+        url: &str,
+        options: &ScrapeOptions,
+    ) -> Result<recipe_scraper::ScrapedRecipe, ScrapeError> {
+        let (r, page) = self
+            .scrape_page(url, None, options, |body| self.scraper.scrape(body, url))
+            .await?;
+        Ok(redirected(r, url, &page))
+    }
+
+    /// Like [Fetcher::scrape_url], but keeps what it could find on pages without a recipe,
+    /// see [recipe_scraper::Scraper::scrape_outcome].
+    #[tracing::instrument(name = "scrape_url_outcome")]
+    pub async fn scrape_url_outcome(
+        &self,
+        url: &str,
+    ) -> Result<recipe_scraper::ScrapeOutcome, ScrapeError> {
+        let (mut outcome, page) = self
+            .scrape_page(url, None, &ScrapeOptions::default(), |body| {
+                self.scraper.scrape_outcome(body, url)
+            })
+            .await?;
+        outcome.recipe = redirected(outcome.recipe, url, &page);
+        Ok(outcome)
+    }
+
+    /// See [recipe_scraper::scrape_all].
+    #[tracing::instrument(name = "scrape_all_url")]
+    pub async fn scrape_all_url(
+        &self,
+        url: &str,
+    ) -> Result<Vec<recipe_scraper::ScrapedRecipe>, ScrapeError> {
+        let (recipes, page) = self
+            .scrape_page(url, None, &ScrapeOptions::default(), |body| {
+                self.scraper.scrape_all(body, url)
+            })
+            .await?;
+        Ok(recipes
+            .into_iter()
+            .map(|r| redirected(r, url, &page))
+            .collect())
+    }
+
+    /// Like [Fetcher::scrape_url], but through `proxy` rather than the one it was built with,
+    /// see [FetcherBuilder::proxy].
+    #[cfg(feature = "fetch")]
+    #[tracing::instrument(name = "scrape_url_via")]
+    pub async fn scrape_url_via(
+        &self,
+        url: &str,
+        proxy: &str,
+    ) -> Result<recipe_scraper::ScrapedRecipe, ScrapeError> {
+        let (r, page) = self
+            .scrape_page(url, Some(proxy), &ScrapeOptions::default(), |body| {
+                self.scraper.scrape(body, url)
+            })
+            .await?;
+        Ok(redirected(r, url, &page))
+    }
+
+    // fetches the page and scrapes it, or its rendered html when there's no recipe in it
+    // and its site is rendered
+    async fn scrape_page<T>(
+        &self,
+        url: &str,
+        proxy: Option<&str>,
+        options: &ScrapeOptions,
+        scrape: impl Fn(&str) -> Result<T, ScrapeError>,
+    ) -> Result<(T, CachedPage), ScrapeError> {
+        let res = match self.fetch_with(url, proxy, options).await {
+            Ok(page) => match self
+                .scrape_lighter(&page, url, proxy, options, &scrape)
+                .await
+            {
+                Some(r) => Ok((r, page)),
+                None => match scrape(&page.body) {
+                    #[cfg(feature = "browser")]
+                    Err(e) if self.renderer.as_ref().is_some_and(|r| r.renders(url)) => {
+                        warn!("rendering {} in the browser: {}", url, e);
+                        match self.renderer.as_ref().unwrap().render(url).await {
+                            Ok(body) => scrape(&body).map(|r| (r, page)),
+                            Err(e) => Err(e),
+                        }
+                    }
+                    res => res.map(|r| (r, page)),
+                },
+            },
+            Err(e) => Err(e),
+        };
+        telemetry::scraped(url, &res);
+        res
+    }
+
+    // the first of the page's AMP and print versions with a recipe, when they're preferred
+    async fn scrape_lighter<T>(
+        &self,
+        page: &CachedPage,
+        url: &str,
+        proxy: Option<&str>,
+        options: &ScrapeOptions,
+        scrape: &impl Fn(&str) -> Result<T, ScrapeError>,
+    ) -> Option<T> {
+        if !self.lighter {
+            return None;
+        }
+        for variant in recipe_scraper::lighter_variants(&page.body, url) {
+            match self.fetch_with(&variant, proxy, options).await {
+                Ok(p) => match scrape(&p.body) {
+                    Ok(r) => return Some(r),
+                    Err(e) => debug!("no recipe in {}: {}", variant, e),
+                },
+                Err(e) => debug!("failed to fetch {}: {}", variant, e),
+            }
+        }
+        None
     }
 
     #[tracing::instrument]
-    async fn fetch_html(&self, url: &str) -> Result<String, ScrapeError> {
+    async fn fetch_with(
+        &self,
+        url: &str,
+        proxy: Option<&str>,
+        options: &ScrapeOptions,
+    ) -> Result<CachedPage, ScrapeError> {
+        let cached = match &self.cache {
+            Some(_) if options.force_refresh => None,
+            Some(cache) => {
+                let cached = cache.get(url).await;
+                telemetry::cache_lookup(url, cached.is_some());
+                cached
+            }
+            None => None,
+        };
+        if let Some(page) = &cached {
+            let fresh = self.cache_policy.is_fresh(page, SystemTime::now());
+            if fresh && !(self.revalidate && page.has_validators()) {
+                return Ok(page.clone());
+            }
+            if !fresh && self.cache_policy.stale_while_revalidate {
+                self.stale.lock().unwrap().insert(url.to_string());
+                return Ok(page.clone());
+            }
+            if options.cache_only {
+                return Ok(page.clone());
+            }
+        }
+        if options.cache_only && !options.force_refresh {
+            return Err(ScrapeError::Http(format!("{url} is not cached")));
+        }
+        let page = self.fetch_page(url, cached, proxy).await?;
+        // so that it isn't fetched again
         if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get(url) {
-                return Ok(cached.to_string());
+            cache.put(url, &page).await;
+        }
+        Ok(page)
+    }
+
+    // a request conditional on `cached` having changed
+    #[cfg_attr(not(feature = "fetch"), allow(unused_variables))]
+    async fn fetch_page(
+        &self,
+        url: &str,
+        cached: Option<CachedPage>,
+        proxy: Option<&str>,
+    ) -> Result<CachedPage, ScrapeError> {
+        let mut request = Request {
+            url: url.to_string(),
+            headers: vec![],
+        };
+        if let Some(page) = &cached {
+            if let Some(etag) = &page.etag {
+                request
+                    .headers
+                    .push(("If-None-Match".to_string(), etag.clone()));
+            }
+            if let Some(last_modified) = &page.last_modified {
+                request
+                    .headers
+                    .push(("If-Modified-Since".to_string(), last_modified.clone()));
             }
         }
 
-        let r = match self
-            .client
-            .get(url)
-            .header("user-agent", "recipe")
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                return Err(match e {
-                    reqwest_middleware::Error::Middleware(e) => panic!("{}", e),
-                    reqwest_middleware::Error::Reqwest(e) => ScrapeError::Http(e.to_string()),
+        let start = std::time::Instant::now();
+        let res = match &self.transport {
+            Some(transport) => transport.fetch(&request).await,
+            #[cfg(feature = "fetch")]
+            None => match self.send(&request, proxy).await {
+                Ok(r) => Ok(transport::from_reqwest(r).await),
+                Err(e) => Err(e),
+            },
+            #[cfg(not(feature = "fetch"))]
+            None => {
+                return cached.ok_or_else(|| {
+                    ScrapeError::Http(format!(
+                        "{url} is not cached, and the `fetch` feature is disabled"
+                    ))
                 })
             }
         };
-        if !r.status().is_success() {
-            let err_string = r.error_for_status_ref().unwrap_err().to_string();
-            error!(
-                "failed to fetch {}: {}",
-                url,
-                r.text().await.unwrap_or_default()
+        let status = res.as_ref().ok().map(|r| r.status);
+        telemetry::fetched(url, start.elapsed(), status);
+        let r = res?;
+        if let Some(recordings) = self.recordings.as_ref().filter(|_| r.status != 304) {
+            recordings.put(url, &r);
+        }
+        if r.status == 304 {
+            if let Some(page) = cached {
+                // fresh again
+                return Ok(CachedPage {
+                    fetched_at: Some(SystemTime::now()),
+                    max_age: cache::max_age(&r),
+                    ..page
+                });
+            }
+        }
+        if !(200..300).contains(&r.status) {
+            error!("failed to fetch {}: {}", url, r.body);
+            // bot challenges are usually a 403 or 503
+            return Err(
+                recipe_scraper::detect_wall(&r.body, url).unwrap_or(ScrapeError::Status {
+                    url: url.to_string(),
+                    status: r.status,
+                }),
             );
-            let e = Err(ScrapeError::Http(err_string));
-            return e;
         }
-        Ok(r.text().await.unwrap())
+        let header = |name| r.header(name).map(str::to_string);
+        Ok(CachedPage {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+            url: r.url.clone().filter(|u| u != url),
+            fetched_at: Some(SystemTime::now()),
+            max_age: cache::max_age(&r),
+            body: r.body,
+        })
+    }
+
+    // sends `request` as the site's robots.txt, the rate limit and the retry policy allow
+    #[cfg(feature = "fetch")]
+    async fn send(
+        &self,
+        request: &Request,
+        proxy: Option<&str>,
+    ) -> Result<reqwest::Response, ScrapeError> {
+        let url = request.url.as_str();
+        if !self.robots_allow(url).await? {
+            return Err(ScrapeError::RobotsDisallowed(url.to_string()));
+        }
+
+        let client = self.client_via(proxy)?;
+        let mut attempt = 0;
+        let res = loop {
+            let mut req = client.get(url).headers(self.config.headers_for(url));
+            for (name, value) in &request.headers {
+                req = req.header(name, value);
+            }
+            if let Some(limiter) = &self.rate_limit {
+                limiter.wait(url).await;
+            }
+            let res = req.send().await;
+
+            let wait = match &res {
+                Ok(r) if retry::is_transient(r.status()) => {
+                    Some(retry::retry_after(r.headers()).unwrap_or(self.retry.backoff(attempt)))
+                }
+                Err(reqwest_middleware::Error::Reqwest(e)) if e.is_timeout() => {
+                    Some(self.retry.backoff(attempt))
+                }
+                _ => None,
+            };
+            attempt += 1;
+            match wait {
+                Some(wait)
+                    if attempt < self.retry.max_attempts && wait <= self.retry.max_backoff =>
+                {
+                    warn!("retrying {} in {:?}, attempt {}", url, wait, attempt);
+                    tokio::time::sleep(wait).await;
+                }
+                _ => break res,
+            }
+        };
+        match res {
+            Ok(r) => Ok(r),
+            Err(e) => Err(match e {
+                reqwest_middleware::Error::Middleware(e) => panic!("{}", e),
+                reqwest_middleware::Error::Reqwest(e) => ScrapeError::Http(e.to_string()),
+            }),
+        }
+    }
+
+    #[cfg(feature = "fetch")]
+    fn client_via(
+        &self,
+        proxy: Option<&str>,
+    ) -> Result<reqwest_middleware::ClientWithMiddleware, ScrapeError> {
+        let proxy = match proxy {
+            Some(p) => p,
+            None => return Ok(self.client.clone()),
+        };
+        let mut proxied = self.proxied.lock().unwrap();
+        if let Some(client) = proxied.get(proxy) {
+            return Ok(client.clone());
+        }
+        let client = self.config.client(Some(proxy))?;
+        proxied.insert(proxy.to_string(), client.clone());
+        Ok(client)
+    }
+
+    // whether the site's robots.txt allows fetching `url`, always when it isn't respected
+    #[cfg(feature = "fetch")]
+    async fn robots_allow(&self, url: &str) -> Result<bool, ScrapeError> {
+        let Some(cache) = self.robots.as_ref() else {
+            return Ok(true);
+        };
+        let Ok(url) = reqwest::Url::parse(url) else {
+            return Ok(true);
+        };
+        let origin = url.origin().ascii_serialization();
+        let cached = cache.lock().unwrap().get(&origin).cloned();
+        let robots = match cached {
+            Some(r) => r,
+            None => {
+                if let Some(limiter) = &self.rate_limit {
+                    limiter.wait(url.as_str()).await;
+                }
+                let res = self
+                    .client
+                    .get(format!("{origin}/robots.txt"))
+                    .send()
+                    .await
+                    .map_err(|e| ScrapeError::Http(e.to_string()))?;
+                // a site without one allows everything, one that's down allows nothing
+                // until it's back, so that isn't kept
+                let status = res.status();
+                let r = std::sync::Arc::new(match status {
+                    s if s.is_success() => robots::Robots::parse(
+                        &res.text().await.unwrap_or_default(),
+                        &self.config.user_agent,
+                    ),
+                    s if s.is_client_error() => robots::Robots::default(),
+                    _ => robots::Robots::disallow_all(),
+                });
+                if !status.is_server_error() {
+                    cache.lock().unwrap().insert(origin, r.clone());
+                }
+                r
+            }
+        };
+        let path = match url.query() {
+            Some(q) => format!("{}?{q}", url.path()),
+            None => url.path().to_string(),
+        };
+        Ok(robots.allows(&path))
+    }
+}
+
+// where the page was redirected to, if it doesn't say where it lives itself
+fn redirected(
+    mut r: recipe_scraper::ScrapedRecipe,
+    url: &str,
+    page: &CachedPage,
+) -> recipe_scraper::ScrapedRecipe {
+    if r.canonical_url.is_none() {
+        r.canonical_url = page.url.clone().filter(|u| u != url);
     }
+    r
 }
 
 impl Default for Fetcher {
@@ -79,6 +661,14 @@ impl Default for Fetcher {
 mod tests {
     use super::*;
 
+    // a page with nothing but a recipe named `name`
+    fn recipe(name: &str) -> String {
+        format!(
+            r#"<script type="application/ld+json">{{"@type": "Recipe", "name": "{name}",
+            "recipeIngredient": ["1 slice bread"], "recipeInstructions": "Toast it."}}</script>"#
+        )
+    }
+
     #[tokio::test]
     async fn scrape_errors() {
         assert!(matches!(
@@ -94,10 +684,187 @@ mod tests {
                 "https://doesnotresolve.com".to_string(),
                 "foo".to_string(),
             )]))
-            .fetch_html("https://doesnotresolve.com")
+            .fetch_with(
+                "https://doesnotresolve.com",
+                None,
+                &ScrapeOptions::default()
+            )
             .await
-            .unwrap(),
+            .unwrap()
+            .body,
+            "foo"
+        );
+    }
+
+    #[tokio::test]
+    async fn lighter_pages() {
+        let url = "https://example.com/toast/";
+        let page = format!(
+            r#"<link rel="amphtml" href="/toast/amp/">{}<a href="/print/toast">Print</a>"#,
+            recipe("Toast | Example.com")
+        );
+        let fetcher = Fetcher::new().with_transport(HashMap::from([
+            (url.to_string(), page),
+            (
+                "https://example.com/toast/amp/".to_string(),
+                "<p>Subscribe to see this recipe</p>".to_string(),
+            ),
+            (
+                "https://example.com/print/toast".to_string(),
+                recipe("Toast"),
+            ),
+        ]));
+        assert_eq!(
+            fetcher.scrape_url(url).await.unwrap().name,
+            "Toast | Example.com"
+        );
+        let fetcher = fetcher.prefer_lighter_pages(true);
+        let r = fetcher.scrape_url(url).await.unwrap();
+        assert_eq!(r.name, "Toast");
+        assert_eq!(r.url, url);
+    }
+
+    #[tokio::test]
+    async fn blocked_pages() {
+        #[derive(Debug)]
+        struct Challenge;
+        #[async_trait::async_trait]
+        impl Transport for Challenge {
+            async fn fetch(&self, _: &Request) -> Result<transport::Response, ScrapeError> {
+                Ok(transport::Response {
+                    status: 403,
+                    body: "<title>Attention Required! | Cloudflare</title>".to_string(),
+                    ..Default::default()
+                })
+            }
+        }
+        let fetcher = Fetcher::new().with_transport(Challenge);
+        assert!(matches!(
+            fetcher.scrape_url("https://example.com").await,
+            Err(ScrapeError::Blocked { .. })
+        ));
+        let fetcher = Fetcher::new().with_transport(HashMap::new());
+        assert_eq!(
+            fetcher
+                .scrape_url("https://example.com")
+                .await
+                .unwrap_err()
+                .status(),
+            Some(404)
+        );
+    }
+
+    #[tokio::test]
+    async fn scrape_options() {
+        let url = "https://example.com/toast";
+        let cache = MemoryCache::default();
+        cache
+            .put(
+                url,
+                &CachedPage {
+                    body: recipe("Toast"),
+                    ..Default::default()
+                },
+            )
+            .await;
+        let fetcher = Fetcher::new()
+            .with_cache(cache)
+            .with_transport(HashMap::from([(url.to_string(), recipe("Better toast"))]));
+        let cache_only = ScrapeOptions::new().cache_only(true);
+        assert_eq!(fetcher.scrape_url(url).await.unwrap().name, "Toast");
+        let refresh = ScrapeOptions::new().force_refresh(true);
+        assert_eq!(
+            fetcher.scrape_url_with(url, &refresh).await.unwrap().name,
+            "Better toast"
+        );
+        // and it's kept
+        assert_eq!(
+            fetcher
+                .scrape_url_with(url, &cache_only)
+                .await
+                .unwrap()
+                .name,
+            "Better toast"
+        );
+        assert!(fetcher
+            .scrape_url_with("https://example.com/tea", &cache_only)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        // its own, so a run that failed before cleaning up isn't replayed
+        let dir = std::env::temp_dir().join(format!("record_and_replay_{}", std::process::id()));
+        let url = "https://example.com/toast";
+        let fetcher = Fetcher::new()
+            .with_transport(HashMap::from([(
+                url.to_string(),
+                "<title>Toast</title>".to_string(),
+            )]))
+            .record(&dir);
+        let recorded = fetcher.scrape_url_outcome(url).await.unwrap().recipe;
+        assert!(fetcher.scrape_url("https://example.com/tea").await.is_err());
+
+        let fetcher = Fetcher::new().replay(&dir);
+        assert_eq!(
+            fetcher.scrape_url_outcome(url).await.unwrap().recipe,
+            recorded
+        );
+        // the site's 404, rather than failing to find it
+        assert_eq!(
+            fetcher
+                .scrape_url("https://example.com/tea")
+                .await
+                .unwrap_err()
+                .status(),
+            Some(404)
+        );
+        assert!(matches!(
+            fetcher.scrape_url("https://example.com/cake").await,
+            Err(ScrapeError::Http(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "fetch")]
+    #[tokio::test]
+    async fn stale_pages() {
+        let url = "https://doesnotresolve.com";
+        let cache = MemoryCache::default();
+        let page = CachedPage {
+            body: "foo".to_string(),
+            fetched_at: Some(SystemTime::now() - std::time::Duration::from_secs(3600)),
+            max_age: Some(std::time::Duration::from_secs(60)),
+            ..Default::default()
+        };
+        cache.put(url, &page).await;
+        let policy = CachePolicy {
+            respect_headers: true,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::new()
+            .with_cache(cache)
+            .cache_policy(policy.clone());
+        // fetched again, rather than scraped from the cache
+        assert!(fetcher
+            .fetch_with(url, None, &ScrapeOptions::default())
+            .await
+            .is_err());
+
+        let fetcher = fetcher.cache_policy(CachePolicy {
+            stale_while_revalidate: true,
+            ..policy
+        });
+        assert_eq!(
+            fetcher
+                .fetch_with(url, None, &ScrapeOptions::default())
+                .await
+                .unwrap()
+                .body,
             "foo"
         );
+        // it still doesn't resolve
+        assert_eq!(fetcher.refresh_stale().await, 0);
     }
 }
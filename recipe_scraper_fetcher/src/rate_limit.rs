@@ -0,0 +1,65 @@
+//! Spacing out requests to the same site, so that batch imports don't hammer it.
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+/// Allows up to a number of requests per second to each host, see [crate::Fetcher::rate_limit].
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    // when each host can next be requested
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    /// `None` for no limit at all, when `per_second` isn't more than zero.
+    pub(crate) fn new(per_second: f64) -> Option<Self> {
+        (per_second > 0.0).then(|| RateLimiter {
+            interval: Duration::from_secs_f64(1.0 / per_second),
+            next: Mutex::new(HashMap::new()),
+        })
+    }
+    /// Waits until `url`'s host can be requested again.
+    pub(crate) async fn wait(&self, url: &str) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let at = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            let at = next.get(&host).map_or(now, |n| (*n).max(now));
+            next.insert(host, at + self.interval);
+            at
+        };
+        tokio::time::sleep_until(at).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let limiter = RateLimiter::new(20.0).unwrap();
+        let start = Instant::now();
+        limiter.wait("https://example.com/a").await;
+        limiter.wait("https://other.com/a").await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        limiter.wait("https://example.com/b").await;
+        limiter.wait("https://example.com/c").await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_no_limit() {
+        assert!(RateLimiter::new(0.0).is_none());
+        assert!(RateLimiter::new(-1.0).is_none());
+        assert!(RateLimiter::new(f64::NAN).is_none());
+        assert_eq!(
+            RateLimiter::new(f64::INFINITY).unwrap().interval,
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Responses saved as they're fetched, to scrape them again offline in the same way,
+//! see [crate::Fetcher::record] and [crate::Fetcher::replay].
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use recipe_scraper::{fnv1a, ScrapeError};
+use tracing::error;
+
+use crate::transport::{Request, Response, Transport};
+
+/// A directory of responses by url, each a `.html` file of its body and a `.headers` file of
+/// its url, status, when it was recorded and its headers. They can be shared along with
+/// the urls, for others to scrape the same pages.
+///
+/// As a [Transport] it replays them, failing with [ScrapeError::Http] for the urls that
+/// weren't recorded rather than fetching them.
+/// ```
+/// use recipe_scraper_fetcher::{record::Recordings, transport::Response, Fetcher};
+/// # let dir = std::env::temp_dir().join("recipe_scraper_fetcher_record_doctest");
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let url = "https://example.com/toast";
+/// Recordings::new(&dir).put(url, &Response::ok("<title>Toast</title>"));
+/// let fetcher = Fetcher::new().replay(&dir);
+/// let outcome = fetcher.scrape_url_outcome(url).await.unwrap();
+/// assert_eq!(outcome.recipe.name, "Toast");
+/// assert!(fetcher.scrape_url("https://example.com/tea").await.is_err());
+/// # });
+/// # std::fs::remove_dir_all(dir).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recordings {
+    dir: PathBuf,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    pub response: Response,
+    pub recorded_at: Option<SystemTime>,
+}
+
+impl Recordings {
+    /// The directory is created on the first [Recordings::put].
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Recordings {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+    // named like a [crate::cache::DirCache]'s, but not meant to be mixed with one
+    fn path(&self, url: &str, extension: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.{extension}", fnv1a(url.as_bytes())))
+    }
+    /// The response recorded for `url`, if any.
+    pub fn get(&self, url: &str) -> Option<Recording> {
+        let body = std::fs::read_to_string(self.path(url, "html")).ok()?;
+        let headers = std::fs::read_to_string(self.path(url, "headers")).ok()?;
+        // the recording's own fields, then a blank line and the response's headers
+        let (fields, headers) = headers.split_once("\n\n").unwrap_or((&headers, ""));
+        let field = |name: &str| {
+            fields
+                .lines()
+                .find_map(|l| l.strip_prefix(name)?.strip_prefix(": "))
+        };
+        Some(Recording {
+            response: Response {
+                status: field("status")?.parse().ok()?,
+                url: field("redirected-to").map(str::to_string),
+                headers: headers
+                    .lines()
+                    .filter_map(|l| l.split_once(": "))
+                    .map(|(n, v)| (n.to_string(), v.to_string()))
+                    .collect(),
+                body,
+            },
+            recorded_at: field("recorded-at")
+                .and_then(|s| s.parse().ok())
+                .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+        })
+    }
+    /// Records `response` as the one for `url`, replacing any earlier one.
+    pub fn put(&self, url: &str, response: &Response) {
+        let recorded_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let mut headers = format!(
+            "url: {url}\nstatus: {}\nrecorded-at: {recorded_at}\n",
+            response.status
+        );
+        if let Some(redirected) = &response.url {
+            headers.push_str(&format!("redirected-to: {redirected}\n"));
+        }
+        headers.push('\n');
+        for (name, value) in &response.headers {
+            headers.push_str(&format!("{name}: {value}\n"));
+        }
+        if let Err(e) = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(self.path(url, "html"), &response.body))
+            .and_then(|_| std::fs::write(self.path(url, "headers"), headers))
+        {
+            error!("failed to record {} in {:?}: {}", url, self.dir, e);
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for Recordings {
+    async fn fetch(&self, request: &Request) -> Result<Response, ScrapeError> {
+        match self.get(&request.url) {
+            Some(recording) => Ok(recording.response),
+            None => Err(ScrapeError::Http(format!(
+                "{} was not recorded in {:?}",
+                request.url, self.dir
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recordings() {
+        let dir = std::env::temp_dir().join(format!("recordings_{}", std::process::id()));
+        let recordings = Recordings::new(&dir);
+        assert_eq!(recordings.get("https://example.com/a"), None);
+        let a = Response {
+            status: 200,
+            url: Some("https://www.example.com/a".to_string()),
+            headers: vec![
+                ("content-type".to_string(), "text/html".to_string()),
+                ("etag".to_string(), r#"W/"123""#.to_string()),
+            ],
+            body: "<html>a</html>\n\n".to_string(),
+        };
+        recordings.put("https://example.com/a", &a);
+        let recording = Recordings::new(&dir).get("https://example.com/a").unwrap();
+        assert_eq!(recording.response, a);
+        assert!(recording.recorded_at.unwrap() <= SystemTime::now());
+
+        let gone = Response {
+            status: 404,
+            ..Default::default()
+        };
+        recordings.put("https://example.com/b", &gone);
+        assert_eq!(
+            recordings.get("https://example.com/b").unwrap().response,
+            gone
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
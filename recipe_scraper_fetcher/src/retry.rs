@@ -0,0 +1,117 @@
+//! Retrying requests that failed for reasons that might not last,
+//! such as a site's CDN throttling us.
+use std::time::{Duration, SystemTime};
+
+use reqwest::{header::RETRY_AFTER, StatusCode};
+
+/// How failed requests are retried, see [crate::Fetcher::retry].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// including the first, `1` to not retry
+    pub max_attempts: u32,
+    /// waited before the first retry, doubling after each one
+    pub backoff: Duration,
+    /// the longest to wait, a `Retry-After` longer than it gives up instead
+    pub max_backoff: Duration,
+    /// waits up to half as long at random, so that throttled fetches don't all retry at once
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// fails on the first error
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+    /// how long to wait before the `retry`th retry, counting from 0
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        match self.jitter {
+            true => backoff.mul_f64(1.0 - random() / 2.0),
+            false => backoff,
+        }
+    }
+}
+
+/// responses that are worth trying again
+pub(crate) fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// how long the site asked us to wait, either in seconds or until a date
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()
+            .map(|at| at.duration_since(SystemTime::now()).unwrap_or_default()),
+    }
+}
+
+// from 0 to 1, good enough for spreading out retries
+fn random() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    f64::from(nanos % 1000) / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(20), Duration::from_secs(30));
+        let jittered = RetryPolicy::default().backoff(1);
+        assert!(jittered <= Duration::from_secs(1) && jittered >= Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient(StatusCode::NOT_FOUND));
+    }
+}
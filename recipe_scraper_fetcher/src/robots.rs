@@ -0,0 +1,150 @@
+//! Which paths a site's `robots.txt` lets us fetch, see [crate::Fetcher::respect_robots].
+//! Follows [RFC 9309](https://www.rfc-editor.org/rfc/rfc9309): the group for our user agent
+//! (or `*`) applies, and the longest matching rule wins, `Allow` on a tie.
+
+/// The rules from a `robots.txt` that apply to us.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Robots {
+    // (allowed, path pattern)
+    rules: Vec<(bool, String)>,
+}
+
+impl Robots {
+    /// everything is disallowed, for when the `robots.txt` couldn't be fetched
+    pub(crate) fn disallow_all() -> Self {
+        Robots {
+            rules: vec![(false, "/".to_string())],
+        }
+    }
+
+    pub(crate) fn parse(robots_txt: &str, user_agent: &str) -> Self {
+        // groups name the product token, the `recipe` of `recipe/1.0 (+https://...)`
+        let token = user_agent
+            .split(|c: char| c == '/' || c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let is_us = |agent: &str| agent != "*" && agent == token;
+        // rules of the groups naming us, and of the `*` ones
+        let (mut ours, mut any) = (vec![], vec![]);
+        let mut agents: Vec<String> = vec![];
+        let mut in_rules = false;
+        for line in robots_txt.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let (key, value) = match line.split_once(':') {
+                Some((k, v)) => (k.trim().to_lowercase(), v.trim()),
+                None => continue,
+            };
+            match key.as_str() {
+                "user-agent" => {
+                    // a user-agent after rules starts a new group
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // an empty `Disallow:` allows everything
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (key == "allow", value.to_string());
+                    if agents.iter().any(|a| is_us(a)) {
+                        ours.push(rule);
+                    } else if agents.iter().any(|a| a == "*") {
+                        any.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let named_us = robots_txt.lines().any(|l| {
+            l.split_once(':').is_some_and(|(k, v)| {
+                k.trim().eq_ignore_ascii_case("user-agent") && is_us(&v.trim().to_lowercase())
+            })
+        });
+        Robots {
+            rules: if named_us { ours } else { any },
+        }
+    }
+
+    /// whether `path` (with its query) may be fetched
+    pub(crate) fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+// `*` matches anything and a trailing `$` anchors the end, otherwise patterns are prefixes
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match path.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        // the last part has to be at the end when anchored
+        if anchored && i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots() {
+        let txt = "
+User-agent: *
+Disallow: /private/
+Allow: /private/recipes/
+Disallow: /*.pdf$
+
+User-agent: BadBot
+User-agent: OtherBot
+Disallow: /
+";
+        let robots = Robots::parse(txt, "recipe");
+        assert!(robots.allows("/2018/04/pad-thai/"));
+        assert!(!robots.allows("/private/notes"));
+        assert!(robots.allows("/private/recipes/toast"));
+        assert!(!robots.allows("/menus/dinner.pdf"));
+        assert!(robots.allows("/menus/dinner.pdf?page=2"));
+
+        let bad = Robots::parse(txt, "BadBot/1.0");
+        assert!(!bad.allows("/2018/04/pad-thai/"));
+        // only the whole product token names us
+        let other = Robots::parse(txt, "badbot-fork/2.0 (compatible; OtherBot)");
+        assert!(other.allows("/2018/04/pad-thai/"));
+        let named = Robots::parse("User-agent: Recipe\nDisallow: /\n", "recipe/1.0");
+        assert!(!named.allows("/anything"));
+        let not_named = Robots::parse("User-agent: rec\nDisallow: /\n", "recipe/1.0");
+        assert!(not_named.allows("/anything"));
+
+        let named = Robots::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: recipe\nDisallow:\n",
+            "recipe",
+        );
+        assert!(named.allows("/anything"));
+        assert!(Robots::parse("", "recipe").allows("/"));
+        assert!(!Robots::disallow_all().allows("/"));
+    }
+}
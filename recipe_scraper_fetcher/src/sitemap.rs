@@ -0,0 +1,207 @@
+//! Finding a site's recipes from its `sitemap.xml`, for importing a whole blog.
+use std::collections::HashSet;
+
+use recipe_scraper::ScrapeError;
+use tracing::warn;
+
+use crate::{batch::BatchResults, Fetcher, ScrapeOptions};
+
+// sitemap indexes can point at other indexes, but not forever
+const MAX_DEPTH: usize = 3;
+
+impl Fetcher {
+    /// The urls in `domain`'s `sitemap.xml` (following sitemap indexes) that look like recipes,
+    /// see [looks_like_recipe]. `domain` can be a bare host like `smittenkitchen.com`, which is fetched over https.
+    /// The sitemaps are always fetched again rather than read from the cache, as they change
+    /// whenever a recipe is posted. Gzipped sitemaps (`sitemap.xml.gz`) aren't supported, and are skipped.
+    pub async fn sitemap_urls(&self, domain: &str) -> Result<Vec<String>, ScrapeError> {
+        let root = match domain.contains("://") {
+            true => domain.trim_end_matches('/').to_string(),
+            false => format!("https://{}", domain.trim_end_matches('/')),
+        };
+        let mut sitemaps = vec![(format!("{root}/sitemap.xml"), 0)];
+        let refresh = ScrapeOptions::new().force_refresh(true);
+        let (mut seen, mut urls) = (HashSet::new(), vec![]);
+        while let Some((sitemap, depth)) = sitemaps.pop() {
+            if !seen.insert(sitemap.clone()) {
+                continue;
+            }
+            if is_gzipped(&sitemap) {
+                warn!("skipping gzipped sitemap {}", sitemap);
+                continue;
+            }
+            let page = match self.fetch_with(&sitemap, None, &refresh).await {
+                Ok(page) => page,
+                // only the top one has to be there
+                Err(e) if depth > 0 => {
+                    warn!("skipping sitemap {}: {}", sitemap, e);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let locs = locs(&page.body);
+            if page.body.contains("<sitemapindex") {
+                if depth < MAX_DEPTH {
+                    // reversed so that they're crawled in order
+                    sitemaps.extend(locs.into_iter().rev().map(|l| (l, depth + 1)));
+                }
+            } else {
+                urls.extend(locs.into_iter().filter(|u| looks_like_recipe(u)));
+            }
+        }
+        let mut unique = HashSet::new();
+        urls.retain(|u| unique.insert(u.clone()));
+        Ok(urls)
+    }
+
+    /// Scrapes the recipes in `domain`'s sitemap, see [Fetcher::sitemap_urls] and [Fetcher::scrape_urls].
+    #[tracing::instrument(name = "crawl_sitemap")]
+    pub async fn crawl_sitemap(
+        &self,
+        domain: &str,
+        concurrency: usize,
+    ) -> Result<BatchResults, ScrapeError> {
+        let urls = self.sitemap_urls(domain).await?;
+        Ok(self.scrape_urls(urls, concurrency).await)
+    }
+}
+
+// `sitemap.xml.gz`, which would need decompressing
+fn is_gzipped(url: &str) -> bool {
+    url.split(['?', '#'])
+        .next()
+        .is_some_and(|path| path.to_lowercase().ends_with(".gz"))
+}
+
+// the `<loc>`s of a sitemap or sitemap index, there's no need for a whole xml parser
+fn locs(xml: &str) -> Vec<String> {
+    xml.split("<loc>")
+        .skip(1)
+        .filter_map(|s| s.split_once("</loc>"))
+        .map(|(loc, _)| {
+            let loc = loc.trim();
+            let loc = loc
+                .strip_prefix("<![CDATA[")
+                .and_then(|l| l.strip_suffix("]]>"))
+                .unwrap_or(loc);
+            loc.trim()
+                .replace("&amp;", "&")
+                .replace("&apos;", "'")
+                .replace("&quot;", "\"")
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+        })
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+// first path segments (or their first words) of pages that list recipes or are about the site
+const NOT_RECIPES: &[&str] = &[
+    "about",
+    "author",
+    "category",
+    "contact",
+    "feed",
+    "page",
+    "privacy",
+    "search",
+    "shop",
+    "tag",
+    "wp-content",
+];
+
+/// Whether `url` is probably a recipe: it mentions one, or it's a post with a slug
+/// rather than an index, an image or a page about the site.
+pub fn looks_like_recipe(url: &str) -> bool {
+    let path = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').skip(1).filter(|s| !s.is_empty()).collect();
+    let (first, last) = match (segments.first(), segments.last()) {
+        (Some(first), Some(last)) => (first.to_lowercase(), last.to_lowercase()),
+        _ => return false,
+    };
+    if NOT_RECIPES
+        .iter()
+        .any(|n| first == *n || first.starts_with(&format!("{n}-")))
+        || (last.contains('.') && !last.ends_with(".html") && !last.ends_with(".htm"))
+    {
+        return false;
+    }
+    match first.as_str() {
+        "recipes" | "recipe" if segments.len() == 1 => false,
+        _ => path.to_lowercase().contains("recipe") || last.contains('-'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::MemoryCache;
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_looks_like_recipe() {
+        assert!(looks_like_recipe(
+            "https://smittenkitchen.com/2018/04/crispy-tofu-pad-thai/"
+        ));
+        assert!(looks_like_recipe("https://example.com/recipes/toast"));
+        assert!(looks_like_recipe("https://example.com/recipe/12345"));
+        assert!(!looks_like_recipe("https://example.com/"));
+        assert!(!looks_like_recipe("https://example.com/recipes/"));
+        assert!(!looks_like_recipe("https://example.com/about-us"));
+        assert!(!looks_like_recipe("https://example.com/tag/quick-dinners/"));
+        assert!(!looks_like_recipe(
+            "https://example.com/wp-content/uploads/pad-thai.jpg"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sitemap_urls() {
+        let index = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/post-sitemap.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/missing-sitemap.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/page-sitemap.xml.gz</loc></sitemap>
+</sitemapindex>"#;
+        let posts = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/</loc></url>
+  <url><loc><![CDATA[https://example.com/crispy-tofu-pad-thai/]]></loc></url>
+  <url><loc>https://example.com/recipes/toast?a=1&amp;b=2</loc></url>
+  <url><loc>https://example.com/crispy-tofu-pad-thai/</loc></url>
+  <url><loc>https://example.com/category/dinner/</loc></url>
+</urlset>"#;
+        // what the sitemap said before the latest posts
+        let cached = MemoryCache::from(HashMap::from([(
+            "https://example.com/post-sitemap.xml".to_string(),
+            "<urlset></urlset>".to_string(),
+        )]));
+        let fetcher = Fetcher::new()
+            .with_cache(cached)
+            .with_transport(HashMap::from([
+                (
+                    "https://example.com/sitemap.xml".to_string(),
+                    index.to_string(),
+                ),
+                (
+                    "https://example.com/post-sitemap.xml".to_string(),
+                    posts.to_string(),
+                ),
+            ]));
+        assert_eq!(
+            fetcher.sitemap_urls("example.com").await.unwrap(),
+            vec![
+                "https://example.com/crispy-tofu-pad-thai/",
+                "https://example.com/recipes/toast?a=1&b=2",
+            ]
+        );
+        assert!(fetcher.sitemap_urls("https://other.com/").await.is_err());
+        assert!(is_gzipped("https://example.com/sitemap.xml.GZ?v=2"));
+        assert!(!is_gzipped("https://example.com/sitemap.xml"));
+    }
+}
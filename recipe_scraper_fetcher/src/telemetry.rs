@@ -0,0 +1,83 @@
+//! What the fetcher reports about itself, as tracing events and, with the `metrics` feature,
+//! as metrics labelled by the domain they're for.
+use std::time::Duration;
+
+use recipe_scraper::ScrapeError;
+use tracing::debug;
+
+/// Whether `url` was found in the cache.
+pub(crate) fn cache_lookup(url: &str, hit: bool) {
+    debug!(hit, "cache lookup for {}", url);
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "recipe_scraper_cache_lookups_total",
+        "domain" => domain(url),
+        "result" => if hit { "hit" } else { "miss" },
+    )
+    .increment(1);
+}
+
+/// How long a request to `url` took, and what it responded with.
+pub(crate) fn fetched(url: &str, elapsed: Duration, status: Option<u16>) {
+    debug!(?elapsed, ?status, "fetched {}", url);
+    #[cfg(feature = "metrics")]
+    metrics::histogram!("recipe_scraper_fetch_seconds", "domain" => domain(url))
+        .record(elapsed.as_secs_f64());
+}
+
+/// Whether a recipe could be scraped from `url`, for error rates by domain.
+pub(crate) fn scraped<T>(url: &str, res: &Result<T, ScrapeError>) {
+    let result = match res {
+        Ok(_) => "ok",
+        Err(e) => error_kind(e),
+    };
+    debug!(result, "scraped {}", url);
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "recipe_scraper_scrapes_total",
+        "domain" => domain(url),
+        "result" => result,
+    )
+    .increment(1);
+}
+
+/// the name of `e`'s variant, for grouping errors
+pub(crate) fn error_kind(e: &ScrapeError) -> &'static str {
+    match e {
+        ScrapeError::Http(_) => "Http",
+        ScrapeError::NoLDJSON(_) => "NoLDJSON",
+        ScrapeError::LDJSONMissingRecipe(_, _) => "LDJSONMissingRecipe",
+        ScrapeError::Deserialize(_) => "Deserialize",
+        ScrapeError::Parse(_) => "Parse",
+        ScrapeError::RobotsDisallowed(_) => "RobotsDisallowed",
+        ScrapeError::Io(_) => "Io",
+        ScrapeError::Status { .. } => "Status",
+        ScrapeError::Blocked { .. } => "Blocked",
+        ScrapeError::Paywalled { .. } => "Paywalled",
+    }
+}
+
+// the host of `url`, without needing a url parser when there's no `fetch`
+#[cfg(feature = "metrics")]
+fn domain(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    host.split(':').next().unwrap_or_default().to_lowercase()
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain() {
+        assert_eq!(
+            domain("https://www.Example.com/toast?a=1"),
+            "www.example.com"
+        );
+        assert_eq!(domain("http://user@example.com:8080"), "example.com");
+    }
+}
@@ -0,0 +1,109 @@
+//! How pages are fetched, for backing a [crate::Fetcher] with another http client or a queue,
+//! or with canned pages in tests, see [crate::Fetcher::with_transport].
+use std::{collections::HashMap, fmt};
+
+use async_trait::async_trait;
+use recipe_scraper::ScrapeError;
+
+/// Fetches pages for a [crate::Fetcher].
+/// ```
+/// use async_trait::async_trait;
+/// use recipe_scraper::ScrapeError;
+/// use recipe_scraper_fetcher::{
+///     transport::{Request, Response, Transport},
+///     Fetcher,
+/// };
+///
+/// #[derive(Debug)]
+/// struct Archive;
+///
+/// #[async_trait]
+/// impl Transport for Archive {
+///     async fn fetch(&self, request: &Request) -> Result<Response, ScrapeError> {
+///         let body = format!(
+///             r#"<script type="application/ld+json">{{"@type": "Recipe", "name": "{}",
+///             "recipeIngredient": ["1 cup tea"], "recipeInstructions": "Steep it."}}</script>"#,
+///             request.url
+///         );
+///         Ok(Response::ok(body))
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let fetcher = Fetcher::new().with_transport(Archive);
+/// let r = fetcher.scrape_url("https://example.com/tea").await.unwrap();
+/// assert_eq!(r.name, "https://example.com/tea");
+/// # });
+/// ```
+#[async_trait]
+pub trait Transport: Send + Sync + fmt::Debug {
+    /// Any response it gets, whatever its status, or [ScrapeError::Http] when there's none.
+    async fn fetch(&self, request: &Request) -> Result<Response, ScrapeError>;
+}
+
+/// A `GET` of a page.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Request {
+    pub url: String,
+    /// `If-None-Match` and `If-Modified-Since` for pages that were cached with validators,
+    /// as the [crate::Fetcher] leaves the others to the transport
+    pub headers: Vec<(String, String)>,
+}
+
+/// What a site responded with.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Response {
+    pub status: u16,
+    /// where it was redirected to, if anywhere
+    pub url: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// A `200 OK` of `body`, without any headers.
+    pub fn ok(body: impl Into<String>) -> Self {
+        Response {
+            status: 200,
+            body: body.into(),
+            ..Default::default()
+        }
+    }
+    /// The value of the header called `name`, in any case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Pages by url, with a `404` for the others, for tests.
+#[async_trait]
+impl Transport for HashMap<String, String> {
+    async fn fetch(&self, request: &Request) -> Result<Response, ScrapeError> {
+        Ok(match self.get(&request.url) {
+            Some(body) => Response::ok(body.clone()),
+            None => Response {
+                status: 404,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// [reqwest]'s response, reading its body.
+#[cfg(feature = "fetch")]
+pub(crate) async fn from_reqwest(r: reqwest::Response) -> Response {
+    let headers = r
+        .headers()
+        .iter()
+        .filter_map(|(n, v)| Some((n.to_string(), v.to_str().ok()?.to_string())))
+        .collect();
+    Response {
+        status: r.status().as_u16(),
+        url: Some(r.url().to_string()),
+        headers,
+        body: r.text().await.unwrap_or_default(),
+    }
+}